@@ -0,0 +1,60 @@
+//! Lookups, walks, and extraction on the generated tree, as a baseline for
+//! changes to how the tree is stored or searched.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use include_dir::DirEntry;
+use include_dir_benchmarks::{DEEP_DIR, DIRS, FILES, LARGE, LAST_FILE, MISSING_FILE};
+use tempdir::TempDir;
+
+fn lookups(c: &mut Criterion) {
+    assert!(LARGE.get_file(LAST_FILE).is_some());
+    assert!(LARGE.get_file(MISSING_FILE).is_none());
+    assert!(LARGE.get_dir(DEEP_DIR).is_some());
+
+    let mut group = c.benchmark_group("lookups");
+    group.bench_function("get_file, hit", |b| {
+        b.iter(|| LARGE.get_file(black_box(LAST_FILE)))
+    });
+    group.bench_function("get_file, miss", |b| {
+        b.iter(|| LARGE.get_file(black_box(MISSING_FILE)))
+    });
+    group.bench_function("get_dir, deep", |b| {
+        b.iter(|| LARGE.get_dir(black_box(DEEP_DIR)))
+    });
+    group.finish();
+}
+
+fn walk(c: &mut Criterion) {
+    let (files, dirs) = count(LARGE.walk());
+    assert_eq!((files, dirs), (FILES, DIRS));
+
+    c.bench_function("walk", |b| b.iter(|| count(black_box(LARGE).walk())));
+}
+
+fn extract(c: &mut Criterion) {
+    let mut group = c.benchmark_group("extract");
+    // every iteration writes 5,000 files
+    group.sample_size(10);
+    group.bench_function("into an empty directory", |b| {
+        b.iter_batched(
+            || TempDir::new("include_dir_bench").unwrap(),
+            |tmp| {
+                LARGE.extract(tmp.path()).unwrap();
+                // clean up outside of the measurement
+                tmp
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.finish();
+}
+
+fn count<'a>(entries: impl Iterator<Item = DirEntry<'a>>) -> (usize, usize) {
+    entries.fold((0, 0), |(files, dirs), entry| match entry {
+        DirEntry::File(_) => (files + 1, dirs),
+        DirEntry::Dir(_) => (files, dirs + 1),
+    })
+}
+
+criterion_group!(benches, lookups, walk, extract);
+criterion_main!(benches);
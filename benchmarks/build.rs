@@ -0,0 +1,63 @@
+//! Generates the large tree embedded by this crate into `$OUT_DIR/large`.
+//!
+//! The tree is 5,000 files spread evenly across 200 directories, three
+//! levels deep. Everything is derived from the file's position, so the
+//! contents are the same on every build and machine.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const SECTIONS: usize = 8;
+const GROUPS: usize = 4;
+const LEAVES: usize = 5;
+const FILES_PER_DIR: usize = 25;
+
+fn main() -> io::Result<()> {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let out_dir = std::env::var_os("OUT_DIR").expect("Cargo always sets OUT_DIR");
+    let root = Path::new(&out_dir).join("large");
+
+    // start from scratch so a change to the layout doesn't leave stale files
+    if root.exists() {
+        fs::remove_dir_all(&root)?;
+    }
+
+    let mut ix = 0;
+    for section in 0..SECTIONS {
+        let section = root.join(format!("section_{:02}", section));
+        ix = write_files(&section, ix)?;
+
+        for group in 0..GROUPS {
+            let group = section.join(format!("group_{:02}", group));
+            ix = write_files(&group, ix)?;
+
+            for leaf in 0..LEAVES {
+                ix = write_files(&group.join(format!("leaf_{:02}", leaf)), ix)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Fill `dir` with its share of the files, numbered from `ix`, returning
+/// the next number.
+fn write_files(dir: &Path, mut ix: usize) -> io::Result<usize> {
+    fs::create_dir_all(dir)?;
+
+    for i in 0..FILES_PER_DIR {
+        let path = dir.join(format!("file_{:03}.txt", i));
+        fs::write(path, contents(ix))?;
+        ix += 1;
+    }
+
+    Ok(ix)
+}
+
+/// Somewhere between a few bytes and 2 KiB of text, depending on `ix`.
+fn contents(ix: usize) -> String {
+    let line = format!("file {} of the generated tree\n", ix);
+    line.repeat(1 + ix * 7 % 64)
+}
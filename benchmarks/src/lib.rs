@@ -0,0 +1,36 @@
+//! A large generated tree for benchmarking `include_dir`.
+//!
+//! The build script writes 5,000 files across 200 nested directories into
+//! `$OUT_DIR/large`, and this crate embeds them. It lives outside the main
+//! workspace so the tree isn't embedded every time the tests are built.
+//!
+//! Run the benchmarks with `cargo bench` from this directory. Because all
+//! this crate does is embed the tree, building it on its own also measures
+//! how long the macro takes:
+//!
+//! ```console
+//! $ touch src/lib.rs
+//! $ cargo build --release --timings
+//! ```
+//!
+//! Touching `src/lib.rs` re-runs the macro without regenerating the tree.
+
+use include_dir::{include_dir, Dir};
+
+/// The generated tree.
+pub static LARGE: Dir<'_> = include_dir!("$OUT_DIR/large");
+
+/// A file at the bottom of the tree, which comes last in a walk.
+pub const LAST_FILE: &str = "section_07/group_03/leaf_04/file_024.txt";
+
+/// A file which would sort right next to [`LAST_FILE`] but doesn't exist.
+pub const MISSING_FILE: &str = "section_07/group_03/leaf_04/file_025.txt";
+
+/// One of the most deeply nested directories.
+pub const DEEP_DIR: &str = "section_07/group_03/leaf_04";
+
+/// How many files there are.
+pub const FILES: usize = 5_000;
+
+/// How many directories there are, not counting the root.
+pub const DIRS: usize = 200;
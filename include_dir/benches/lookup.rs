@@ -0,0 +1,74 @@
+//! Compares `Dir::get_file()` against the naive linear search it replaced,
+//! using a synthetic tree with a few thousand entries.
+//!
+//! See the `benchmarks` crate for benchmarks of a tree embedded by the macro.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use include_dir::{Dir, DirBuilder, File};
+use std::path::Path;
+
+const DIRS: usize = 20;
+const SUB_DIRS: usize = 10;
+const FILES: usize = 25;
+
+/// Build a tree with `DIRS * SUB_DIRS * FILES` files.
+fn synthetic_tree() -> Dir<'static> {
+    let mut builder = DirBuilder::new();
+
+    for d in 0..DIRS {
+        for s in 0..SUB_DIRS {
+            for f in 0..FILES {
+                let path = format!("dir_{:02}/sub_{:02}/file_{:03}.txt", d, s, f);
+                builder.add_file(path, &b""[..]).unwrap();
+            }
+        }
+    }
+
+    builder.build().leak()
+}
+
+/// The original implementation of `Dir::get_file()`, which visits every
+/// entry until it finds a match.
+fn linear_get_file<'a>(dir: &Dir<'a>, path: &Path) -> Option<File<'a>> {
+    for file in dir.files() {
+        if file.path() == path {
+            return Some(*file);
+        }
+    }
+
+    for dir in dir.dirs() {
+        if let Some(f) = linear_get_file(dir, path) {
+            return Some(f);
+        }
+    }
+
+    None
+}
+
+fn lookups(c: &mut Criterion) {
+    let tree = synthetic_tree();
+    let last = Path::new("dir_19/sub_09/file_024.txt");
+    let missing = Path::new("dir_19/sub_09/missing.txt");
+    assert!(tree.get_file(last).is_some());
+
+    let mut group = c.benchmark_group("get_file");
+    group.bench_function("binary search, hit", |b| {
+        b.iter(|| tree.get_file(black_box(last)))
+    });
+    group.bench_function("binary search, miss", |b| {
+        b.iter(|| tree.get_file(black_box(missing)))
+    });
+    group.bench_function("by reference, hit", |b| {
+        b.iter(|| tree.get_file_ref(black_box(last)))
+    });
+    group.bench_function("linear scan, hit", |b| {
+        b.iter(|| linear_get_file(&tree, black_box(last)))
+    });
+    group.bench_function("linear scan, miss", |b| {
+        b.iter(|| linear_get_file(&tree, black_box(missing)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, lookups);
+criterion_main!(benches);
@@ -0,0 +1,56 @@
+//! A minimal trait for code which wants "something holding embedded files"
+//! without caring which crate embedded them.
+
+use crate::dir::Dir;
+use crate::merge::MergedDir;
+use std::borrow::Cow;
+
+/// A collection of embedded files which can be looked up by path.
+///
+/// This has the same shape as the `get()` and `iter()` functions generated
+/// by `rust-embed`, so glue code written against that interface only needs
+/// to swap the type it calls them on. It works as a trait object, so an
+/// application can also hold a `&dyn EmbeddedAssets` and decide where its
+/// files come from at runtime.
+///
+/// Paths always use `/` as the separator.
+pub trait EmbeddedAssets {
+    /// The original contents of the file at `path`, or `None` if there is no
+    /// such file.
+    fn get(&self, path: &str) -> Option<Cow<'_, [u8]>>;
+
+    /// The path of every file, in sorted order.
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'_, str>> + '_>;
+}
+
+impl<'a> EmbeddedAssets for Dir<'a> {
+    /// This uses [`Dir::read_file()`], so files are decompressed and the
+    /// `hot-reload` feature is respected.
+    fn get(&self, path: &str) -> Option<Cow<'_, [u8]>> {
+        self.read_file(path)
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'_, str>> + '_> {
+        let files = self
+            .walk()
+            .filter_map(|entry| entry.as_file())
+            .map(|file| Cow::Borrowed(file.path));
+
+        Box::new(files)
+    }
+}
+
+impl<'a> EmbeddedAssets for MergedDir<'a> {
+    fn get(&self, path: &str) -> Option<Cow<'_, [u8]>> {
+        self.get_file(path).map(|file| file.uncompressed())
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = Cow<'_, str>> + '_> {
+        let files = self
+            .walk()
+            .filter_map(|entry| entry.as_file())
+            .map(|file| Cow::Borrowed(file.path));
+
+        Box::new(files)
+    }
+}
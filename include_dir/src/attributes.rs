@@ -0,0 +1,31 @@
+use crate::file::File;
+
+impl<'a> File<'a> {
+    /// Look up one of the attributes given to this file by the macro's
+    /// `attributes` manifest.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir};
+    ///
+    /// static ASSETS: Dir<'_> = include_dir!(
+    ///     "tests/fixtures/attributes/assets",
+    ///     attributes = "tests/fixtures/attributes/attributes.toml",
+    /// );
+    ///
+    /// let logo = ASSETS.get_file("img/logo.svg").unwrap();
+    /// assert_eq!(logo.attr("cache"), Some("public, max-age=31536000"));
+    /// assert_eq!(logo.attr("content-language"), None);
+    /// ```
+    pub fn attr(&self, key: &str) -> Option<&'a str> {
+        self.attrs
+            .binary_search_by(|(k, _)| (*k).cmp(key))
+            .ok()
+            .map(|ix| self.attrs[ix].1)
+    }
+
+    /// Every attribute given to this file by the macro's `attributes`
+    /// manifest, as `(key, value)` pairs sorted by key.
+    pub fn attrs(&self) -> &'a [(&'a str, &'a str)] {
+        self.attrs
+    }
+}
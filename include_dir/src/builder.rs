@@ -0,0 +1,301 @@
+use crate::dir::Dir;
+use crate::dir_entry::DirEntry;
+use crate::file::File;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Component, Path};
+
+/// Build a [`Dir`] at runtime, for tests or content which isn't known at
+/// compile time.
+///
+/// Paths are validated as they are added, and any missing parent
+/// directories are created automatically. Building gives an [`OwnedDir`],
+/// whose [`Dir`] works exactly like one created by `include_dir!()`.
+///
+/// ```rust
+/// use include_dir::DirBuilder;
+///
+/// let mut builder = DirBuilder::new();
+/// builder
+///     .add_file("templates/index.html", &b"<h1>Hello</h1>"[..])?
+///     .add_file("templates/partials/nav.html", b"<nav/>".to_vec())?
+///     .add_dir("logs")?;
+/// let owned = builder.build();
+/// let dir = owned.as_dir();
+///
+/// assert!(dir.get_file("templates/partials/nav.html").is_some());
+/// assert!(dir.get_dir("logs").unwrap().files().is_empty());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DirBuilder<'a> {
+    root: Node<'a>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Node<'a> {
+    // sorted by name, which gives the same order as sorting by path
+    files: BTreeMap<String, Cow<'a, [u8]>>,
+    dirs: BTreeMap<String, Node<'a>>,
+}
+
+impl<'a> DirBuilder<'a> {
+    /// Create an empty builder.
+    pub fn new() -> DirBuilder<'a> {
+        DirBuilder::default()
+    }
+
+    /// Start with a copy of everything in `dir`, using the same paths.
+    ///
+    /// File contents are borrowed rather than copied, unless they were
+    /// compressed.
+    pub fn from_dir(dir: &Dir<'a>) -> DirBuilder<'a> {
+        let mut builder = DirBuilder::new();
+
+        for entry in dir.walk() {
+            let result = match entry {
+                DirEntry::Dir(d) => builder.add_dir(d.path()).map(|_| ()),
+                DirEntry::File(f) => builder.add_file(f.path(), f.uncompressed()).map(|_| ()),
+            };
+            result.expect("Embedded paths are always valid");
+        }
+
+        builder
+    }
+
+    /// Add a file, replacing any file which is already at `path`.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] if `path` is empty,
+    /// absolute, contains `..`, or goes through an existing file.
+    pub fn add_file<P, C>(&mut self, path: P, contents: C) -> io::Result<&mut DirBuilder<'a>>
+    where
+        P: AsRef<Path>,
+        C: Into<Cow<'a, [u8]>>,
+    {
+        let path = path.as_ref();
+        let mut names = names(path)?;
+        let name = names.pop().ok_or_else(|| invalid(path))?;
+
+        let parent = self.root.dir_mut(&names).ok_or_else(|| invalid(path))?;
+        if parent.dirs.contains_key(&name) {
+            return Err(invalid(path));
+        }
+        parent.files.insert(name, contents.into());
+
+        Ok(self)
+    }
+
+    /// Add a directory, which may be empty.
+    ///
+    /// Adding a directory which already exists does nothing. Fails with
+    /// [`io::ErrorKind::InvalidInput`] under the same conditions as
+    /// [`DirBuilder::add_file()`].
+    pub fn add_dir<P: AsRef<Path>>(&mut self, path: P) -> io::Result<&mut DirBuilder<'a>> {
+        let path = path.as_ref();
+        let names = names(path)?;
+        if names.is_empty() {
+            return Err(invalid(path));
+        }
+
+        self.root.dir_mut(&names).ok_or_else(|| invalid(path))?;
+
+        Ok(self)
+    }
+
+    /// Turn the builder into an [`OwnedDir`], which holds everything the
+    /// tree needs and frees it when dropped.
+    pub fn build(self) -> OwnedDir<'a> {
+        let mut storage = Storage::default();
+        let root = self.root.build(String::new(), &mut storage);
+
+        OwnedDir { root, storage }
+    }
+}
+
+/// A tree created by [`DirBuilder::build()`], which owns its paths, file
+/// lists, and any contents which weren't borrowed.
+///
+/// [`Dir`] and [`File`] only hold references, so the tree is used through
+/// [`OwnedDir::as_dir()`], which borrows from the `OwnedDir`. Everything is
+/// freed when it is dropped, so trees can be rebuilt as often as needed.
+pub struct OwnedDir<'a> {
+    // everything borrowed from `storage` is really only valid for as long
+    // as `self` is, which `as_dir()` makes sure of
+    root: Dir<'a>,
+    storage: Storage<'a>,
+}
+
+impl<'a> OwnedDir<'a> {
+    /// The tree, borrowed from the `OwnedDir`.
+    pub fn as_dir(&self) -> Dir<'_> {
+        self.root
+    }
+
+    /// Give up ownership of the tree, so it lives for as long as the
+    /// borrowed contents do.
+    ///
+    /// The memory is never freed, which is fine for a tree built once at
+    /// startup but shouldn't be done over and over.
+    pub fn leak(self) -> Dir<'a> {
+        let root = self.root;
+        std::mem::forget(self.storage);
+        root
+    }
+}
+
+impl<'a> std::fmt::Debug for OwnedDir<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("OwnedDir").field(&self.root).finish()
+    }
+}
+
+/// The heap allocations an [`OwnedDir`]'s tree points into.
+///
+/// Only the outer `Vec`s are ever changed. Moving a `String` or `Vec` into
+/// them (or moving the `Storage` itself) doesn't move the buffer it owns, so
+/// borrows of those buffers stay valid until the `Storage` is dropped.
+#[derive(Default)]
+struct Storage<'a> {
+    strings: Vec<String>,
+    contents: Vec<Vec<u8>>,
+    files: Vec<Vec<File<'a>>>,
+    dirs: Vec<Vec<Dir<'a>>>,
+}
+
+impl<'a> Storage<'a> {
+    fn keep_str(&mut self, s: String) -> &'a str {
+        // SAFETY: the buffer is never changed and outlives every borrow
+        // handed out by the `OwnedDir` (see `Storage`)
+        let borrowed = unsafe { &*(s.as_str() as *const str) };
+        self.strings.push(s);
+        borrowed
+    }
+
+    fn keep<T>(list: &mut Vec<Vec<T>>, items: Vec<T>) -> &'a [T] {
+        // SAFETY: as above
+        let borrowed = unsafe { &*(items.as_slice() as *const [T]) };
+        list.push(items);
+        borrowed
+    }
+}
+
+impl<'a> Node<'a> {
+    /// Get the directory at `names`, creating it (and its parents) if they
+    /// don't exist. Returns `None` if a file is in the way.
+    fn dir_mut(&mut self, names: &[String]) -> Option<&mut Node<'a>> {
+        let mut node = self;
+
+        for name in names {
+            if node.files.contains_key(name) {
+                return None;
+            }
+            node = node.dirs.entry(name.clone()).or_default();
+        }
+
+        Some(node)
+    }
+
+    fn build(self, path: String, storage: &mut Storage<'a>) -> Dir<'a> {
+        let join = |name: &str| {
+            if path.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}/{}", path, name)
+            }
+        };
+
+        let files: Vec<File<'a>> = self
+            .files
+            .into_iter()
+            .map(|(name, contents)| build_file(join(&name), contents, storage))
+            .collect();
+        let dirs: Vec<Dir<'a>> = self
+            .dirs
+            .into_iter()
+            .map(|(name, node)| node.build(join(&name), storage))
+            .collect();
+
+        #[cfg(feature = "hash")]
+        let hash = crate::hash::hash_children(
+            files.iter().map(|f| (f.file_name(), &f.hash)),
+            dirs.iter().map(|d| (d.file_name(), &d.hash)),
+        );
+
+        Dir {
+            path: storage.keep_str(path),
+            files: Storage::keep(&mut storage.files, files),
+            dirs: Storage::keep(&mut storage.dirs, dirs),
+            #[cfg(feature = "hash")]
+            hash,
+            #[cfg(feature = "hot-reload")]
+            source: "",
+            #[cfg(feature = "source-info")]
+            source_path: "",
+            #[cfg(feature = "source-info")]
+            embedded_at: None,
+            #[cfg(feature = "build-id")]
+            build_id: "",
+        }
+    }
+}
+
+fn build_file<'a>(path: String, contents: Cow<'a, [u8]>, storage: &mut Storage<'a>) -> File<'a> {
+    let contents: &'a [u8] = match contents {
+        Cow::Borrowed(borrowed) => borrowed,
+        Cow::Owned(owned) => Storage::keep(&mut storage.contents, owned),
+    };
+
+    File {
+        path: storage.keep_str(path),
+        contents,
+        #[cfg(feature = "metadata")]
+        metadata: None,
+        #[cfg(feature = "hash")]
+        hash: crate::hash::hash_contents(contents),
+        #[cfg(feature = "compression")]
+        compressed: false,
+        #[cfg(feature = "attributes")]
+        attrs: &[],
+    }
+}
+
+/// Split a path into the names stored in the tree, rejecting anything which
+/// could never be looked up.
+fn names(path: &Path) -> io::Result<Vec<String>> {
+    let mut names = Vec::new();
+
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => {
+                let name = name.to_str().ok_or_else(|| invalid(path))?;
+
+                // backslashes are treated as separators when looking up paths
+                for name in name.split('\\').filter(|n| !n.is_empty()) {
+                    if name == ".." {
+                        return Err(invalid(path));
+                    }
+                    if name != "." {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+            Component::CurDir => {}
+            Component::RootDir | Component::Prefix(_) | Component::ParentDir => {
+                return Err(invalid(path))
+            }
+        }
+    }
+
+    Ok(names)
+}
+
+fn invalid(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!(
+            "\"{}\" isn't a valid path for an embedded file or directory",
+            path.display()
+        ),
+    )
+}
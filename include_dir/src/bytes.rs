@@ -0,0 +1,51 @@
+use crate::file::File;
+use ::bytes::Bytes;
+
+impl File<'static> {
+    /// The file's [raw contents](File::contents) as [`Bytes`], pointing at
+    /// the embedded data instead of copying it.
+    ///
+    /// This uses [`Bytes::from_static()`], so there is no allocation or
+    /// reference counting either, and cloning the `Bytes` is free.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir};
+    ///
+    /// static TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+    ///
+    /// let readme = TREE.get_file("README.md").unwrap();
+    /// let body = readme.as_bytes();
+    /// assert_eq!(body, readme.contents());
+    /// ```
+    pub fn as_bytes(&self) -> Bytes {
+        Bytes::from_static(self.contents)
+    }
+
+    /// The file's original contents as [`Bytes`], only allocating if the
+    /// file [was compressed](File::is_compressed).
+    #[cfg(feature = "compression")]
+    pub fn decompressed_bytes(&self) -> Bytes {
+        match self.decompressed_contents() {
+            std::borrow::Cow::Borrowed(contents) => Bytes::from_static(contents),
+            std::borrow::Cow::Owned(contents) => Bytes::from(contents),
+        }
+    }
+}
+
+impl<'a> File<'a> {
+    /// A copy of the file's [raw contents](File::contents) as [`Bytes`].
+    ///
+    /// This is for files which don't live for the rest of the program, like
+    /// ones created with a short-lived `DirBuilder`. Files embedded by
+    /// `include_dir!()` should use [`File::as_bytes()`], which doesn't
+    /// copy.
+    pub fn to_bytes(&self) -> Bytes {
+        Bytes::copy_from_slice(self.contents)
+    }
+}
+
+impl From<File<'static>> for Bytes {
+    fn from(file: File<'static>) -> Bytes {
+        file.as_bytes()
+    }
+}
@@ -0,0 +1,96 @@
+use crate::file::File;
+use miniz_oxide::inflate::stream::{inflate, InflateState};
+use miniz_oxide::{DataFormat, MZFlush, MZStatus};
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Formatter};
+use std::slice::Chunks;
+
+/// An iterator over a file's original contents in fixed-size pieces,
+/// created with [`File::decompressed_chunks()`].
+pub struct DecompressedChunks<'a> {
+    path: &'a str,
+    inner: Inner<'a>,
+}
+
+enum Inner<'a> {
+    Stored(Chunks<'a, u8>),
+    Compressed {
+        input: &'a [u8],
+        // boxed because it holds the 32 KB DEFLATE window
+        state: Box<InflateState>,
+        chunk_size: usize,
+        finished: bool,
+    },
+}
+
+impl<'a> DecompressedChunks<'a> {
+    pub(crate) fn new(file: &File<'a>, chunk_size: usize) -> Self {
+        let inner = if file.compressed {
+            Inner::Compressed {
+                input: file.contents,
+                state: InflateState::new_boxed(DataFormat::Raw),
+                chunk_size,
+                finished: false,
+            }
+        } else {
+            Inner::Stored(file.contents.chunks(chunk_size))
+        };
+
+        DecompressedChunks {
+            path: file.path,
+            inner,
+        }
+    }
+}
+
+impl<'a> Iterator for DecompressedChunks<'a> {
+    type Item = Cow<'a, [u8]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (input, state, chunk_size, finished) = match self.inner {
+            Inner::Stored(ref mut chunks) => return chunks.next().map(Cow::Borrowed),
+            Inner::Compressed {
+                ref mut input,
+                ref mut state,
+                chunk_size,
+                ref mut finished,
+            } => (input, state, chunk_size, finished),
+        };
+
+        let mut chunk = vec![0; chunk_size];
+        let mut filled = 0;
+
+        while filled < chunk_size && !*finished {
+            let result = inflate(state, input, &mut chunk[filled..], MZFlush::None);
+            *input = &input[result.bytes_consumed..];
+            filled += result.bytes_written;
+
+            match result.status {
+                Ok(MZStatus::StreamEnd) => *finished = true,
+                Ok(_) if result.bytes_consumed > 0 || result.bytes_written > 0 => {}
+                _ => panic!(
+                    "The embedded data for \"{}\" should always be valid DEFLATE",
+                    self.path
+                ),
+            }
+        }
+
+        if filled == 0 {
+            return None;
+        }
+
+        chunk.truncate(filled);
+        Some(Cow::Owned(chunk))
+    }
+}
+
+impl<'a> Debug for DecompressedChunks<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let compressed = matches!(self.inner, Inner::Compressed { .. });
+
+        f.debug_struct("DecompressedChunks")
+            .field("path", &self.path)
+            .field("compressed", &compressed)
+            .finish()
+    }
+}
@@ -0,0 +1,295 @@
+//! Lookups and sizes which can be evaluated at compile time.
+//!
+//! The lookups follow the same rules as [`Dir::get_file()`] and friends, but
+//! walk the path byte-by-byte instead of going through `Path` so they can be
+//! `const fn`s.
+
+use crate::dir::Dir;
+use crate::file::File;
+use core::cmp::Ordering;
+
+impl<'a> Dir<'a> {
+    /// A `const` version of [`Dir::get_file()`].
+    ///
+    /// This makes it possible to check that an asset exists while compiling,
+    /// so a typo becomes a compile error instead of a `None` at runtime.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir, File};
+    ///
+    /// const ASSETS: Dir<'_> = include_dir!(".");
+    /// const MANIFEST: File<'_> = match ASSETS.get_file_const("Cargo.toml") {
+    ///     Some(file) => file,
+    ///     None => panic!("Cargo.toml is missing"),
+    /// };
+    ///
+    /// assert_eq!(MANIFEST.path(), std::path::Path::new("Cargo.toml"));
+    /// ```
+    pub const fn get_file_const(&self, path: &str) -> Option<File<'a>> {
+        match self.find_const(path) {
+            Some(Found::File(file)) => Some(*file),
+            _ => None,
+        }
+    }
+
+    /// A `const` version of [`Dir::get_dir()`].
+    pub const fn get_dir_const(&self, path: &str) -> Option<Dir<'a>> {
+        match self.find_const(path) {
+            Some(Found::Dir(dir)) => Some(*dir),
+            _ => None,
+        }
+    }
+
+    /// A `const` version of [`Dir::contains()`].
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir};
+    ///
+    /// const ASSETS: Dir<'_> = include_dir!(".");
+    /// const _: () = assert!(ASSETS.contains_const("src/lib.rs"));
+    /// ```
+    pub const fn contains_const(&self, path: &str) -> bool {
+        self.find_const(path).is_some()
+    }
+
+    /// A `const` version of [`Dir::total_size()`], counting the bytes
+    /// actually stored in the binary.
+    ///
+    /// Compressed files can't be decompressed at compile time, so they count
+    /// as their compressed size. Otherwise this is the same as
+    /// `total_size()`.
+    ///
+    /// Together with [`Dir::file_count()`] and [`Dir::largest_file_size()`],
+    /// this makes it possible to enforce a budget without running anything,
+    /// so a build fails as soon as someone adds too much:
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir};
+    ///
+    /// const ASSETS: Dir<'_> = include_dir!("tests/fixtures/tree");
+    ///
+    /// const _: () = assert!(ASSETS.file_count() < 500, "Too many assets");
+    /// const _: () = assert!(ASSETS.total_size_const() <= 1024 * 1024, "Assets are over 1 MB");
+    /// const _: () = assert!(ASSETS.largest_file_size() <= 64 * 1024, "An asset is over 64 KB");
+    /// ```
+    ///
+    /// The `max_file_size` and `max_total_size` macro options are simpler
+    /// when the limit belongs with the `include_dir!()` call. These are for
+    /// when it doesn't, e.g. a budget for one sub-directory, or checks kept
+    /// in a different crate from the one embedding the files.
+    pub const fn total_size_const(&self) -> u64 {
+        let mut total = 0;
+
+        let mut ix = 0;
+        while ix < self.files.len() {
+            total += self.files[ix].contents.len() as u64;
+            ix += 1;
+        }
+
+        let mut ix = 0;
+        while ix < self.dirs.len() {
+            total += self.dirs[ix].total_size_const();
+            ix += 1;
+        }
+
+        total
+    }
+
+    /// The size of the biggest file inside this directory and all of its
+    /// sub-directories, in bytes, or `0` if there are no files.
+    ///
+    /// Like [`Dir::total_size_const()`], compressed files count as their
+    /// compressed size.
+    pub const fn largest_file_size(&self) -> u64 {
+        let mut largest = 0;
+
+        let mut ix = 0;
+        while ix < self.files.len() {
+            let len = self.files[ix].contents.len() as u64;
+            if len > largest {
+                largest = len;
+            }
+            ix += 1;
+        }
+
+        let mut ix = 0;
+        while ix < self.dirs.len() {
+            let len = self.dirs[ix].largest_file_size();
+            if len > largest {
+                largest = len;
+            }
+            ix += 1;
+        }
+
+        largest
+    }
+
+    const fn find_const(&self, path: &str) -> Option<Found<'a>> {
+        let path = path.as_bytes();
+
+        // absolute paths can never match
+        if !path.is_empty() && is_separator(path[0]) {
+            return None;
+        }
+
+        // paths always include this directory's own path
+        let own = self.path.as_bytes();
+        let mut own_cursor = 0;
+        let mut cursor = 0;
+        while let Some((own_start, own_end)) = next_name(own, own_cursor) {
+            let (start, end) = match next_name(path, cursor) {
+                Some(name) => name,
+                None => return None,
+            };
+            if !matches!(
+                compare(own, own_start, own_end, path, start, end),
+                Ordering::Equal
+            ) {
+                return None;
+            }
+            own_cursor = own_end;
+            cursor = end;
+        }
+
+        let (mut files, mut dirs) = (self.files, self.dirs);
+
+        while let Some((start, end)) = next_name(path, cursor) {
+            if is_parent(path, start, end) {
+                return None;
+            }
+
+            if next_name(path, end).is_none() {
+                if let Some(ix) = search_files(files, path, start, end) {
+                    return Some(Found::File(&files[ix]));
+                }
+                return match search_dirs(dirs, path, start, end) {
+                    Some(ix) => Some(Found::Dir(&dirs[ix])),
+                    None => None,
+                };
+            }
+
+            let dir = match search_dirs(dirs, path, start, end) {
+                Some(ix) => &dirs[ix],
+                None => return None,
+            };
+            files = dir.files;
+            dirs = dir.dirs;
+            cursor = end;
+        }
+
+        None
+    }
+}
+
+enum Found<'a> {
+    File(&'a File<'a>),
+    Dir(&'a Dir<'a>),
+}
+
+const fn is_separator(byte: u8) -> bool {
+    byte == b'/' || byte == b'\\'
+}
+
+/// Is `path[start..end]` a `..` component?
+const fn is_parent(path: &[u8], start: usize, end: usize) -> bool {
+    end - start == 2 && path[start] == b'.' && path[start + 1] == b'.'
+}
+
+/// Find the next name in `path`, starting at `cursor`, skipping empty and `.`
+/// components. Returns the start and end of the name.
+const fn next_name(path: &[u8], mut cursor: usize) -> Option<(usize, usize)> {
+    loop {
+        while cursor < path.len() && is_separator(path[cursor]) {
+            cursor += 1;
+        }
+        if cursor == path.len() {
+            return None;
+        }
+
+        let start = cursor;
+        while cursor < path.len() && !is_separator(path[cursor]) {
+            cursor += 1;
+        }
+
+        if !(cursor - start == 1 && path[start] == b'.') {
+            return Some((start, cursor));
+        }
+    }
+}
+
+/// Where the last component of a stored path starts.
+const fn file_name_start(path: &[u8]) -> usize {
+    let mut ix = path.len();
+    while ix > 0 && !is_separator(path[ix - 1]) {
+        ix -= 1;
+    }
+    ix
+}
+
+/// Compare `a[a_start..a_end]` with `b[b_start..b_end]`, byte by byte.
+const fn compare(
+    a: &[u8],
+    mut a_start: usize,
+    a_end: usize,
+    b: &[u8],
+    mut b_start: usize,
+    b_end: usize,
+) -> Ordering {
+    while a_start < a_end && b_start < b_end {
+        if a[a_start] < b[b_start] {
+            return Ordering::Less;
+        }
+        if a[a_start] > b[b_start] {
+            return Ordering::Greater;
+        }
+        a_start += 1;
+        b_start += 1;
+    }
+
+    match (a_start < a_end, b_start < b_end) {
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        _ => Ordering::Equal,
+    }
+}
+
+/// Compare the name of the entry stored at `stored` with `name[start..end]`.
+const fn compare_name(stored: &str, name: &[u8], start: usize, end: usize) -> Ordering {
+    let stored = stored.as_bytes();
+    compare(
+        stored,
+        file_name_start(stored),
+        stored.len(),
+        name,
+        start,
+        end,
+    )
+}
+
+/// Binary search a directory's (sorted) files by name.
+const fn search_files(files: &[File<'_>], name: &[u8], start: usize, end: usize) -> Option<usize> {
+    let (mut low, mut high) = (0, files.len());
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match compare_name(files[mid].path, name, start, end) {
+            Ordering::Less => low = mid + 1,
+            Ordering::Greater => high = mid,
+            Ordering::Equal => return Some(mid),
+        }
+    }
+    None
+}
+
+/// Binary search a directory's (sorted) sub-directories by name.
+const fn search_dirs(dirs: &[Dir<'_>], name: &[u8], start: usize, end: usize) -> Option<usize> {
+    let (mut low, mut high) = (0, dirs.len());
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match compare_name(dirs[mid].path, name, start, end) {
+            Ordering::Less => low = mid + 1,
+            Ordering::Greater => high = mid,
+            Ordering::Equal => return Some(mid),
+        }
+    }
+    None
+}
@@ -0,0 +1,145 @@
+use crate::dir::Dir;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The differences between an embedded [`Dir`] and a directory on disk,
+/// created with [`Dir::diff()`].
+///
+/// All paths are relative to the directories being compared, and each
+/// category is sorted.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DirDiff {
+    missing: Vec<PathBuf>,
+    modified: Vec<PathBuf>,
+    extra: Vec<PathBuf>,
+    identical: Vec<PathBuf>,
+}
+
+impl DirDiff {
+    /// Embedded files which don't exist on disk.
+    pub fn missing(&self) -> impl Iterator<Item = &Path> + '_ {
+        self.missing.iter().map(PathBuf::as_path)
+    }
+
+    /// Embedded files which exist on disk, but with different contents (or
+    /// as something other than a file).
+    pub fn modified(&self) -> impl Iterator<Item = &Path> + '_ {
+        self.modified.iter().map(PathBuf::as_path)
+    }
+
+    /// Files on disk which weren't embedded.
+    pub fn extra(&self) -> impl Iterator<Item = &Path> + '_ {
+        self.extra.iter().map(PathBuf::as_path)
+    }
+
+    /// Embedded files which exist on disk with exactly the same contents.
+    pub fn identical(&self) -> impl Iterator<Item = &Path> + '_ {
+        self.identical.iter().map(PathBuf::as_path)
+    }
+
+    /// Do both directories contain exactly the same files?
+    pub fn is_identical(&self) -> bool {
+        self.missing.is_empty() && self.modified.is_empty() && self.extra.is_empty()
+    }
+}
+
+impl<'a> Dir<'a> {
+    /// Compare the embedded files against a directory on disk, e.g. to see
+    /// what a user has changed since the directory was
+    /// [extracted](Dir::extract_with()).
+    ///
+    /// Files are compared byte for byte (after decompressing), skipping the
+    /// read when the sizes differ. Only files are compared; directories are
+    /// just used to find them, so an empty directory on either side doesn't
+    /// show up in the [`DirDiff`]. Symlinks on disk are followed when they
+    /// point at a file, but never descended into.
+    ///
+    /// If `on_disk` doesn't exist, every embedded file is reported as
+    /// missing. Errors are annotated with the path that couldn't be read.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir};
+    ///
+    /// static TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+    ///
+    /// let diff = TREE.diff("tests/fixtures/tree")?;
+    /// assert!(diff.is_identical());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn diff<P: AsRef<Path>>(&self, on_disk: P) -> io::Result<DirDiff> {
+        let root = on_disk.as_ref();
+        let mut diff = DirDiff::default();
+        let mut embedded = HashSet::new();
+
+        for file in self.walk().filter_map(|entry| entry.as_file()) {
+            let relative = file.relative_to(self).unwrap_or(file.path()).to_path_buf();
+            let full = root.join(&relative);
+
+            match fs::metadata(&full) {
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    diff.missing.push(relative.clone())
+                }
+                Err(e) => return Err(annotate(e, &full)),
+                Ok(meta) if !meta.is_file() => diff.modified.push(relative.clone()),
+                Ok(meta) => {
+                    let contents = file.uncompressed();
+                    let same = meta.len() == contents.len() as u64
+                        && fs::read(&full).map_err(|e| annotate(e, &full))? == *contents;
+
+                    if same {
+                        diff.identical.push(relative.clone());
+                    } else {
+                        diff.modified.push(relative.clone());
+                    }
+                }
+            }
+
+            embedded.insert(relative);
+        }
+
+        if root.is_dir() {
+            find_extra(root, Path::new(""), &embedded, &mut diff.extra)?;
+        }
+
+        diff.missing.sort();
+        diff.modified.sort();
+        diff.extra.sort();
+        diff.identical.sort();
+
+        Ok(diff)
+    }
+}
+
+/// Recursively look for files under `root.join(relative)` which aren't in
+/// the `embedded` set.
+fn find_extra(
+    root: &Path,
+    relative: &Path,
+    embedded: &HashSet<PathBuf>,
+    extra: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    let dir = root.join(relative);
+
+    for entry in fs::read_dir(&dir).map_err(|e| annotate(e, &dir))? {
+        let entry = entry.map_err(|e| annotate(e, &dir))?;
+        let path = relative.join(entry.file_name());
+        let file_type = entry.file_type().map_err(|e| annotate(e, &entry.path()))?;
+
+        if file_type.is_dir() {
+            find_extra(root, &path, embedded, extra)?;
+        } else if entry.path().is_file() && !embedded.contains(&path) {
+            extra.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn annotate(error: io::Error, path: &Path) -> io::Error {
+    io::Error::new(
+        error.kind(),
+        format!("Unable to compare \"{}\": {}", path.display(), error),
+    )
+}
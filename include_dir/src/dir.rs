@@ -1,9 +1,60 @@
+use crate::dir_entry::{DirEntry, Entries, EntryKind};
+#[cfg(feature = "std")]
+use crate::extract::ExtractOptions;
 use crate::file::File;
-use std::fs;
-use std::io::Write;
-use std::path::Path;
+use crate::ignore_case::eq_ignore_case;
+use crate::lookup_error::LookupError;
+use crate::path::{self, Path};
+use crate::walk::Walk;
+use memchr::memmem::Finder;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+#[cfg(feature = "std")]
+use std::path::Component;
+#[cfg(feature = "source-info")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// A directory entry.
+///
+/// # Paths
+///
+/// Every file and directory stores its full path, relative to the directory
+/// included with `include_dir!()`, using `/` as the separator on all
+/// platforms. The lookup methods ([`Dir::get_entry()`], [`Dir::get_file()`],
+/// [`Dir::get_dir()`], their `_ref` and `try_` variants, and [`Dir::contains()`]) take these full paths and accept either `/` or
+/// `\` as a separator, so paths built with [`Path::join()`] work everywhere.
+///
+/// Stored paths are always valid UTF-8. Names which weren't are rejected by
+/// the macro unless the `non_utf8_names` option says to skip them or to
+/// store them with invalid sequences replaced by `U+FFFD`, in which case
+/// that replaced form is what lookups have to use.
+///
+/// The lookup methods take any `AsRef<Path>`, so an `OsStr` or `OsString`
+/// straight from the OS can be passed in as-is. A path which isn't valid
+/// UTF-8 simply doesn't match anything (a [`LookupError::InvalidPath`] for
+/// the `try_` variants), and the rest of it is still checked the same way.
+///
+/// Lookups are case-sensitive. Use [`Dir::get_file_ignore_case()`] or
+/// [`Dir::get_dir_ignore_case()`] when the case of a path isn't known.
+///
+/// [`Dir::get_file_const()`], [`Dir::get_dir_const()`], and
+/// [`Dir::contains_const()`] follow the same rules but can be used in a
+/// `const`, so a missing asset can be caught at compile time.
+///
+/// # Ordering
+///
+/// The macro sorts every directory's files and sub-directories by path,
+/// comparing the bytes of their names, so the order never depends on the
+/// filesystem the code was compiled on. This means uppercase letters come
+/// before lowercase ones and `10.txt` comes before `9.txt`. Every method
+/// which lists entries ([`Dir::files()`], [`Dir::dirs()`],
+/// [`Dir::entries()`], [`Dir::walk()`], etc.) follows this order.
+///
+/// # Equality
+///
+/// [`File`]s are compared by path alone, so two directories are equal when
+/// they contain the same paths, even if the files' contents differ. Compare
+/// the contents of each file to tell them apart.
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Dir<'a> {
     #[doc(hidden)]
@@ -12,97 +63,850 @@ pub struct Dir<'a> {
     pub files: &'a [File<'a>],
     #[doc(hidden)]
     pub dirs: &'a [Dir<'a>],
+    #[cfg(feature = "hash")]
+    #[doc(hidden)]
+    pub hash: [u8; 32],
+    /// Where the directory was embedded from, only recorded in debug builds.
+    #[cfg(feature = "hot-reload")]
+    #[doc(hidden)]
+    pub source: &'a str,
+    #[cfg(feature = "source-info")]
+    #[doc(hidden)]
+    pub source_path: &'a str,
+    /// Seconds since the Unix epoch.
+    #[cfg(feature = "source-info")]
+    #[doc(hidden)]
+    pub embedded_at: Option<u64>,
+    #[cfg(feature = "build-id")]
+    #[doc(hidden)]
+    pub build_id: &'a str,
 }
 
 impl<'a> Dir<'a> {
     /// Get the directory's path.
     pub fn path(&self) -> &'a Path {
-        Path::new(self.path)
+        path::new(self.path)
     }
 
-    /// Get a list of the files in this directory.
+    /// The last component of the directory's path.
+    ///
+    /// This is an empty string for the directory passed to `include_dir!()`
+    /// (unless it was given a `prefix`).
+    pub fn file_name(&self) -> &'a str {
+        file_name(self.path)
+    }
+
+    /// This directory's path relative to `ancestor`, or `None` if it isn't
+    /// inside `ancestor`.
+    ///
+    /// This only compares paths, so `ancestor` can be any directory above
+    /// this one, not just its parent. A directory relative to itself is an
+    /// empty path.
+    pub fn relative_to(&self, ancestor: &Dir<'_>) -> Option<&'a Path> {
+        relative_path(self.path, ancestor.path).map(path::new)
+    }
+
+    /// Get a list of the files in this directory, sorted by path.
     pub fn files(&self) -> &'a [File<'a>] {
         self.files
     }
 
-    /// Get a list of the sub-directories inside this directory.
+    /// Get a list of the sub-directories inside this directory, sorted by
+    /// path.
     pub fn dirs(&self) -> &'a [Dir<'a>] {
         self.dirs
     }
 
+    /// A SHA-256 hash of everything inside this directory, calculated when
+    /// the directory was embedded.
+    ///
+    /// The hash covers the names, kinds, and hashes of every child, visited
+    /// in sorted order so it is the same regardless of how the filesystem
+    /// ordered them. Only names are used, not full paths, so the same tree
+    /// has the same hash wherever it is in the hierarchy (or when a `prefix`
+    /// is used).
+    #[cfg(feature = "hash")]
+    pub fn hash(&self) -> [u8; 32] {
+        self.hash
+    }
+
+    /// The absolute path this directory was embedded from, as seen by the
+    /// machine which compiled it.
+    ///
+    /// This is `None` for directories which weren't created by
+    /// `include_dir!()`, or which were merged together from several
+    /// directories passed to it.
+    #[cfg(feature = "source-info")]
+    pub fn source_path(&self) -> Option<&'a str> {
+        if self.source_path.is_empty() {
+            None
+        } else {
+            Some(self.source_path)
+        }
+    }
+
+    /// When the directory was embedded.
+    ///
+    /// This is the time `include_dir!()` ran, unless the `SOURCE_DATE_EPOCH`
+    /// environment variable was set while compiling, in which case that is
+    /// used instead so builds stay reproducible. It only has a precision of
+    /// one second, and is `None` for directories which weren't created by
+    /// `include_dir!()`.
+    #[cfg(feature = "source-info")]
+    pub fn embedded_at(&self) -> Option<SystemTime> {
+        self.embedded_at
+            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    /// A short identifier which changes whenever anything inside this
+    /// directory does, for cache-busting URLs like `/app.css?v=...`.
+    ///
+    /// It is 16 hex characters derived from the names and contents of
+    /// everything inside the directory, calculated when it was embedded.
+    /// It doesn't depend on the order the filesystem listed entries in,
+    /// where the directory was on disk, or its `prefix`, so the same tree
+    /// gets the same id on every machine. Only the macro does any hashing,
+    /// so this doesn't need the `hash` feature. Directories which
+    /// weren't created by `include_dir!()` have an empty id.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir};
+    ///
+    /// static ASSETS: Dir<'_> = include_dir!("src");
+    ///
+    /// let url = format!("/static/lib.rs?v={}", ASSETS.build_id());
+    /// assert_eq!(url.len(), "/static/lib.rs?v=".len() + 16);
+    /// ```
+    #[cfg(feature = "build-id")]
+    pub fn build_id(&self) -> &'a str {
+        self.build_id
+    }
+
+    /// Iterate over the files and sub-directories immediately inside this
+    /// directory, sorted by path.
+    pub fn entries(&self) -> Entries<'a> {
+        Entries::new(*self)
+    }
+
+    /// Recursively iterate over every file and sub-directory inside this
+    /// directory.
+    ///
+    /// Entries are yielded depth-first, with each directory coming
+    /// immediately before its contents. Siblings are visited in order of
+    /// their paths, so the sequence is the same on every platform.
+    pub fn walk(&self) -> Walk<'a> {
+        Walk::new(*self)
+    }
+
+    /// Recursively iterate over every file with a particular extension, in
+    /// the same order as [`Dir::walk()`].
+    ///
+    /// Extensions are compared case-insensitively and may be given with or
+    /// without the leading dot. Extensions made of several parts work too, so
+    /// `archive.tar.gz` matches both `"gz"` and `"tar.gz"`. Dotfiles like
+    /// `.gitignore` don't have an extension.
+    pub fn files_with_extension<'e>(
+        &self,
+        extension: &'e str,
+    ) -> impl Iterator<Item = File<'a>> + 'e
+    where
+        'a: 'e,
+    {
+        let extension = extension.strip_prefix('.').unwrap_or(extension);
+
+        self.walk()
+            .filter_map(|entry| entry.as_file())
+            .filter(move |file| has_extension(file.file_name(), extension))
+    }
+
+    /// Recursively iterate over the files for which `predicate` returns
+    /// `true`, in the same order as [`Dir::walk()`].
+    ///
+    /// The walk is lazy, so taking the first few matches doesn't visit the
+    /// rest of the tree. The predicate may borrow from its surroundings.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir};
+    ///
+    /// static PROJECT: Dir<'_> = include_dir!(".");
+    ///
+    /// let min_len = 1024;
+    /// let big: Vec<_> = PROJECT
+    ///     .files_matching(|f| f.len() >= min_len)
+    ///     .take(3)
+    ///     .collect();
+    /// assert!(big.iter().all(|f| f.len() >= min_len));
+    /// ```
+    pub fn files_matching<'p, P>(&self, predicate: P) -> impl Iterator<Item = File<'a>> + 'p
+    where
+        P: Fn(&File<'a>) -> bool + 'p,
+        'a: 'p,
+    {
+        self.walk()
+            .filter_map(|entry| entry.as_file())
+            .filter(move |file| predicate(file))
+    }
+
+    /// Recursively iterate over the sub-directories for which `predicate`
+    /// returns `true`, in the same order as [`Dir::walk()`].
+    ///
+    /// Directories which don't match are still searched, so their
+    /// sub-directories can match. Like [`Dir::files_matching()`], this is
+    /// lazy and the predicate may borrow from its surroundings.
+    pub fn dirs_matching<'p, P>(&self, predicate: P) -> impl Iterator<Item = Dir<'a>> + 'p
+    where
+        P: Fn(&Dir<'a>) -> bool + 'p,
+        'a: 'p,
+    {
+        self.walk()
+            .filter_map(|entry| entry.as_dir())
+            .filter(move |dir| predicate(dir))
+    }
+
+    /// Recursively iterate over the paths of every file inside this
+    /// directory, in the same order as [`Dir::walk()`].
+    ///
+    /// Paths are exactly as they are stored: relative to the root of the
+    /// embedded tree and always separated by `/`, even when the macro ran on
+    /// Windows. That makes them ready to write into manifests, sitemaps or
+    /// precache lists as-is.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir};
+    ///
+    /// static PROJECT: Dir<'_> = include_dir!(".");
+    ///
+    /// let src = PROJECT.get_dir("src").unwrap();
+    /// assert!(src.file_paths().any(|path| path == "src/lib.rs"));
+    /// ```
+    pub fn file_paths(&self) -> impl Iterator<Item = &'a str> {
+        self.walk()
+            .filter_map(|entry| entry.as_file())
+            .map(|file| file.path)
+    }
+
+    /// Recursively iterate over the paths of every sub-directory inside this
+    /// directory, in the same order as [`Dir::walk()`].
+    ///
+    /// Paths follow the same rules as [`Dir::file_paths()`].
+    pub fn dir_paths(&self) -> impl Iterator<Item = &'a str> {
+        self.walk()
+            .filter_map(|entry| entry.as_dir())
+            .map(|dir| dir.path)
+    }
+
+    /// Recursively iterate over every file inside this directory as a
+    /// `(path, contents)` pair, in the same order as [`Dir::walk()`].
+    ///
+    /// Paths follow the same rules as [`Dir::file_paths()`], so each one is
+    /// exactly the string [`Dir::get_file()`] would be given for that file,
+    /// and the contents are its [raw contents](File::contents). This makes
+    /// it easy to collect the tree into a map for lookups on a hot path,
+    /// with the map and the `Dir` staying interchangeable.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir};
+    /// use std::collections::HashMap;
+    ///
+    /// static TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+    ///
+    /// let files: HashMap<&str, &[u8]> = TREE.flatten().collect();
+    /// let readme = TREE.get_file("migrations/README.md").unwrap();
+    /// assert_eq!(files["migrations/README.md"], readme.contents());
+    /// ```
+    pub fn flatten(&self) -> impl Iterator<Item = (&'a str, &'a [u8])> {
+        self.walk()
+            .filter_map(|entry| entry.as_file())
+            .map(|file| (file.path, file.contents()))
+    }
+
+    /// Collect every file inside this directory into a map keyed by its
+    /// path, like [`Dir::flatten()`] but keeping the whole [`File`].
+    #[cfg(feature = "std")]
+    pub fn to_map(&self) -> std::collections::HashMap<&'a str, File<'a>> {
+        self.walk()
+            .filter_map(|entry| entry.as_file())
+            .map(|file| (file.path, file))
+            .collect()
+    }
+
+    /// Recursively search for files containing `needle`, yielding each
+    /// matching file along with the offset of the first match, in the same
+    /// order as [`Dir::walk()`].
+    ///
+    /// An empty needle matches every file at offset 0. Compressed files are
+    /// searched by decompressing them one at a time, and the offset always
+    /// refers to the original contents. Nothing else is allocated, so this is
+    /// fine to use on large files.
+    pub fn find_in_contents<'n>(
+        &self,
+        needle: &'n [u8],
+    ) -> impl Iterator<Item = (File<'a>, usize)> + 'n
+    where
+        'a: 'n,
+    {
+        let finder = Finder::new(needle);
+
+        self.walk()
+            .filter_map(|entry| entry.as_file())
+            .filter_map(move |file| {
+                // without std this is already a slice
+                #[allow(clippy::needless_borrow)]
+                let found = finder.find(&file.uncompressed());
+                found.map(|ix| (file, ix))
+            })
+    }
+
+    /// Like [`Dir::find_in_contents()`], but searching for a string.
+    ///
+    /// The offset is in bytes, and files don't need to be valid UTF-8 to
+    /// match.
+    pub fn find_str_in_contents<'n>(
+        &self,
+        needle: &'n str,
+    ) -> impl Iterator<Item = (File<'a>, usize)> + 'n
+    where
+        'a: 'n,
+    {
+        self.find_in_contents(needle.as_bytes())
+    }
+
+    /// The number of files inside this directory and all of its
+    /// sub-directories.
+    ///
+    /// This is a `const fn`, so it can be used to put a limit on embedded
+    /// directories at compile time. See [`Dir::total_size_const()`].
+    pub const fn file_count(&self) -> usize {
+        let mut count = self.files.len();
+        let mut ix = 0;
+        while ix < self.dirs.len() {
+            count += self.dirs[ix].file_count();
+            ix += 1;
+        }
+        count
+    }
+
+    /// The number of sub-directories inside this directory, counted
+    /// recursively (not including this directory).
+    pub const fn dir_count(&self) -> usize {
+        let mut count = self.dirs.len();
+        let mut ix = 0;
+        while ix < self.dirs.len() {
+            count += self.dirs[ix].dir_count();
+            ix += 1;
+        }
+        count
+    }
+
+    /// The combined length of every file inside this directory and all of
+    /// its sub-directories, in bytes.
+    ///
+    /// This is the size of the original files, so compressed files are
+    /// decompressed to find out how big they are. Use
+    /// [`Dir::total_size_compressed()`] for the number of bytes actually
+    /// stored in the binary.
+    pub fn total_size(&self) -> u64 {
+        let files: u64 = self
+            .files
+            .iter()
+            .map(|f| f.uncompressed().len() as u64)
+            .sum();
+
+        files + self.dirs.iter().map(Dir::total_size).sum::<u64>()
+    }
+
+    /// The number of bytes used to store every file inside this directory
+    /// and all of its sub-directories, after compression.
+    ///
+    /// This is the same as [`Dir::total_size()`] for directories which
+    /// weren't embedded with `compress = true`.
+    #[cfg(feature = "compression")]
+    pub fn total_size_compressed(&self) -> u64 {
+        let files: u64 = self.files.iter().map(|f| f.contents.len() as u64).sum();
+
+        files
+            + self
+                .dirs
+                .iter()
+                .map(Dir::total_size_compressed)
+                .sum::<u64>()
+    }
+
     /// Does this directory contain `path`?
+    ///
+    /// This is true for both files and sub-directories, so it is always the
+    /// same as `get_entry(path).is_some()`. Paths are compared one whole
+    /// component at a time, so `assets/im` never matches `assets/img`.
     pub fn contains<S: AsRef<Path>>(&self, path: S) -> bool {
+        self.find_ref(path.as_ref()).is_some()
+    }
+
+    /// Find out whether `path` is a file or a directory, without fetching
+    /// it.
+    ///
+    /// This follows exactly the same rules as [`Dir::contains()`],
+    /// [`Dir::get_file()`], and [`Dir::get_dir()`], so it is `None` when
+    /// `contains()` is `false` and otherwise says which of the other two
+    /// would succeed.
+    pub fn entry_kind<S: AsRef<Path>>(&self, path: S) -> Option<EntryKind> {
+        match self.find_ref(path.as_ref())? {
+            EntryRef::File(_) => Some(EntryKind::File),
+            EntryRef::Dir(_) => Some(EntryKind::Dir),
+        }
+    }
+
+    /// Find the directory which contains `path`.
+    ///
+    /// The path is looked up with the same rules as [`Dir::get_entry()`],
+    /// so this is `None` when there is nothing at `path`. Entries directly
+    /// inside this directory give back this directory.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir};
+    ///
+    /// static PROJECT: Dir<'_> = include_dir!(".");
+    ///
+    /// let src = PROJECT.parent_of("src/lib.rs").unwrap();
+    /// assert_eq!(src.path(), std::path::Path::new("src"));
+    /// ```
+    pub fn parent_of<S: AsRef<Path>>(&self, path: S) -> Option<Dir<'a>> {
+        let stored = self.find_ref(path.as_ref())?.stored_path();
+        let parent = &stored[..stored.len() - file_name(stored).len()];
+
+        if stored_names(parent).eq(stored_names(self.path)) {
+            Some(*self)
+        } else {
+            self.find_stored_dir(parent).copied()
+        }
+    }
+
+    /// Iterate over the other entries in the directory containing `path`,
+    /// sorted by path.
+    ///
+    /// This is [`Dir::parent_of()`]'s entries without the one at `path`, so
+    /// it is `None` under the same conditions.
+    pub fn siblings_of<S: AsRef<Path>>(
+        &self,
+        path: S,
+    ) -> Option<impl Iterator<Item = DirEntry<'a>>> {
         let path = path.as_ref();
+        let stored = self.find_ref(path)?.stored_path();
+        let parent = self.parent_of(path)?;
 
-        self.get_file(path).is_some() || self.get_dir(path).is_some()
+        Some(
+            parent
+                .entries()
+                .filter(move |entry| entry.path() != path::new(stored)),
+        )
+    }
+
+    /// Fetch a file or sub-directory by *exactly* matching its path relative
+    /// to the directory included with `include_dir!()`.
+    ///
+    /// This is useful when you want to handle both kinds of entry without
+    /// searching twice.
+    pub fn get_entry<S: AsRef<Path>>(&self, path: S) -> Option<DirEntry<'a>> {
+        self.try_get_entry(path).ok()
     }
 
     /// Fetch a sub-directory by *exactly* matching its path relative to the
     /// directory included with `include_dir!()`.
     pub fn get_dir<S: AsRef<Path>>(&self, path: S) -> Option<Dir<'a>> {
-        let path = path.as_ref();
+        self.get_dir_ref(path).copied()
+    }
 
-        for dir in self.dirs {
-            if Path::new(dir.path) == path {
-                return Some(*dir);
-            }
+    /// Fetch a file by *exactly* matching its path relative to the
+    /// directory included with `include_dir!()`.
+    pub fn get_file<S: AsRef<Path>>(&self, path: S) -> Option<File<'a>> {
+        self.get_file_ref(path).copied()
+    }
 
-            if let Some(d) = dir.get_dir(path) {
-                return Some(d);
-            }
+    /// Like [`Dir::get_dir()`], but borrowing the sub-directory from the
+    /// embedded tree instead of copying it.
+    pub fn get_dir_ref<S: AsRef<Path>>(&self, path: S) -> Option<&'a Dir<'a>> {
+        self.try_get_dir_ref(path.as_ref()).ok()
+    }
+
+    /// Like [`Dir::get_file()`], but borrowing the file from the embedded
+    /// tree instead of copying it.
+    ///
+    /// The reference lives as long as the embedded data, so it can be kept
+    /// in long-lived collections (e.g. a `HashMap<&str, &File>`).
+    pub fn get_file_ref<S: AsRef<Path>>(&self, path: S) -> Option<&'a File<'a>> {
+        self.try_get_file_ref(path.as_ref()).ok()
+    }
+
+    /// Like [`Dir::get_entry()`], but saying why nothing was found.
+    ///
+    /// The only possible errors are [`LookupError::NotFound`] and
+    /// [`LookupError::InvalidPath`].
+    pub fn try_get_entry<S: AsRef<Path>>(&self, path: S) -> Result<DirEntry<'a>, LookupError> {
+        match self.try_find_ref(path.as_ref())? {
+            EntryRef::File(f) => Ok(DirEntry::File(*f)),
+            EntryRef::Dir(d) => Ok(DirEntry::Dir(*d)),
         }
+    }
 
-        None
+    /// Like [`Dir::get_dir()`], but saying why nothing was found.
+    pub fn try_get_dir<S: AsRef<Path>>(&self, path: S) -> Result<Dir<'a>, LookupError> {
+        self.try_get_dir_ref(path.as_ref()).copied()
     }
 
-    /// Fetch a sub-directory by *exactly* matching its path relative to the
-    /// directory included with `include_dir!()`.
-    pub fn get_file<S: AsRef<Path>>(&self, path: S) -> Option<File<'a>> {
-        let path = path.as_ref();
+    /// Like [`Dir::get_file()`], but saying why nothing was found.
+    ///
+    /// This makes it easy to tell a missing file apart from a directory,
+    /// e.g. to redirect to an index page instead of responding with a 404.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir, LookupError};
+    ///
+    /// static PROJECT: Dir<'_> = include_dir!(".");
+    ///
+    /// assert!(PROJECT.try_get_file("src/lib.rs").is_ok());
+    /// assert_eq!(PROJECT.try_get_file("src"), Err(LookupError::NotAFile));
+    /// assert_eq!(PROJECT.try_get_file("missing.txt"), Err(LookupError::NotFound));
+    /// assert!(matches!(
+    ///     PROJECT.try_get_file("../secrets.txt"),
+    ///     Err(LookupError::InvalidPath { .. })
+    /// ));
+    /// ```
+    pub fn try_get_file<S: AsRef<Path>>(&self, path: S) -> Result<File<'a>, LookupError> {
+        self.try_get_file_ref(path.as_ref()).copied()
+    }
 
-        for file in self.files {
-            if Path::new(file.path) == path {
-                return Some(*file);
-            }
-        }
+    /// Read a file's original contents.
+    ///
+    /// This is the same as calling [`File::decompressed_contents()`] on the
+    /// result of [`Dir::get_file()`], except when the `hot-reload` feature is
+    /// enabled in a debug build. Then the file is read from the directory it
+    /// was embedded from, so edits show up without recompiling, and the
+    /// embedded copy is only used if that fails (e.g. because the file was
+    /// deleted). Release builds never touch the filesystem.
+    #[cfg(feature = "std")]
+    pub fn read_file<S: AsRef<Path>>(&self, path: S) -> Option<Cow<'a, [u8]>> {
+        let path = path.as_ref();
 
-        for dir in self.dirs {
-            if let Some(d) = dir.get_file(path) {
-                return Some(d);
-            }
+        #[cfg(all(feature = "hot-reload", debug_assertions))]
+        if let Some(contents) = self.read_from_source(path) {
+            return Some(Cow::Owned(contents));
         }
 
-        None
+        self.get_file(path).map(|f| f.uncompressed())
     }
 
     /// Create directories and extract all files to real filesystem.
+    /// Sub-directories are extracted recursively, including empty ones.
     /// Creates parent directories of `path` if they do not already exist.
     /// Fails if some files already exist.
     /// In case of error, partially extracted directory may remain on the filesystem.
+    ///
+    /// Entries are written relative to this directory, so extracting
+    /// `include_dir!("assets", prefix = "assets")` (or a sub-directory found
+    /// with [`Dir::get_dir()`]) into `/tmp/x` writes `assets/css/main.css` to
+    /// `/tmp/x/css/main.css`. Use [`Dir::extract_into()`] to keep this
+    /// directory's own path, or [`Dir::extract_with()`] for more control over
+    /// how files are written.
+    #[cfg(feature = "std")]
     pub fn extract<S: AsRef<Path>>(&self, path: S) -> std::io::Result<()> {
-        // Extracts the given directory entry to the given path
-        // We use this internally for recursing on subdirectories
-        fn extract_dir<S: AsRef<Path>>(dir: Dir<'_>, path: S) -> std::io::Result<()> {
-            let path = path.as_ref();
-            // Create all the subdirectories in here (but not their files yet)
-            for dir in dir.dirs() {
-                fs::create_dir_all(path.join(dir.path()))?;
-                extract_dir(*dir, path)?;
+        self.extract_with(path, &ExtractOptions::default())
+            .map(|_| ())
+    }
+}
+
+impl<'a> Dir<'a> {
+    fn try_get_dir_ref(&self, path: &Path) -> Result<&'a Dir<'a>, LookupError> {
+        match self.try_find_ref(path)? {
+            EntryRef::Dir(d) => Ok(d),
+            EntryRef::File(_) => Err(LookupError::NotADir),
+        }
+    }
+
+    fn try_get_file_ref(&self, path: &Path) -> Result<&'a File<'a>, LookupError> {
+        match self.try_find_ref(path)? {
+            EntryRef::File(f) => Ok(f),
+            EntryRef::Dir(_) => Err(LookupError::NotAFile),
+        }
+    }
+
+    /// Look up a descendant by its full path, borrowing it from the tree.
+    fn find_ref(&self, path: &Path) -> Option<EntryRef<'a>> {
+        self.try_find_ref(path).ok()
+    }
+
+    /// Look up a descendant by its full path, borrowing it from the tree.
+    ///
+    /// Because the macro sorts each directory's entries by path, this only
+    /// needs a binary search at each level of the tree rather than visiting
+    /// every entry. Only the slices are followed on the way down, so no
+    /// intermediate `Dir`s are copied.
+    fn try_find_ref(&self, path: &Path) -> Result<EntryRef<'a>, LookupError> {
+        let names = lookup_names(path).map_err(|reason| LookupError::InvalidPath { reason })?;
+
+        self.find_ref_by_names(names).ok_or(LookupError::NotFound)
+    }
+
+    /// Look up a descendant by its path relative to this directory, for
+    /// [`ScopedDir`](crate::ScopedDir).
+    pub(crate) fn try_get_relative(&self, path: &Path) -> Result<DirEntry<'a>, LookupError> {
+        let names = lookup_names(path).map_err(|reason| LookupError::InvalidPath { reason })?;
+
+        match self
+            .find_ref_by_names(stored_names(self.path).chain(names))
+            .ok_or(LookupError::NotFound)?
+        {
+            EntryRef::File(f) => Ok(DirEntry::File(*f)),
+            EntryRef::Dir(d) => Ok(DirEntry::Dir(*d)),
+        }
+    }
+
+    /// Look up a sub-directory by the path stored in it, for internal code
+    /// which already has one (e.g. to find a directory's parent).
+    pub(crate) fn find_stored_dir(&self, stored_path: &str) -> Option<&'a Dir<'a>> {
+        match self.find_ref_by_names(stored_names(stored_path))? {
+            EntryRef::Dir(d) => Some(d),
+            EntryRef::File(_) => None,
+        }
+    }
+
+    fn find_ref_by_names<'n, I>(&self, mut names: I) -> Option<EntryRef<'a>>
+    where
+        I: Iterator<Item = &'n str>,
+    {
+        // paths always include this directory's own path
+        for own in stored_names(self.path) {
+            if names.next() != Some(own) {
+                return None;
             }
+        }
+
+        let mut names = names.peekable();
+        let (mut files, mut dirs) = (self.files, self.dirs);
+
+        while let Some(name) = names.next() {
+            if names.peek().is_none() {
+                return find_child(files, dirs, name);
+            }
+
+            let dir = find_child_dir(dirs, name)?;
+            files = dir.files;
+            dirs = dir.dirs;
+        }
+
+        None
+    }
+
+    /// Read `path` from the directory this was embedded from.
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
+    fn read_from_source(&self, path: &Path) -> Option<Vec<u8>> {
+        if self.source.is_empty() {
+            return None;
+        }
 
-            // Only write files at the root of this directory (we recurse on subdirectories)
-            for file in dir.files() {
-                let mut fsf = fs::OpenOptions::new()
-                    .write(true)
-                    .create_new(true)
-                    .open(path.join(file.path()))?;
-                fsf.write_all(file.contents())?;
-                fsf.sync_all()?;
+        let mut names = lookup_names(path).ok()?;
+        for own in stored_names(self.path) {
+            if names.next() != Some(own) {
+                return None;
             }
+        }
+
+        let on_disk = names.fold(std::path::PathBuf::from(self.source), |p, name| {
+            p.join(name)
+        });
+
+        if on_disk.is_file() {
+            std::fs::read(on_disk).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Find an immediate child by name.
+    #[cfg(feature = "std")]
+    pub(crate) fn child(&self, name: &str) -> Option<DirEntry<'a>> {
+        match find_child(self.files, self.dirs, name)? {
+            EntryRef::File(f) => Some(DirEntry::File(*f)),
+            EntryRef::Dir(d) => Some(DirEntry::Dir(*d)),
+        }
+    }
+
+    /// Find an immediate sub-directory by name.
+    #[cfg(feature = "std")]
+    pub(crate) fn child_dir(&self, name: &str) -> Option<Dir<'a>> {
+        find_child_dir(self.dirs, name).copied()
+    }
+}
 
-            Ok(())
+/// A borrowed entry, used by the lookup internals to avoid copying.
+enum EntryRef<'a> {
+    File(&'a File<'a>),
+    Dir(&'a Dir<'a>),
+}
+
+impl<'a> EntryRef<'a> {
+    fn stored_path(&self) -> &'a str {
+        match *self {
+            EntryRef::File(f) => f.path,
+            EntryRef::Dir(d) => d.path,
+        }
+    }
+}
+
+/// Find the file or directory called `name` in a directory's (sorted)
+/// entries.
+fn find_child<'a>(files: &'a [File<'a>], dirs: &'a [Dir<'a>], name: &str) -> Option<EntryRef<'a>> {
+    match files.binary_search_by(|f| file_name(f.path).cmp(name)) {
+        Ok(ix) => Some(EntryRef::File(&files[ix])),
+        Err(_) => find_child_dir(dirs, name).map(EntryRef::Dir),
+    }
+}
+
+fn find_child_dir<'a>(dirs: &'a [Dir<'a>], name: &str) -> Option<&'a Dir<'a>> {
+    dirs.binary_search_by(|d| file_name(d.path).cmp(name))
+        .ok()
+        .map(|ix| &dirs[ix])
+}
+
+/// The components of a path passed to one of the lookup methods.
+///
+/// Both `/` and `\\` are treated as separators on every platform, so the
+/// same lookup works no matter which OS the code runs on. Trivial
+/// decorations like a leading `./`, trailing slashes, or repeated separators
+/// are ignored. Returns `None` for paths which could never match a stored
+/// path, like absolute paths or ones containing `..`, saying why.
+///
+/// This works on each component's raw `OsStr` bytes rather than requiring
+/// the whole path to be UTF-8, so `..` and absolute paths are caught the same
+/// way whatever else the path contains. A name which isn't UTF-8 can't match
+/// anything the macro stored, so it is only reported once the rest of the
+/// path has been checked.
+#[cfg(feature = "std")]
+pub(crate) fn lookup_names(
+    path: &Path,
+) -> Result<impl Iterator<Item = &str> + Clone, &'static str> {
+    let mut not_utf8 = false;
+
+    for (i, component) in path.components().enumerate() {
+        match component {
+            Component::CurDir => {}
+            Component::Normal(name) => {
+                // a leading backslash would be a root directory on Windows
+                if i == 0 && name.as_encoded_bytes().starts_with(b"\\") {
+                    return Err(ABSOLUTE);
+                }
+
+                for name in split_os_name(name) {
+                    match name {
+                        b".." => return Err(PARENT),
+                        name => not_utf8 |= std::str::from_utf8(name).is_err(),
+                    }
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return Err(ABSOLUTE),
+            Component::ParentDir => return Err(PARENT),
         }
+    }
+
+    if not_utf8 {
+        return Err(NOT_UTF8);
+    }
+
+    let names = path
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => Some(name),
+            _ => None,
+        })
+        .flat_map(split_os_name)
+        // everything was checked above, so nothing gets dropped here
+        .filter_map(|name| std::str::from_utf8(name).ok());
+
+    Ok(names)
+}
+
+/// Split a single path component on either separator, skipping the empty
+/// and `.` names.
+///
+/// Splitting the encoded bytes on ASCII is always sound, even for names which
+/// aren't valid Unicode.
+#[cfg(feature = "std")]
+fn split_os_name(name: &std::ffi::OsStr) -> impl Iterator<Item = &[u8]> + Clone {
+    name.as_encoded_bytes()
+        .split(|&b| b == b'/' || b == b'\\')
+        .filter(|name| !name.is_empty() && *name != b".")
+}
+
+/// The components of a path passed to one of the lookup methods, following
+/// the same rules as when the standard library is available.
+#[cfg(not(feature = "std"))]
+pub(crate) fn lookup_names(path: &str) -> Result<impl Iterator<Item = &str> + Clone, &'static str> {
+    // without std we can't ask which platform this is, so only reject the
+    // roots every platform agrees on
+    if path.starts_with(SEPARATORS) {
+        return Err(ABSOLUTE);
+    }
+
+    let names = path
+        .split(SEPARATORS)
+        .filter(|name| !name.is_empty() && *name != ".");
+    if names.clone().any(|name| name == "..") {
+        return Err(PARENT);
+    }
+
+    Ok(names)
+}
+
+/// The components of a path stored in a [`Dir`] or [`File`].
+///
+/// The macro always uses `/`, but `\\` is accepted too in case the tree was
+/// constructed some other way.
+pub(crate) fn stored_names(path: &str) -> impl Iterator<Item = &str> {
+    path.split(SEPARATORS).filter(|name| !name.is_empty())
+}
+
+/// Does `name` end with `.extension`, ignoring case?
+fn has_extension(name: &str, extension: &str) -> bool {
+    let dots = extension.matches('.').count() + 1;
+
+    match name.rmatch_indices('.').nth(dots - 1) {
+        // a leading dot doesn't start an extension
+        Some((0, _)) | None => false,
+        Some((ix, _)) => eq_ignore_case(&name[ix + 1..], extension),
+    }
+}
+
+/// Strip `ancestor` from the front of a stored path, as long as it is made of
+/// whole components.
+pub(crate) fn relative_path<'p>(stored: &'p str, ancestor: &str) -> Option<&'p str> {
+    if ancestor.is_empty() {
+        return Some(stored);
+    }
+
+    let rest = stored.strip_prefix(ancestor)?;
+    if rest.is_empty() {
+        Some(rest)
+    } else {
+        rest.strip_prefix(SEPARATORS)
+    }
+}
+
+/// The last component of a stored path.
+pub(crate) fn file_name(path: &str) -> &str {
+    path.rsplit(SEPARATORS).next().unwrap_or(path)
+}
+
+const SEPARATORS: [char; 2] = ['/', '\\'];
+
+// why lookup_names() rejected a path
+const ABSOLUTE: &str = "embedded paths are always relative";
+const PARENT: &str = "embedded paths never contain \"..\"";
+#[cfg(feature = "std")]
+const NOT_UTF8: &str = "embedded paths are always valid UTF-8";
+
+impl<'a> IntoIterator for &Dir<'a> {
+    type Item = DirEntry<'a>;
+    type IntoIter = Walk<'a>;
 
-        extract_dir(*self, path)
+    fn into_iter(self) -> Self::IntoIter {
+        self.walk()
     }
 }
@@ -0,0 +1,137 @@
+use crate::dir::Dir;
+use crate::file::File;
+use crate::path::Path;
+use core::cmp::Ordering;
+
+/// A directory entry, either a file or a sub-directory.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DirEntry<'a> {
+    /// A file with its contents stored in a &'static [u8].
+    File(File<'a>),
+    /// A directory entry.
+    Dir(Dir<'a>),
+}
+
+/// Whether an entry is a file or a directory, returned by
+/// [`Dir::entry_kind()`] and [`DirEntry::kind()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EntryKind {
+    /// A file.
+    File,
+    /// A directory.
+    Dir,
+}
+
+impl<'a> DirEntry<'a> {
+    /// Is this entry a file or a directory?
+    pub fn kind(&self) -> EntryKind {
+        match *self {
+            DirEntry::File(_) => EntryKind::File,
+            DirEntry::Dir(_) => EntryKind::Dir,
+        }
+    }
+
+    /// Get the entries's path
+    pub fn path(&self) -> &'a Path {
+        match *self {
+            DirEntry::File(f) => f.path(),
+            DirEntry::Dir(d) => d.path(),
+        }
+    }
+
+    /// The entry's path relative to `ancestor`, or `None` if it isn't inside
+    /// `ancestor`.
+    ///
+    /// See [`File::relative_to()`] and [`Dir::relative_to()`].
+    pub fn relative_to(&self, ancestor: &Dir<'_>) -> Option<&'a Path> {
+        match *self {
+            DirEntry::File(f) => f.relative_to(ancestor),
+            DirEntry::Dir(d) => d.relative_to(ancestor),
+        }
+    }
+
+    /// Get the file, if this entry is a file.
+    pub fn as_file(&self) -> Option<File<'a>> {
+        match *self {
+            DirEntry::File(f) => Some(f),
+            DirEntry::Dir(_) => None,
+        }
+    }
+
+    /// Get the directory, if this entry is a directory.
+    pub fn as_dir(&self) -> Option<Dir<'a>> {
+        match *self {
+            DirEntry::File(_) => None,
+            DirEntry::Dir(d) => Some(d),
+        }
+    }
+}
+
+/// An iterator over the immediate children of a directory, created by
+/// [`Dir::entries()`].
+///
+/// Files and sub-directories are merged into a single sequence sorted by
+/// path.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(missing_copy_implementations)]
+pub struct Entries<'a> {
+    files: &'a [File<'a>],
+    dirs: &'a [Dir<'a>],
+}
+
+impl<'a> Entries<'a> {
+    pub(crate) fn new(dir: Dir<'a>) -> Entries<'a> {
+        Entries {
+            files: dir.files,
+            dirs: dir.dirs,
+        }
+    }
+
+    /// The entries of `parent` which come after `child`, as if `child` had
+    /// just been yielded.
+    pub(crate) fn after(parent: Dir<'a>, child: &Dir<'a>) -> Entries<'a> {
+        let files = parent.files.partition_point(|f| f.path <= child.path);
+        let dirs = parent.dirs.partition_point(|d| d.path <= child.path);
+
+        Entries {
+            files: &parent.files[files..],
+            dirs: &parent.dirs[dirs..],
+        }
+    }
+
+    fn next_file(&mut self) -> Option<DirEntry<'a>> {
+        let (first, rest) = self.files.split_first()?;
+        self.files = rest;
+        Some(DirEntry::File(*first))
+    }
+
+    fn next_dir(&mut self) -> Option<DirEntry<'a>> {
+        let (first, rest) = self.dirs.split_first()?;
+        self.dirs = rest;
+        Some(DirEntry::Dir(*first))
+    }
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = DirEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // both slices are sorted by the macro, so this is just the merge step
+        // from merge sort
+        match (self.files.first(), self.dirs.first()) {
+            (Some(f), Some(d)) => match f.path.cmp(d.path) {
+                Ordering::Greater => self.next_dir(),
+                _ => self.next_file(),
+            },
+            (Some(_), None) => self.next_file(),
+            (None, _) => self.next_dir(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.files.len() + self.dirs.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for Entries<'a> {}
@@ -0,0 +1,79 @@
+use crate::file::File;
+
+impl<'a> File<'a> {
+    /// A strong `ETag` for the file's contents, including the surrounding
+    /// quotes (e.g. `"3f2a…"`).
+    ///
+    /// With the `hash` feature this comes from the hash calculated at compile
+    /// time. Otherwise the contents are hashed (with FNV-1a) every time this
+    /// is called. Either way it only changes when the contents do.
+    pub fn etag(&self) -> String {
+        #[cfg(feature = "hash")]
+        let digest: String = self.hash()[..16]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+
+        #[cfg(not(feature = "hash"))]
+        let digest = {
+            let hash = self
+                .contents()
+                .iter()
+                .fold(0xcbf2_9ce4_8422_2325_u64, |h, b| {
+                    (h ^ u64::from(*b)).wrapping_mul(0x0000_0100_0000_01b3)
+                });
+            format!("{:016x}", hash)
+        };
+
+        format!("\"{}\"", digest)
+    }
+
+    /// Does an `If-None-Match` header match this file's [`File::etag()`]?
+    ///
+    /// When it does, the client already has the file and the response can
+    /// be a `304 Not Modified`. The header may be a comma-separated list of
+    /// tags, weak tags (`W/"..."`) match their strong equivalent as the
+    /// header requires, and `*` matches everything. A malformed header
+    /// matches nothing, so the file is just sent again.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir};
+    ///
+    /// static PROJECT: Dir<'_> = include_dir!(".");
+    ///
+    /// let file = PROJECT.get_file("Cargo.toml").unwrap();
+    /// let header = format!("\"something-else\", W/{}", file.etag());
+    /// assert!(file.matches_etag(&header));
+    /// assert!(!file.matches_etag("\"something-else\""));
+    /// ```
+    pub fn matches_etag(&self, if_none_match: &str) -> bool {
+        let etag = self.etag();
+        let own = &etag[1..etag.len() - 1];
+
+        let mut rest = if_none_match;
+        loop {
+            rest = rest.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+            if rest.is_empty() {
+                return false;
+            }
+            if rest.starts_with('*') {
+                return true;
+            }
+
+            let tag = rest.strip_prefix("W/").unwrap_or(rest);
+            let tag = match tag.strip_prefix('"') {
+                Some(tag) => tag,
+                None => return false,
+            };
+            let end = match tag.find('"') {
+                Some(end) => end,
+                None => return false,
+            };
+
+            if &tag[..end] == own {
+                return true;
+            }
+            rest = &tag[end + 1..];
+        }
+    }
+}
@@ -0,0 +1,632 @@
+use crate::dir::Dir;
+use crate::file::File;
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, Write};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// What to do when extracting a file that already exists on disk.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Collision {
+    /// Truncate the existing file and replace its contents.
+    Overwrite,
+    /// Leave the existing file untouched.
+    Skip,
+    /// Fail with [`io::ErrorKind::AlreadyExists`].
+    #[default]
+    Error,
+}
+
+/// Where entries end up relative to the directory being extracted into.
+///
+/// Every entry stores its full path from the directory passed to
+/// `include_dir!()`, including any `prefix`. This decides whether the part
+/// of that path belonging to the [`Dir`] being extracted is kept.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum PrefixBehavior {
+    /// Write the directory's contents straight into the destination, so
+    /// extracting a `Dir` with the path `assets` writes `assets/css/main.css`
+    /// to `<dest>/css/main.css`.
+    #[default]
+    StripRoot,
+    /// Recreate the directory's own path inside the destination, so
+    /// `assets/css/main.css` is written to `<dest>/assets/css/main.css`.
+    KeepRoot,
+}
+
+/// Options controlling how [`Dir::extract_with()`] writes to disk.
+///
+/// The defaults match [`Dir::extract()`]; existing files are an error,
+/// every file is synced to disk after it is written, the directory's own
+/// path is [stripped](PrefixBehavior::StripRoot), and anything else already
+/// in the destination is left alone.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ExtractOptions {
+    pub(crate) collision: Collision,
+    pub(crate) sync: bool,
+    pub(crate) prefix: PrefixBehavior,
+    pub(crate) clean: bool,
+    #[cfg(feature = "tokio")]
+    pub(crate) concurrency: usize,
+}
+
+impl ExtractOptions {
+    /// Create a new set of options with the default settings.
+    pub fn new() -> ExtractOptions {
+        ExtractOptions::default()
+    }
+
+    /// Set what happens when a file already exists.
+    pub fn collision(mut self, collision: Collision) -> ExtractOptions {
+        self.collision = collision;
+        self
+    }
+
+    /// Should `sync_all()` be called on each file after it is written?
+    ///
+    /// Turning this off makes extracting lots of small files much faster,
+    /// at the cost of durability if the machine crashes part-way through.
+    pub fn sync(mut self, sync: bool) -> ExtractOptions {
+        self.sync = sync;
+        self
+    }
+
+    /// Set whether the extracted directory's own path is recreated inside
+    /// the destination.
+    pub fn prefix_behavior(mut self, prefix: PrefixBehavior) -> ExtractOptions {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Should anything in the destination which isn't part of the embedded
+    /// tree be deleted, so the destination ends up an exact copy of it?
+    ///
+    /// This is for extracting over an older version, where files which have
+    /// since been removed would otherwise be left behind. Cleaning happens
+    /// after everything has been written, so it is usually combined with
+    /// [`Collision::Overwrite`]. Removed paths are listed in
+    /// [`ExtractReport::removed()`].
+    ///
+    /// Extracting fails with [`io::ErrorKind::InvalidInput`] before anything
+    /// is written if the destination exists but isn't a directory, or is
+    /// the root of a filesystem. Symlinks inside the destination are
+    /// removed (or kept) as links, so nothing they point to is ever deleted.
+    pub fn clean(mut self, clean: bool) -> ExtractOptions {
+        self.clean = clean;
+        self
+    }
+
+    /// The maximum number of files [`Dir::extract_async()`] will write at
+    /// the same time (defaults to 8).
+    ///
+    /// # Panics
+    ///
+    /// The concurrency must be at least 1.
+    #[cfg(feature = "tokio")]
+    pub fn concurrency(mut self, concurrency: usize) -> ExtractOptions {
+        assert!(concurrency > 0, "The concurrency must be at least 1");
+        self.concurrency = concurrency;
+        self
+    }
+}
+
+impl Default for ExtractOptions {
+    fn default() -> ExtractOptions {
+        ExtractOptions {
+            collision: Collision::default(),
+            sync: true,
+            prefix: PrefixBehavior::default(),
+            clean: false,
+            #[cfg(feature = "tokio")]
+            concurrency: 8,
+        }
+    }
+}
+
+/// A summary of what [`Dir::extract_with()`] did.
+///
+/// All paths are relative to the directory being extracted into.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExtractReport {
+    pub(crate) created_files: Vec<PathBuf>,
+    pub(crate) skipped_files: Vec<PathBuf>,
+    pub(crate) created_dirs: Vec<PathBuf>,
+    pub(crate) removed: Vec<PathBuf>,
+}
+
+impl ExtractReport {
+    /// Files which were written to disk.
+    pub fn created_files(&self) -> &[PathBuf] {
+        &self.created_files
+    }
+
+    /// Files which already existed and were left untouched.
+    pub fn skipped_files(&self) -> &[PathBuf] {
+        &self.skipped_files
+    }
+
+    /// Directories which didn't exist before extracting.
+    pub fn created_dirs(&self) -> &[PathBuf] {
+        &self.created_dirs
+    }
+
+    /// Files and directories which weren't part of the embedded tree and
+    /// were deleted because of [`ExtractOptions::clean()`]. Only the top of
+    /// each removed directory is listed.
+    pub fn removed(&self) -> &[PathBuf] {
+        &self.removed
+    }
+}
+
+/// Something which happened while extracting, passed to the callback given
+/// to [`Dir::extract_with_progress()`].
+///
+/// Paths are relative to the directory being extracted into, like in
+/// [`ExtractReport`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExtractEvent<'p> {
+    /// A directory was created.
+    DirCreated(&'p Path),
+    /// A file is about to be written. The size is the number of bytes which
+    /// will be written, after decompressing.
+    FileStart {
+        /// Where the file is being written.
+        path: &'p Path,
+        /// How many bytes are going to be written.
+        size: u64,
+    },
+    /// A file was written.
+    FileDone {
+        /// Where the file was written.
+        path: &'p Path,
+    },
+    /// A file already existed and was left alone because of
+    /// [`Collision::Skip`]. This comes after its `FileStart`, instead of
+    /// `FileDone`.
+    FileSkipped {
+        /// The file which already existed.
+        path: &'p Path,
+    },
+}
+
+/// The callback used to report an extraction's progress.
+pub(crate) type Progress<'p> = dyn FnMut(ExtractEvent<'_>) -> ControlFlow<()> + 'p;
+
+impl<'a> Dir<'a> {
+    /// Extract all files into `path`, choosing whether this directory's own
+    /// path is recreated inside it.
+    ///
+    /// This is a shortcut for [`Dir::extract_with()`] using the default
+    /// options with [`ExtractOptions::prefix_behavior()`].
+    pub fn extract_into<S: AsRef<Path>>(&self, path: S, prefix: PrefixBehavior) -> io::Result<()> {
+        let options = ExtractOptions::new().prefix_behavior(prefix);
+        self.extract_with(path, &options).map(|_| ())
+    }
+
+    /// Extract all files to the real filesystem, using the provided
+    /// [`ExtractOptions`].
+    ///
+    /// Errors are annotated with the path that couldn't be written. In case
+    /// of error, partially extracted directory may remain on the filesystem.
+    pub fn extract_with<S: AsRef<Path>>(
+        &self,
+        path: S,
+        options: &ExtractOptions,
+    ) -> io::Result<ExtractReport> {
+        self.extract_reporting(path.as_ref(), options, None)
+    }
+
+    /// Extract all files, like [`Dir::extract_with()`], calling `progress`
+    /// as each directory and file is written.
+    ///
+    /// Combined with [`Dir::total_size()`], the sizes in
+    /// [`ExtractEvent::FileStart`] are enough to drive a progress bar. The
+    /// callback can stop extracting by returning [`ControlFlow::Break`], in
+    /// which case this fails with [`io::ErrorKind::Interrupted`] and
+    /// whatever was already written is left on disk.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir, ExtractEvent, ExtractOptions};
+    /// use std::ops::ControlFlow;
+    ///
+    /// static PROJECT: Dir<'_> = include_dir!(".");
+    ///
+    /// # let tmp = tempdir::TempDir::new("progress").unwrap();
+    /// # let destination = tmp.path();
+    /// let total = PROJECT.total_size();
+    /// let mut written = 0;
+    ///
+    /// PROJECT.extract_with_progress(destination, &ExtractOptions::new(), |event| {
+    ///     if let ExtractEvent::FileStart { size, .. } = event {
+    ///         written += size;
+    ///         println!("{}/{} bytes", written, total);
+    ///     }
+    ///     ControlFlow::Continue(())
+    /// })?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn extract_with_progress<S, F>(
+        &self,
+        path: S,
+        options: &ExtractOptions,
+        mut progress: F,
+    ) -> io::Result<ExtractReport>
+    where
+        S: AsRef<Path>,
+        F: FnMut(ExtractEvent<'_>) -> ControlFlow<()>,
+    {
+        self.extract_reporting(path.as_ref(), options, Some(&mut progress))
+    }
+
+    fn extract_reporting<'p>(
+        &self,
+        root: &'p Path,
+        options: &'p ExtractOptions,
+        progress: Option<&'p mut Progress<'p>>,
+    ) -> io::Result<ExtractReport>
+    where
+        'a: 'p,
+    {
+        if options.clean {
+            check_clean_destination(root)?;
+        }
+        fs::create_dir_all(root).map_err(|e| annotate(e, root))?;
+
+        let mut extractor = Extractor {
+            root,
+            dir_path: self.path(),
+            options,
+            report: ExtractReport::default(),
+            progress,
+        };
+        // only does anything when keeping the root
+        extractor.create_dir(self.path())?;
+        extractor.extract_dir(*self)?;
+
+        if options.clean {
+            extractor.report.removed = remove_stale(*self, root, options)?;
+        }
+
+        Ok(extractor.report)
+    }
+
+    /// Extract into a temporary directory next to `path`, then rename it
+    /// into place once every file has been written.
+    ///
+    /// This means `path` either doesn't exist or contains everything, even
+    /// if extraction fails part-way through (the temporary directory is
+    /// removed). If `path` already exists, the [`Collision`] policy decides
+    /// what happens to it as a whole:
+    ///
+    /// - [`Collision::Error`] fails with [`io::ErrorKind::AlreadyExists`]
+    /// - [`Collision::Skip`] leaves it alone, reporting every file as
+    ///   skipped
+    /// - [`Collision::Overwrite`] replaces it. The old directory is moved
+    ///   aside before the new one is renamed into place, so there is a brief
+    ///   moment where `path` doesn't exist. Removing the old directory
+    ///   afterwards is best-effort: if it fails, it is left next to `path`
+    ///   with a hidden `.old` name and extraction still succeeds
+    ///
+    /// Renaming only works within a single filesystem. The temporary
+    /// directory is created in the same parent as `path` to make sure that
+    /// is the case, but if `path` is itself a mount point the rename fails
+    /// and that error is returned without touching `path`.
+    pub fn extract_atomic<S: AsRef<Path>>(
+        &self,
+        path: S,
+        options: &ExtractOptions,
+    ) -> io::Result<ExtractReport> {
+        let target = path.as_ref();
+        let exists = target.exists();
+
+        if exists && options.collision == Collision::Error {
+            return Err(annotate(
+                io::Error::from(io::ErrorKind::AlreadyExists),
+                target,
+            ));
+        }
+        if exists && options.collision == Collision::Skip {
+            return Ok(ExtractReport {
+                skipped_files: self
+                    .walk()
+                    .filter_map(|e| e.as_file())
+                    .map(|f| destination(self.path(), f.path(), options).to_path_buf())
+                    .collect(),
+                ..ExtractReport::default()
+            });
+        }
+
+        let temp = sibling(target, "tmp");
+        let report = self
+            .extract_with(&temp, options)
+            .and_then(|report| replace(&temp, target).map(|_| report));
+
+        if report.is_err() {
+            let _ = fs::remove_dir_all(&temp);
+        }
+
+        report
+    }
+}
+
+/// Make sure it is safe to delete things inside `root` for
+/// [`ExtractOptions::clean()`].
+pub(crate) fn check_clean_destination(root: &Path) -> io::Result<()> {
+    let invalid = |msg: &str| annotate(io::Error::new(io::ErrorKind::InvalidInput, msg), root);
+
+    match fs::metadata(root) {
+        Ok(meta) if !meta.is_dir() => return Err(invalid("The destination isn't a directory")),
+        Ok(_) => {}
+        // nothing to clean
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(annotate(e, root)),
+    }
+
+    let canonical = root.canonicalize().map_err(|e| annotate(e, root))?;
+    if canonical.parent().is_none() {
+        return Err(invalid("Refusing to clean the root of a filesystem"));
+    }
+
+    Ok(())
+}
+
+/// Delete everything inside `root` which extracting `dir` didn't write,
+/// returning what was removed relative to `root`.
+pub(crate) fn remove_stale(
+    dir: Dir<'_>,
+    root: &Path,
+    options: &ExtractOptions,
+) -> io::Result<Vec<PathBuf>> {
+    let mut expected = HashSet::new();
+    let entries = dir.walk().map(|e| e.path()).chain(Some(dir.path()));
+    for stored in entries {
+        let relative = destination(dir.path(), stored, options);
+        expected.extend(relative.ancestors().map(Path::to_path_buf));
+    }
+
+    let mut removed = Vec::new();
+    remove_stale_in(root, Path::new(""), &expected, &mut removed)?;
+
+    Ok(removed)
+}
+
+fn remove_stale_in(
+    root: &Path,
+    relative: &Path,
+    expected: &HashSet<PathBuf>,
+    removed: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    let dir = root.join(relative);
+    let mut names = fs::read_dir(&dir)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|e| e.file_name()))
+                .collect::<io::Result<Vec<_>>>()
+        })
+        .map_err(|e| annotate(e, &dir))?;
+    // remove things in a consistent order
+    names.sort();
+
+    for name in names {
+        let relative = relative.join(name);
+        let path = root.join(&relative);
+        // this doesn't follow symlinks, so a link to a directory is never
+        // recursed into
+        let meta = fs::symlink_metadata(&path).map_err(|e| annotate(e, &path))?;
+
+        if expected.contains(&relative) {
+            if meta.is_dir() {
+                remove_stale_in(root, &relative, expected, removed)?;
+            }
+            continue;
+        }
+
+        let result = if meta.is_dir() {
+            fs::remove_dir_all(&path)
+        } else {
+            fs::remove_file(&path)
+        };
+        // symlinks to directories have to be removed as directories on
+        // Windows
+        #[cfg(windows)]
+        let result = result.or_else(|e| {
+            if meta.file_type().is_symlink() {
+                fs::remove_dir(&path)
+            } else {
+                Err(e)
+            }
+        });
+        result.map_err(|e| annotate(e, &path))?;
+
+        removed.push(relative);
+    }
+
+    Ok(())
+}
+
+/// Rename `from` to `to`, moving anything already at `to` out of the way
+/// first.
+fn replace(from: &Path, to: &Path) -> io::Result<()> {
+    if !to.exists() {
+        return fs::rename(from, to).map_err(|e| annotate(e, to));
+    }
+
+    let old = sibling(to, "old");
+    fs::rename(to, &old).map_err(|e| annotate(e, to))?;
+
+    if let Err(e) = fs::rename(from, to) {
+        // put things back the way they were
+        let _ = fs::rename(&old, to);
+        return Err(annotate(e, to));
+    }
+
+    // `to` has already been replaced, so failing now would only make the
+    // caller think it hadn't been. Leave anything which can't be removed.
+    let _ = fs::remove_dir_all(&old);
+
+    Ok(())
+}
+
+/// A unique path in the same directory as `path`.
+fn sibling(path: &Path, kind: &str) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy())
+        .unwrap_or_default();
+    let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    path.with_file_name(format!(".{}.{}-{}-{}", name, kind, process::id(), unique))
+}
+
+pub(crate) struct Extractor<'p> {
+    pub(crate) root: &'p Path,
+    /// The path of the directory being extracted.
+    pub(crate) dir_path: &'p Path,
+    pub(crate) options: &'p ExtractOptions,
+    pub(crate) report: ExtractReport,
+    pub(crate) progress: Option<&'p mut Progress<'p>>,
+}
+
+impl<'p> Extractor<'p> {
+    // Extracts the given directory entry, recursing on subdirectories
+    fn extract_dir(&mut self, dir: Dir<'_>) -> io::Result<()> {
+        // Create all the subdirectories in here (but not their files yet)
+        for dir in dir.dirs() {
+            self.create_dir(dir.path())?;
+            self.extract_dir(*dir)?;
+        }
+
+        // Only write files at the root of this directory (we recurse on subdirectories)
+        for file in dir.files() {
+            self.write_file(file)?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn create_dir(&mut self, stored: &Path) -> io::Result<()> {
+        let relative = destination(self.dir_path, stored, self.options);
+        if relative.as_os_str().is_empty() {
+            return Ok(());
+        }
+        let path = self.root.join(relative);
+
+        if !path.is_dir() {
+            fs::create_dir_all(&path).map_err(|e| annotate(e, &path))?;
+            self.report.created_dirs.push(relative.to_path_buf());
+            self.emit(ExtractEvent::DirCreated(relative))?;
+        }
+
+        Ok(())
+    }
+
+    /// Tell the progress callback (if there is one) what happened.
+    fn emit(&mut self, event: ExtractEvent<'_>) -> io::Result<()> {
+        let progress = match self.progress {
+            Some(ref mut progress) => progress,
+            None => return Ok(()),
+        };
+
+        match progress(event) {
+            ControlFlow::Continue(()) => Ok(()),
+            ControlFlow::Break(()) => Err(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "Extraction was cancelled",
+            )),
+        }
+    }
+
+    pub(crate) fn write_file(&mut self, file: &File<'_>) -> io::Result<()> {
+        let relative = destination(self.dir_path, file.path(), self.options);
+        let path = self.root.join(relative);
+        let contents = file.uncompressed();
+
+        self.emit(ExtractEvent::FileStart {
+            path: relative,
+            size: contents.len() as u64,
+        })?;
+
+        let mut fsf = match open_options(self.options).open(&path) {
+            Ok(f) => f,
+            Err(ref e)
+                if e.kind() == io::ErrorKind::AlreadyExists
+                    && self.options.collision == Collision::Skip =>
+            {
+                self.report.skipped_files.push(relative.to_path_buf());
+                return self.emit(ExtractEvent::FileSkipped { path: relative });
+            }
+            Err(e) => return Err(annotate(e, &path)),
+        };
+
+        fsf.write_all(&contents).map_err(|e| annotate(e, &path))?;
+
+        if let Some(permissions) = permissions(file) {
+            fsf.set_permissions(permissions)
+                .map_err(|e| annotate(e, &path))?;
+        }
+
+        if self.options.sync {
+            fsf.sync_all().map_err(|e| annotate(e, &path))?;
+        }
+
+        self.report.created_files.push(relative.to_path_buf());
+
+        self.emit(ExtractEvent::FileDone { path: relative })
+    }
+}
+
+/// Where an entry with the `stored` path ends up, relative to the
+/// destination, when extracting the directory at `dir_path`.
+pub(crate) fn destination<'p>(
+    dir_path: &Path,
+    stored: &'p Path,
+    options: &ExtractOptions,
+) -> &'p Path {
+    match options.prefix {
+        PrefixBehavior::StripRoot => stored.strip_prefix(dir_path).unwrap_or(stored),
+        PrefixBehavior::KeepRoot => stored,
+    }
+}
+
+/// How to open a file being extracted, depending on the [`Collision`]
+/// policy.
+pub(crate) fn open_options(options: &ExtractOptions) -> fs::OpenOptions {
+    let mut open_options = fs::OpenOptions::new();
+    open_options.write(true);
+
+    match options.collision {
+        Collision::Overwrite => open_options.create(true).truncate(true),
+        Collision::Skip | Collision::Error => open_options.create_new(true),
+    };
+
+    open_options
+}
+
+/// The permissions a file should be given after it is extracted, if any
+/// were recorded when it was embedded.
+#[cfg(all(unix, feature = "metadata"))]
+pub(crate) fn permissions(file: &File<'_>) -> Option<fs::Permissions> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = file.metadata()?.mode()?;
+    Some(fs::Permissions::from_mode(mode))
+}
+
+#[cfg(not(all(unix, feature = "metadata")))]
+pub(crate) fn permissions(_file: &File<'_>) -> Option<fs::Permissions> {
+    None
+}
+
+/// Wrap an error with the path it came from, keeping the original
+/// [`io::ErrorKind`].
+pub(crate) fn annotate(error: io::Error, path: &Path) -> io::Error {
+    io::Error::new(
+        error.kind(),
+        format!("Unable to extract \"{}\": {}", path.display(), error),
+    )
+}
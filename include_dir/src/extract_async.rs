@@ -0,0 +1,191 @@
+use crate::dir::Dir;
+use crate::extract::{
+    annotate, check_clean_destination, destination, open_options, permissions, remove_stale,
+    Collision, ExtractOptions, ExtractReport,
+};
+use crate::file::File;
+use std::borrow::Cow;
+use std::io;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio::task::JoinSet;
+
+enum Written {
+    Created(PathBuf),
+    Skipped(PathBuf),
+}
+
+impl Dir<'static> {
+    /// Extract all files to the real filesystem from inside an async
+    /// runtime, using [`tokio::fs`].
+    ///
+    /// This produces the same result as [`Dir::extract_with()`] and uses the
+    /// same [`ExtractOptions`], except up to
+    /// [`ExtractOptions::concurrency()`] files are written in parallel.
+    /// Directories are all created before any files are written, and the
+    /// paths in the [`ExtractReport`] are listed in the same order as the
+    /// synchronous version would write them.
+    ///
+    /// If any file fails, the remaining writes are cancelled and the error is
+    /// returned. In case of error, partially extracted directory may remain
+    /// on the filesystem. [Cleaning](ExtractOptions::clean()) runs on a
+    /// blocking thread once every file has been written.
+    pub async fn extract_async<S: AsRef<Path>>(
+        &self,
+        path: S,
+        options: &ExtractOptions,
+    ) -> io::Result<ExtractReport> {
+        let root = path.as_ref();
+        if options.clean {
+            let root = root.to_path_buf();
+            blocking(move || check_clean_destination(&root)).await?;
+        }
+        fs::create_dir_all(root)
+            .await
+            .map_err(|e| annotate(e, root))?;
+
+        let mut report = ExtractReport::default();
+
+        // this directory itself only needs creating when keeping the root
+        let dirs = std::iter::once(*self).chain(self.walk().filter_map(|e| e.as_dir()));
+
+        for dir in dirs {
+            let relative = destination(self.path(), dir.path(), options);
+            if !relative.as_os_str().is_empty() {
+                let path = root.join(relative);
+
+                if !fs::metadata(&path)
+                    .await
+                    .map(|m| m.is_dir())
+                    .unwrap_or(false)
+                {
+                    fs::create_dir_all(&path)
+                        .await
+                        .map_err(|e| annotate(e, &path))?;
+                    report.created_dirs.push(relative.to_path_buf());
+                }
+            }
+        }
+
+        let mut files = Vec::new();
+        files_in_extract_order(*self, &mut files);
+
+        let mut tasks = JoinSet::new();
+        let mut written = Vec::with_capacity(files.len());
+
+        for (ix, file) in files.into_iter().enumerate() {
+            while tasks.len() >= options.concurrency {
+                written.push(join_next(&mut tasks).await?);
+            }
+
+            let relative = destination(self.path(), file.path(), options).to_path_buf();
+            let options = *options;
+            let path = root.join(&relative);
+            tasks.spawn(async move { (ix, write_file(file, path, relative, options).await) });
+        }
+
+        while !tasks.is_empty() {
+            written.push(join_next(&mut tasks).await?);
+        }
+
+        written.sort_by_key(|(ix, _)| *ix);
+        for (_, outcome) in written {
+            match outcome {
+                Written::Created(path) => report.created_files.push(path),
+                Written::Skipped(path) => report.skipped_files.push(path),
+            }
+        }
+
+        if options.clean {
+            let (dir, root, options) = (*self, root.to_path_buf(), *options);
+            report.removed = blocking(move || remove_stale(dir, &root, &options)).await?;
+        }
+
+        Ok(report)
+    }
+}
+
+/// The synchronous version writes a directory's files after everything in
+/// its sub-directories.
+fn files_in_extract_order(dir: Dir<'static>, files: &mut Vec<File<'static>>) {
+    for sub_dir in dir.dirs() {
+        files_in_extract_order(*sub_dir, files);
+    }
+
+    files.extend_from_slice(dir.files());
+}
+
+/// Run some synchronous filesystem code without stalling the runtime.
+async fn blocking<T, F>(op: F) -> io::Result<T>
+where
+    F: FnOnce() -> io::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(op)
+        .await
+        .map_err(io::Error::other)?
+}
+
+async fn join_next(
+    tasks: &mut JoinSet<(usize, io::Result<Written>)>,
+) -> io::Result<(usize, Written)> {
+    match tasks.join_next().await {
+        Some(Ok((ix, Ok(written)))) => Ok((ix, written)),
+        Some(Ok((_, Err(e)))) => Err(e),
+        Some(Err(e)) => Err(io::Error::other(e)),
+        None => unreachable!("Only called when there are tasks left"),
+    }
+}
+
+async fn write_file(
+    file: File<'static>,
+    path: PathBuf,
+    relative: PathBuf,
+    options: ExtractOptions,
+) -> io::Result<Written> {
+    let mut fsf = match fs::OpenOptions::from(open_options(&options))
+        .open(&path)
+        .await
+    {
+        Ok(f) => f,
+        Err(ref e)
+            if e.kind() == io::ErrorKind::AlreadyExists && options.collision == Collision::Skip =>
+        {
+            return Ok(Written::Skipped(relative));
+        }
+        Err(e) => return Err(annotate(e, &path)),
+    };
+
+    let contents = contents_of(file).await?;
+    fsf.write_all(&contents)
+        .await
+        .map_err(|e| annotate(e, &path))?;
+    // tokio writes in the background, so make sure it has finished
+    fsf.flush().await.map_err(|e| annotate(e, &path))?;
+
+    if let Some(permissions) = permissions(&file) {
+        fsf.set_permissions(permissions)
+            .await
+            .map_err(|e| annotate(e, &path))?;
+    }
+
+    if options.sync {
+        fsf.sync_all().await.map_err(|e| annotate(e, &path))?;
+    }
+
+    Ok(Written::Created(relative))
+}
+
+/// Get the file's original contents, decompressing on a blocking thread so
+/// big files don't stall the runtime.
+async fn contents_of(file: File<'static>) -> io::Result<Cow<'static, [u8]>> {
+    #[cfg(feature = "compression")]
+    if file.is_compressed() {
+        return tokio::task::spawn_blocking(move || file.uncompressed())
+            .await
+            .map_err(io::Error::other);
+    }
+
+    Ok(file.uncompressed())
+}
@@ -0,0 +1,80 @@
+//! Views of the embedded data with a stable layout, for handing to C.
+
+use crate::dir::Dir;
+use crate::file::File;
+
+/// A file's path and contents as raw pointers, with a C-compatible layout.
+///
+/// The fields are laid out in exactly this order, so it matches this C
+/// struct:
+///
+/// ```c
+/// struct include_dir_file {
+///     const uint8_t *path;
+///     size_t path_len;
+///     const uint8_t *contents;
+///     size_t contents_len;
+///     bool compressed;
+/// };
+/// ```
+///
+/// The layout is part of this crate's public API, so it will only change
+/// in a semver-incompatible release.
+///
+/// Neither string is NUL-terminated. The path is valid UTF-8, and both
+/// point into the embedded data so they are valid for as long as the
+/// [`Dir`] they came from.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RawFile {
+    /// The start of the file's path, the same one [`File::path()`] returns.
+    pub path: *const u8,
+    /// The length of the path in bytes.
+    pub path_len: usize,
+    /// The start of the file's contents, exactly as they are stored.
+    pub contents: *const u8,
+    /// The length of the stored contents in bytes.
+    pub contents_len: usize,
+    /// Are the contents compressed with DEFLATE? This is always `false`
+    /// unless the `compression` feature is enabled.
+    pub compressed: bool,
+}
+
+impl<'a> File<'a> {
+    /// The location and length of the file's stored contents, for passing
+    /// to C without copying them.
+    pub fn as_ptr_len(&self) -> (*const u8, usize) {
+        (self.contents.as_ptr(), self.contents.len())
+    }
+
+    /// The file as a [`RawFile`].
+    pub fn as_raw(&self) -> RawFile {
+        let (contents, contents_len) = self.as_ptr_len();
+
+        RawFile {
+            path: self.path.as_ptr(),
+            path_len: self.path.len(),
+            contents,
+            contents_len,
+            #[cfg(feature = "compression")]
+            compressed: self.compressed,
+            #[cfg(not(feature = "compression"))]
+            compressed: false,
+        }
+    }
+}
+
+impl<'a> Dir<'a> {
+    /// Every file inside this directory and its sub-directories as a
+    /// [`RawFile`], in the same order as [`Dir::walk()`].
+    ///
+    /// Only the table is allocated, the paths and contents aren't copied.
+    /// Directories aren't listed, C code can find them from the paths.
+    #[cfg(feature = "std")]
+    pub fn raw_files(&self) -> Vec<RawFile> {
+        self.walk()
+            .filter_map(|entry| entry.as_file())
+            .map(|file| file.as_raw())
+            .collect()
+    }
+}
@@ -1,39 +1,367 @@
-use std::fmt::{self, Debug, Formatter};
-use std::path::Path;
-use std::str;
+#[cfg(feature = "compression")]
+use crate::chunks::DecompressedChunks;
+use crate::dir::{file_name, relative_path, Dir};
+#[cfg(feature = "metadata")]
+use crate::metadata::Metadata;
+use crate::mime;
+use crate::path::{self, Path};
+#[cfg(feature = "std")]
+use crate::reader::FileReader;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use core::str;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
 
 /// A file with its contents stored in a `&'static [u8]`.
-#[derive(Copy, Clone, PartialEq)]
+///
+/// A `File` dereferences to its [raw contents](File::contents), so slice
+/// methods can be used directly. Comparisons, ordering, and hashing only
+/// look at the file's path, not its contents, which makes it easy to keep
+/// files in a `HashSet` or `BTreeMap`.
+///
+/// Because of this, comparing anything which holds files (a [`Dir`], a
+/// `DirEntry`, etc.) only compares their layout: two trees with the same
+/// paths are equal even if their files' contents differ. Compare
+/// [`File::contents()`] directly to tell them apart.
+#[derive(Copy, Clone)]
 pub struct File<'a> {
     #[doc(hidden)]
     pub path: &'a str,
     #[doc(hidden)]
     pub contents: &'a [u8],
+    #[cfg(feature = "metadata")]
+    #[doc(hidden)]
+    pub metadata: Option<Metadata>,
+    #[cfg(feature = "hash")]
+    #[doc(hidden)]
+    pub hash: [u8; 32],
+    #[cfg(feature = "compression")]
+    #[doc(hidden)]
+    pub compressed: bool,
+    #[cfg(feature = "attributes")]
+    #[doc(hidden)]
+    pub attrs: &'a [(&'a str, &'a str)],
 }
 
 impl<'a> File<'a> {
     /// The file's path, relative to the directory included with
     /// `include_dir!()`.
     pub fn path(&self) -> &'a Path {
-        Path::new(self.path)
+        path::new(self.path)
+    }
+
+    /// The file's path relative to `ancestor`, or `None` if it isn't inside
+    /// `ancestor`.
+    ///
+    /// This only compares paths, so `ancestor` can be the file's parent or
+    /// any directory above it. For example, `migrations/2023/001_posts.sql`
+    /// relative to `migrations` is `2023/001_posts.sql`.
+    pub fn relative_to(&self, ancestor: &Dir<'_>) -> Option<&'a Path> {
+        relative_path(self.path, ancestor.path).map(path::new)
+    }
+
+    /// The last component of the file's path, e.g. `"001_posts.sql"` for
+    /// `migrations/2023/001_posts.sql`.
+    pub fn file_name(&self) -> &'a str {
+        file_name(self.path)
+    }
+
+    /// The part of the file name after the last `.`, if there is one.
+    ///
+    /// Like [`Path::extension()`], a leading dot doesn't count, so
+    /// `.gitignore` has no extension and `archive.tar.gz` has `gz`.
+    pub fn extension(&self) -> Option<&'a str> {
+        split_extension(self.file_name()).1
+    }
+
+    /// The file name without its [extension](File::extension).
+    ///
+    /// `archive.tar.gz` gives `archive.tar`, and dotfiles like `.gitignore`
+    /// are returned unchanged. This is only `None` if the file name is empty.
+    pub fn stem(&self) -> Option<&'a str> {
+        let name = self.file_name();
+        if name.is_empty() {
+            return None;
+        }
+
+        Some(split_extension(name).0)
+    }
+
+    /// Guess the file's MIME type from its [extension](File::extension),
+    /// ignoring case.
+    ///
+    /// This uses a small built-in table of the types commonly found on the
+    /// web (HTML, CSS, JavaScript, images, fonts, WebAssembly, etc.), and
+    /// returns `None` for anything it doesn't recognise.
+    pub fn mime_type(&self) -> Option<&'static str> {
+        mime::from_extension(self.extension()?)
     }
 
     /// The file's raw contents.
+    ///
+    /// If the file [was compressed](File::is_compressed), these are the
+    /// compressed bytes. Use [`File::decompressed_contents()`] to get the
+    /// original contents.
     pub fn contents(&self) -> &'a [u8] {
         self.contents
     }
 
+    /// The number of bytes in the file's [raw contents](File::contents).
+    ///
+    /// For a [compressed](File::is_compressed) file this is the compressed
+    /// size.
+    pub fn len(&self) -> usize {
+        self.contents.len()
+    }
+
+    /// Are the file's [raw contents](File::contents) empty?
+    pub fn is_empty(&self) -> bool {
+        self.contents.is_empty()
+    }
+
+    /// Iterate over the file's [raw contents](File::contents) in pieces of
+    /// `chunk_size` bytes, without copying them. The last chunk is shorter if
+    /// the size doesn't divide evenly.
+    ///
+    /// Use [`File::decompressed_chunks()`] to stream the original contents
+    /// of a compressed file.
+    ///
+    /// # Panics
+    ///
+    /// The chunk size must be at least 1.
+    pub fn chunks(&self, chunk_size: usize) -> core::slice::Chunks<'a, u8> {
+        assert!(chunk_size > 0, "The chunk size must be at least 1");
+        self.contents.chunks(chunk_size)
+    }
+
+    /// Iterate over the file's original contents in pieces of `chunk_size`
+    /// bytes, decompressing them as it goes so the whole file is never held
+    /// in memory.
+    ///
+    /// Files which aren't compressed are borrowed, just like
+    /// [`File::chunks()`]. Every chunk except the last is exactly
+    /// `chunk_size` bytes long.
+    ///
+    /// # Panics
+    ///
+    /// The chunk size must be at least 1.
+    #[cfg(feature = "compression")]
+    pub fn decompressed_chunks(&self, chunk_size: usize) -> DecompressedChunks<'a> {
+        assert!(chunk_size > 0, "The chunk size must be at least 1");
+        DecompressedChunks::new(self, chunk_size)
+    }
+
+    /// Get a reader over the file's [raw contents](File::contents), for APIs
+    /// which want something implementing [`Read`](std::io::Read) and
+    /// [`Seek`](std::io::Seek).
+    #[cfg(feature = "std")]
+    pub fn reader(&self) -> FileReader<'a> {
+        FileReader::new(self)
+    }
+
+    /// Was this file compressed when it was embedded?
+    ///
+    /// Only files embedded with `compress = true` are compressed, and even
+    /// then files are stored as-is when they are too small or compression
+    /// wouldn't make them any smaller.
+    #[cfg(feature = "compression")]
+    pub fn is_compressed(&self) -> bool {
+        self.compressed
+    }
+
+    /// The file's original contents, decompressing them if necessary.
+    ///
+    /// This doesn't allocate when the file isn't compressed.
+    #[cfg(feature = "compression")]
+    pub fn decompressed_contents(&self) -> Cow<'a, [u8]> {
+        if self.compressed {
+            let decompressed = miniz_oxide::inflate::decompress_to_vec(self.contents)
+                .expect("The embedded data should always be valid DEFLATE");
+            Cow::Owned(decompressed)
+        } else {
+            Cow::Borrowed(self.contents)
+        }
+    }
+
+    /// The file's original contents, for internal code which shouldn't care
+    /// whether the file was compressed.
+    #[cfg(feature = "std")]
+    pub(crate) fn uncompressed(&self) -> Cow<'a, [u8]> {
+        #[cfg(feature = "compression")]
+        return self.decompressed_contents();
+        #[cfg(not(feature = "compression"))]
+        return Cow::Borrowed(self.contents);
+    }
+
+    /// Without `std` nothing can be compressed, so the original contents
+    /// are always the raw contents.
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn uncompressed(&self) -> &'a [u8] {
+        self.contents
+    }
+
     /// The file's contents interpreted as a string.
+    ///
+    /// Returns `None` if the contents aren't valid UTF-8, or if the file
+    /// [was compressed](File::is_compressed) since the compressed bytes
+    /// aren't text even when they happen to be valid UTF-8. The string is
+    /// returned exactly as it was embedded, so empty files give `Some("")`
+    /// and a leading byte order mark is kept.
     pub fn contents_utf8(&self) -> Option<&'a str> {
+        #[cfg(feature = "compression")]
+        if self.compressed {
+            return None;
+        }
+
         str::from_utf8(self.contents()).ok()
     }
+
+    /// Iterate over the lines of the file's contents, or `None` if they
+    /// aren't [valid UTF-8](File::contents_utf8).
+    ///
+    /// This is [`str::lines()`], so lines end with either `\n` or `\r\n`
+    /// (which isn't included), a trailing newline doesn't add an empty line,
+    /// and an empty file has no lines at all. The lines borrow from the
+    /// embedded data rather than from the `File`, so they can be kept around
+    /// for as long as the contents are.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir};
+    ///
+    /// static TEXT: Dir<'_> = include_dir!("tests/fixtures/text");
+    ///
+    /// let crlf = TEXT.get_file("crlf.txt").unwrap();
+    /// let lines: Vec<&'static str> = crlf.lines().unwrap().collect();
+    /// assert_eq!(lines, ["first", "second", "", "last"]);
+    /// ```
+    pub fn lines(&self) -> Option<str::Lines<'a>> {
+        self.contents_utf8().map(str::lines)
+    }
+
+    /// Iterate over the records in the file's [raw contents](File::contents),
+    /// separated by `separator`, for binary formats like NUL-separated
+    /// lists.
+    ///
+    /// The separator isn't included, and like [`File::lines()`] a
+    /// trailing separator doesn't add an empty record and an empty file has
+    /// no records. Empty records in the middle are kept.
+    pub fn split(&self, separator: u8) -> impl Iterator<Item = &'a [u8]> {
+        let contents = self.contents();
+        let empty = contents.is_empty();
+        let contents = contents.strip_suffix(&[separator]).unwrap_or(contents);
+
+        contents
+            .split(move |&b| b == separator)
+            .filter(move |_| !empty)
+    }
+
+    /// Does the file look like binary data rather than text?
+    ///
+    /// A file counts as text if it isn't [compressed](File::is_compressed)
+    /// and its [raw contents](File::contents) are valid UTF-8, so this is
+    /// always the same as `contents_utf8().is_none()`. That means text in
+    /// other encodings, like UTF-16 or Latin-1, is classified as binary, as
+    /// are compressed files. NUL bytes are valid UTF-8 and don't make a file
+    /// binary on their own.
+    ///
+    /// Checking is quick, but it does read the whole file unless it was
+    /// compressed.
+    pub fn is_binary(&self) -> bool {
+        #[cfg(feature = "compression")]
+        if self.compressed {
+            return true;
+        }
+
+        self.contents_utf8().is_none()
+    }
+
+    /// The SHA-256 hash of the file's contents, calculated when the file was
+    /// embedded.
+    #[cfg(feature = "hash")]
+    pub fn hash(&self) -> [u8; 32] {
+        self.hash
+    }
+
+    /// The file's metadata, as it was when the file was embedded.
+    #[cfg(feature = "metadata")]
+    pub fn metadata(&self) -> Option<Metadata> {
+        self.metadata
+    }
+}
+
+/// Split a file name into its stem and extension, following the same rules
+/// as [`Path::file_stem()`] and [`Path::extension()`].
+fn split_extension(name: &str) -> (&str, Option<&str>) {
+    match name.rsplit_once('.') {
+        Some(("", _)) | None => (name, None),
+        Some((stem, extension)) => (stem, Some(extension)),
+    }
 }
 
 impl<'a> Debug for File<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.debug_struct("File")
-            .field("path", &self.path)
-            .field("contents", &format!("<{} bytes>", self.contents.len()))
-            .finish()
+        let mut d = f.debug_struct("File");
+        d.field("path", &self.path)
+            .field("contents", &format_args!("<{} bytes>", self.contents.len()));
+
+        #[cfg(feature = "metadata")]
+        d.field("metadata", &self.metadata);
+
+        #[cfg(feature = "compression")]
+        d.field("compressed", &self.compressed);
+
+        #[cfg(feature = "attributes")]
+        d.field("attrs", &self.attrs);
+
+        d.finish()
+    }
+}
+
+impl<'a> Display for File<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.path)
+    }
+}
+
+impl<'a> AsRef<[u8]> for File<'a> {
+    fn as_ref(&self) -> &[u8] {
+        self.contents
+    }
+}
+
+impl<'a> Deref for File<'a> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.contents
+    }
+}
+
+impl<'a> PartialEq for File<'a> {
+    fn eq(&self, other: &File<'a>) -> bool {
+        self.path == other.path
+    }
+}
+
+impl<'a> Eq for File<'a> {}
+
+impl<'a> PartialOrd for File<'a> {
+    fn partial_cmp(&self, other: &File<'a>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a> Ord for File<'a> {
+    fn cmp(&self, other: &File<'a>) -> Ordering {
+        self.path.cmp(other.path)
+    }
+}
+
+impl<'a> Hash for File<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.path.hash(state);
     }
 }
@@ -0,0 +1,72 @@
+use crate::file::File;
+
+/// The fences which may open a header, `---` for YAML and `+++` for TOML.
+const FENCES: [&str; 2] = ["---", "+++"];
+
+impl<'a> File<'a> {
+    /// Split a file like a markdown post into its front matter and body,
+    /// without parsing the front matter itself.
+    ///
+    /// The front matter is a header fenced by `---` (usually YAML) or `+++`
+    /// (usually TOML) lines, where the first line of the file opens it and
+    /// the next line with the same fence closes it. Fences may have trailing
+    /// whitespace and end with either `\n` or `\r\n`, and a leading byte
+    /// order mark is skipped.
+    ///
+    /// The front matter is everything between the fences, including the
+    /// line ending before the closing one, and the body is everything after
+    /// the closing fence's line. A closing fence on the last line gives an
+    /// empty body.
+    ///
+    /// Returns `None` if the file doesn't start with a fence, the header is
+    /// never closed, or the contents aren't
+    /// [valid UTF-8](File::contents_utf8), so files without front matter
+    /// can be told apart from ones with an empty header. Use
+    /// [`File::contents_utf8()`] for their body.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir};
+    ///
+    /// static CONTENT: Dir<'_> = include_dir!("tests/fixtures/content");
+    ///
+    /// for post in CONTENT.files_with_extension("md") {
+    ///     if let Some((front_matter, body)) = post.split_front_matter() {
+    ///         assert!(front_matter.contains("title"));
+    ///         assert!(!body.starts_with("---"));
+    ///     }
+    /// }
+    /// ```
+    pub fn split_front_matter(&self) -> Option<(&'a str, &'a str)> {
+        let text = self.contents_utf8()?;
+        let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+
+        let (first, header) = split_line(text);
+        let fence = FENCES
+            .iter()
+            .copied()
+            .find(|fence| first.trim_end() == *fence)?;
+
+        let mut rest = header;
+        while !rest.is_empty() {
+            let (line, next) = split_line(rest);
+            if line.trim_end() == fence {
+                let len = header.len() - rest.len();
+                return Some((&header[..len], next));
+            }
+            rest = next;
+        }
+
+        None
+    }
+}
+
+/// Split off the first line, without its `\n` or `\r\n`.
+fn split_line(text: &str) -> (&str, &str) {
+    match text.find('\n') {
+        Some(ix) => {
+            let line = &text[..ix];
+            (line.strip_suffix('\r').unwrap_or(line), &text[ix + 1..])
+        }
+        None => (text, ""),
+    }
+}
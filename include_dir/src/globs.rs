@@ -1,40 +1,38 @@
 use crate::dir::Dir;
-use crate::file::File;
-use glob::{Pattern, PatternError};
-use std::path::Path;
+use crate::dir_entry::DirEntry;
+use crate::walk::Walk;
+use glob::{MatchOptions, Pattern, PatternError};
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Globs<'a> {
-    stack: Vec<DirEntry<'a>>,
+    walk: Walk<'a>,
     pattern: Pattern,
 }
 
 impl<'a> Dir<'a> {
     /// Search for a file or directory with a glob pattern.
+    ///
+    /// Patterns are matched against each entry's path relative to the
+    /// directory included with `include_dir!()`. Wildcards never match a
+    /// path separator, so `*.rs` only matches files at the top level while
+    /// `**/*.rs` matches them at any depth.
     pub fn find(&self, glob: &str) -> Result<impl Iterator<Item = DirEntry<'a>>, PatternError> {
         let pattern = Pattern::new(glob)?;
 
         Ok(Globs::new(pattern, *self))
     }
-
-    pub(crate) fn dir_entries(&self) -> impl Iterator<Item = DirEntry<'a>> {
-        let files = self.files().iter().map(|f| DirEntry::File(*f));
-        let dirs = self.dirs().iter().map(|d| DirEntry::Dir(*d));
-
-        files.chain(dirs)
-    }
 }
 
 impl<'a> Globs<'a> {
     pub(crate) fn new(pattern: Pattern, root: Dir<'a>) -> Globs<'a> {
-        let stack = vec![DirEntry::Dir(root)];
-        Globs { stack, pattern }
-    }
-
-    fn fill_buffer(&mut self, item: &DirEntry<'a>) {
-        if let DirEntry::Dir(ref dir) = *item {
-            self.stack.extend(dir.dir_entries());
-        }
+        let walk = root.walk();
+        Globs { walk, pattern }
     }
 }
 
@@ -42,33 +40,9 @@ impl<'a> Iterator for Globs<'a> {
     type Item = DirEntry<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(item) = self.stack.pop() {
-            self.fill_buffer(&item);
-
-            if self.pattern.matches_path(item.path()) {
-                return Some(item);
-            }
-        }
-
-        None
-    }
-}
-
-/// Entries returned by the Globs iterator
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub enum DirEntry<'a> {
-    /// A file with its contents stored in a &'static [u8].
-    File(File<'a>),
-    /// A directory entry.
-    Dir(Dir<'a>),
-}
+        let pattern = &self.pattern;
 
-impl<'a> DirEntry<'a> {
-    /// Get the entries's path
-    pub fn path(&self) -> &'a Path {
-        match *self {
-            DirEntry::File(f) => f.path(),
-            DirEntry::Dir(d) => d.path(),
-        }
+        self.walk
+            .find(|item| pattern.matches_path_with(item.path(), MATCH_OPTIONS))
     }
 }
@@ -0,0 +1,29 @@
+use sha2::{Digest, Sha256};
+
+/// Hash a file's contents, the same way the macro does.
+pub(crate) fn hash_contents(contents: &[u8]) -> [u8; 32] {
+    Sha256::digest(contents).into()
+}
+
+/// Combine the hashes of a directory's children, the same way the macro
+/// does. The children must already be sorted by name.
+pub(crate) fn hash_children<'h, F, D>(files: F, dirs: D) -> [u8; 32]
+where
+    F: IntoIterator<Item = (&'h str, &'h [u8; 32])>,
+    D: IntoIterator<Item = (&'h str, &'h [u8; 32])>,
+{
+    let mut hasher = Sha256::new();
+
+    for (kind, (name, hash)) in files
+        .into_iter()
+        .map(|f| (b'f', f))
+        .chain(dirs.into_iter().map(|d| (b'd', d)))
+    {
+        hasher.update([kind]);
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update(hash);
+    }
+
+    hasher.finalize().into()
+}
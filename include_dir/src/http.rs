@@ -0,0 +1,84 @@
+use crate::dir::Dir;
+use crate::dir_entry::DirEntry;
+use crate::file::File;
+use crate::sanitize::percent_decode;
+use std::borrow::Cow;
+
+/// Everything needed to answer a HTTP request for an embedded file, created
+/// with [`Dir::serve()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Asset<'a> {
+    file: File<'a>,
+    content_type: &'static str,
+    etag: String,
+}
+
+impl<'a> Asset<'a> {
+    /// The file being served.
+    pub fn file(&self) -> File<'a> {
+        self.file
+    }
+
+    /// The value for the `Content-Type` header, from [`File::mime_type()`].
+    ///
+    /// Unknown extensions are served as `application/octet-stream`.
+    pub fn content_type(&self) -> &'static str {
+        self.content_type
+    }
+
+    /// A strong `ETag` for the file's contents, including the surrounding
+    /// quotes, from [`File::etag()`].
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+
+    /// The response body, decompressing the file if necessary.
+    pub fn body(&self) -> Cow<'a, [u8]> {
+        self.file.uncompressed()
+    }
+}
+
+impl<'a> Dir<'a> {
+    /// Find the file to serve for a HTTP request path, like
+    /// `/css/main.css?v=2`.
+    ///
+    /// The path is percent-decoded and looked up relative to this directory,
+    /// so `/css/main.css` finds `css/main.css` no matter what this
+    /// directory's own path is (or which `prefix` it was embedded with).
+    /// Directories, including `/`, are served using their `index.html`.
+    ///
+    /// Returns `None` when nothing matches, which would normally become a
+    /// `404 Not Found`. Paths which try to escape the directory with `..`
+    /// never match anything.
+    pub fn serve(&self, request_path: &str) -> Option<Asset<'a>> {
+        let path = request_path.split(['?', '#']).next().unwrap_or_default();
+        let path = percent_decode(path)?;
+        let path = path.trim_start_matches('/');
+
+        let entry = if path.is_empty() {
+            DirEntry::Dir(*self)
+        } else if self.path.is_empty() {
+            self.get_entry(path)?
+        } else {
+            self.get_entry(format!("{}/{}", self.path, path))?
+        };
+
+        let file = match entry {
+            DirEntry::File(f) => f,
+            DirEntry::Dir(d) => d.index()?,
+        };
+
+        Some(Asset {
+            file,
+            content_type: file.mime_type().unwrap_or("application/octet-stream"),
+            etag: file.etag(),
+        })
+    }
+
+    fn index(&self) -> Option<File<'a>> {
+        self.files
+            .iter()
+            .find(|f| f.file_name() == "index.html")
+            .copied()
+    }
+}
@@ -0,0 +1,74 @@
+use crate::case_fold::fold;
+use crate::dir::{file_name, lookup_names, stored_names, Dir};
+use crate::dir_entry::DirEntry;
+use crate::file::File;
+use crate::path::Path;
+
+impl<'a> Dir<'a> {
+    /// Fetch a file by its path, ignoring differences in case.
+    ///
+    /// Each component is compared using full Unicode case folding, so
+    /// `Textures/Wall.PNG` will find `textures/wall.png`, `STRASSE` will find
+    /// `straße`, and `ΣΟΦΟΣ` will find `σοφος`. If several entries
+    /// only differ by case, the first one in sorted order is returned (e.g.
+    /// `Textures/` is checked before `textures/`). Otherwise this follows
+    /// the same rules as [`Dir::get_file()`].
+    pub fn get_file_ignore_case<S: AsRef<Path>>(&self, path: S) -> Option<File<'a>> {
+        self.find_ignore_case(path.as_ref(), false)?.as_file()
+    }
+
+    /// Fetch a sub-directory by its path, ignoring differences in case.
+    ///
+    /// See [`Dir::get_file_ignore_case()`] for how paths are compared.
+    pub fn get_dir_ignore_case<S: AsRef<Path>>(&self, path: S) -> Option<Dir<'a>> {
+        self.find_ignore_case(path.as_ref(), true)?.as_dir()
+    }
+
+    fn find_ignore_case(&self, path: &Path, want_dir: bool) -> Option<DirEntry<'a>> {
+        let mut names = lookup_names(path).ok()?;
+
+        for own in stored_names(self.path) {
+            match names.next() {
+                Some(name) if eq_ignore_case(name, own) => {}
+                _ => return None,
+            }
+        }
+
+        search(*self, names, want_dir)
+    }
+}
+
+/// Depth-first search through every sibling which matches, because the
+/// first directory with the right name (ignoring case) may not be the one
+/// containing the entry we want.
+fn search<'a, 'n, I>(dir: Dir<'a>, mut names: I, want_dir: bool) -> Option<DirEntry<'a>>
+where
+    I: Iterator<Item = &'n str> + Clone,
+{
+    let name = names.next()?;
+    let is_last = names.clone().next().is_none();
+
+    if is_last && !want_dir {
+        return dir
+            .files()
+            .iter()
+            .find(|f| eq_ignore_case(file_name(f.path), name))
+            .map(|f| DirEntry::File(*f));
+    }
+
+    let mut candidates = dir
+        .dirs()
+        .iter()
+        .filter(|d| eq_ignore_case(file_name(d.path), name));
+
+    if is_last {
+        candidates.next().map(|d| DirEntry::Dir(*d))
+    } else {
+        candidates.find_map(|d| search(*d, names.clone(), want_dir))
+    }
+}
+
+/// Are `left` and `right` the same once case folded?
+pub(crate) fn eq_ignore_case(left: &str, right: &str) -> bool {
+    left.chars().flat_map(fold).eq(right.chars().flat_map(fold))
+}
@@ -30,20 +30,277 @@
 //! }
 //! ```
 //!
+//! The path may also refer to environment variables using `$VAR` or `${VAR}`,
+//! which is handy for embedding files generated by a build script (e.g.
+//! `include_dir!("$OUT_DIR/generated")`). Variables are read at compile time
+//! and an unset variable is a compile error.
+//!
+//! Several directories can be embedded as a single tree by listing more than
+//! one path, e.g. `include_dir!("locales/common", "locales/en")`. Their
+//! contents are merged, with directories that appear in more than one of
+//! them combined, and the result is sorted just like a single directory.
+//! A file which appears in more than one of them is a compile error unless
+//! the `duplicates` option says otherwise.
+//!
+//! # Macro Options
+//!
+//! The path (or paths) may be followed by `key = value` options which change
+//! what gets embedded. Options apply to every directory.
+//!
+//! - **relative_to:** what the paths are relative to; `"manifest"` (the
+//!   default) is the directory containing the crate's `Cargo.toml`, and
+//!   `"file"` is the directory containing the file the macro is called from,
+//!   like `include_str!()`. That lets a fixtures directory move together
+//!   with the tests that use it. Absolute paths (e.g. from `$OUT_DIR`) are
+//!   used as-is either way
+//! - **prefix:** a path to put in front of every embedded path (e.g.
+//!   `prefix = "assets"` stores `img/logo.png` as `assets/img/logo.png`).
+//!   Without a prefix, paths are relative to the included directory and the
+//!   top-level `Dir` has an empty path
+//! - **compress:** set to `true` to store files compressed with DEFLATE,
+//!   using `File::decompressed_contents()` to get the original bytes back.
+//!   Requires the `compression` feature
+//! - **include:** a list of glob patterns, only files matching at least one
+//!   of them are embedded (e.g. `include = ["**/*.sql"]`). Directories are
+//!   always searched, so nested files can still match
+//! - **exclude:** a list of glob patterns for files and directories to leave
+//!   out (e.g. `exclude = ["**/.DS_Store", "node_modules"]`). Exclusions take
+//!   priority over `include`
+//! - **include_empty_dirs:** set to `true` to keep directories which
+//!   `include` or `exclude` left empty, so they are still created by
+//!   `Dir::extract()`
+//! - **deny_empty:** set to `true` to make it a compile error when nothing
+//!   would be embedded, e.g. because the directory was emptied or the
+//!   filters don't match anything
+//! - **require:** a list of glob patterns which must each match at least
+//!   one embedded file (e.g. `require = ["0001_init.sql", "**/*.sql"]`),
+//!   otherwise it is a compile error listing what was missing and what was
+//!   found instead
+//! - **deduplicate:** set to `true` to embed files with identical contents
+//!   only once. Every path is still there (and is still written out by
+//!   `Dir::extract()`), they just point at the same bytes. This means the
+//!   macro has to read every file itself, so it's off by default
+//! - **symlinks:** what to do with symbolic links; `"follow"` (the default)
+//!   embeds whatever the link points to, `"skip"` leaves links out, and
+//!   `"error"` refuses to compile if there are any. A followed link which
+//!   points back to one of its own parent directories is always an error
+//! - **non_utf8_names:** what to do with files and directories whose names
+//!   aren't valid UTF-8; `"error"` (the default) refuses to compile,
+//!   `"skip"` leaves them out (with a warning when the `nightly` feature is
+//!   enabled), and `"lossy"` stores them with each invalid sequence replaced
+//!   by `U+FFFD`. Two names which become the same after conversion are a
+//!   compile error
+//! - **duplicates:** what to do when several directories contain the same
+//!   path; `"error"` (the default) refuses to compile, and `"override"`
+//!   keeps the entry from the directory listed last
+//! - **vanished:** what to do with files and directories which are deleted
+//!   while the macro is reading the directory, e.g. by another process
+//!   cleaning up `OUT_DIR`; `"error"` (the default) refuses to compile,
+//!   naming the path, and `"skip"` leaves them out. Each file is read at
+//!   most once, so its hash, length, and compressed contents always describe
+//!   the same bytes, and a file which changes while it is read is embedded
+//!   exactly as the macro saw it. On Windows, reading a file which
+//!   another process has open without sharing it is retried once before
+//!   giving up
+//! - **max_file_size:** the largest file which may be embedded, either a
+//!   number of bytes or a string like `"10MB"`. Anything bigger is a compile
+//!   error naming the file, which helps catch a stray database dump before
+//!   it ends up in the binary
+//! - **max_total_size:** the same, but for everything being embedded
+//! - **max_depth:** how many levels of sub-directories are allowed, so
+//!   `max_depth = 0` only allows files at the top level
+//! - **inline_threshold:** files smaller than this (1 KiB by default) are
+//!   written into the generated code as byte strings, while bigger ones are
+//!   read by the compiler with `include_bytes!()`. Use `inline_threshold = 0`
+//!   to always use `include_bytes!()`. Files the macro has to read anyway,
+//!   because the `hash` or `build-id` feature is enabled or
+//!   `compress = true`, are always written into the generated code
+//! - **attributes:** the path to a TOML file giving files attributes, e.g.
+//!   `attributes = "assets.toml"`. Requires the `attributes` feature. Each
+//!   table is named by a glob and holds string, number, or boolean
+//!   attributes for the files it matches. Every matching glob is applied in
+//!   the order they are written, so later ones override earlier ones. The
+//!   path is resolved the same way as the directories, and the manifest
+//!   isn't embedded unless it is inside one of them
+//! - **unmatched_attributes:** what to do with a glob in the `attributes`
+//!   manifest which doesn't match any file; `"error"` (the default) refuses
+//!   to compile, and `"warn"` carries on (with a warning when the `nightly`
+//!   feature is enabled)
+//!
+//! Sizes accept `B`, `KB`, `MB`, and `GB` (case-insensitive, with or
+//! without an `i`) and always use powers of 1024. Only files which survive
+//! the `include` and `exclude` filters count towards the limits.
+//!
+//! Glob patterns are always matched against paths relative to the included
+//! directory, ignoring any `prefix`, with the same rules as `Dir::find()`, so `*.psd` only matches
+//! files at the top level while `**/*.psd` matches them anywhere. When
+//! filters are used, directories left with nothing to embed are dropped
+//! unless `include_empty_dirs = true` is set.
+//!
+//! Because git can't store empty directories, a common trick is to put a
+//! `.gitkeep` file in them and embed the tree with
+//! `exclude = ["**/.gitkeep"], include_empty_dirs = true`.
+//!
+//! # Checking Paths at Compile Time
+//!
+//! A typo in a path passed to `Dir::get_file()` is only noticed when the
+//! lookup fails at runtime. The `include_dir_path!()` macro takes the same
+//! directory (and options) as `include_dir!()` followed by a path inside it,
+//! and fails to compile unless that file or directory would be embedded. It
+//! expands to the path as it is stored, with any `prefix` added and `./` or
+//! repeated slashes removed, so it can be passed straight to the lookups.
+//!
+//! ```rust
+//! use include_dir::{include_dir, include_dir_path, Dir};
+//!
+//! static TREE: Dir<'_> = include_dir!("tests/fixtures/tree", prefix = "static");
+//! const BOOTSTRAP: &str =
+//!     include_dir_path!("tests/fixtures/tree", "./migrations//0000_bootstrap.sql", prefix = "static");
+//!
+//! assert_eq!(BOOTSTRAP, "static/migrations/0000_bootstrap.sql");
+//! assert!(TREE.get_file(BOOTSTRAP).is_some());
+//! ```
+//!
+//! Each use only looks at the entries along the path, so it stays cheap no
+//! matter how big the directory is.
+//!
+//! # Binary Layout
+//!
+//! Every path is stored in a single string, one after the other in the
+//! order `Dir::walk()` visits them, while each file's contents are stored
+//! separately. Lookups and walks only read the paths and the tables of
+//! entries, so the pages holding contents are only loaded when a file's
+//! contents are actually used. This makes embedding thousands of files
+//! cheap when a program only reads a few of them.
+//!
+//! # Sharing Embedded Files Between Crates
+//!
+//! Every `include_dir!()` call embeds its own copy of the files, so when
+//! several crates in a workspace need the same directory, embed it once in
+//! a small library crate and re-export it from there:
+//!
+//! ```rust,ignore
+//! // assets/src/lib.rs
+//! pub static ASSETS: include_dir::Dir<'_> = include_dir::include_dir!("files");
+//!
+//! // in every other crate
+//! use assets::ASSETS;
+//! ```
+//!
+//! Make sure it is a `static` rather than a `const`. A `static` is a single
+//! item which every crate refers to, so the final binary has one copy of
+//! the data. A `const` is copied into each crate which uses it, which can
+//! leave a copy of every file per crate.
+//!
 //! # Features
 //!
 //! This library exposes a couple feature flags for enabling and disabling extra
 //! functionality. These are:
 //!
+//! - **attributes:** allow the `attributes` macro option, which attaches
+//!   key/value attributes from a TOML manifest to files, available through
+//!   `File::attr()` and `File::attrs()`
+//! - **build-id:** add `Dir::build_id()`, a short id which changes
+//!   whenever the directory's contents do, for cache-busting URLs
+//! - **bytes:** add `File::as_bytes()`, which turns an embedded file's
+//!   contents into a `bytes::Bytes` without copying them
+//! - **compression:** allow files to be compressed with the `compress`
+//!   option
+//! - **embedded-assets:** add the `EmbeddedAssets` trait, which has the
+//!   same `get()` and `iter()` shape as `rust-embed` so code written
+//!   against that interface can use a `Dir` instead
 //! - **example:** compile in an example of the embedded directory tree
+//! - **ffi:** add `RawFile`, a `#[repr(C)]` view of a file's path and
+//!   contents, along with `File::as_ptr_len()` and `Dir::raw_files()` for
+//!   handing embedded data to C without copying it
+//! - **frontmatter:** add `File::split_front_matter()`, which separates a
+//!   markdown file's `---` or `+++` fenced header from its body
+//! - **hash:** calculate a SHA-256 hash for each file and directory at
+//!   compile time, available through `File::hash()` and `Dir::hash()`, and
+//!   add `Dir::verify()` for checking the embedded files against them
+//! - **hot-reload:** make `Dir::read_file()` read from the original
+//!   directory in debug builds, so edits show up without recompiling.
+//!   Release builds always use the embedded files
+//! - **http:** add `Dir::serve()`, which finds the file for a HTTP request
+//!   path along with its content type and `ETag`
+//! - **metadata:** record each file's size and modification time, available
+//!   through `File::metadata()`
+//! - **nightly:** use unstable compiler APIs to recompile when files are
+//!   added to or removed from an embedded directory (requires a nightly
+//!   compiler)
+//! - **permissions:** record each file's Unix permissions (implies
+//!   `metadata`), which are restored when extracting on Unix
+//! - **precache:** add `Dir::precache_manifest()`, which lists every file's
+//!   URL and a revision from its hash for a service worker to precache
+//!   (implies `hash`)
+//! - **serde:** implement `Serialize` for `Dir` and `File`, plus a
+//!   `DirManifest` wrapper which leaves out file contents
+//! - **source-info:** record where each directory was embedded from and
+//!   when, available through `Dir::source_path()` and `Dir::embedded_at()`.
+//!   This is off by default because it puts paths from the build machine
+//!   into the binary
+//! - **std:** (enabled by default) use the standard library. See below
+//! - **tar:** add `Dir::write_tar()` for streaming a directory as a tar
+//!   archive
+//! - **tera:** add `Dir::add_to_tera()` for loading a directory of
+//!   templates into a `tera::Tera` instance
+//! - **tokio:** add `Dir::extract_async()`, which extracts files using
+//!   `tokio::fs` without blocking the async runtime
+//! - **zip:** add `Dir::write_zip()` for exporting a directory as a zip
+//!   archive
+//!
+//! # `no_std` Support
+//!
+//! The embedded data is just `&'static` slices, so the core of the crate
+//! works without the standard library (or even an allocator). Disable the
+//! default features to build it as `#![no_std]`:
+//!
+//! ```toml
+//! [dependencies]
+//! include_dir = { version = "*", default-features = false }
+//! ```
+//!
+//! The macro generates exactly the same code either way. The differences
+//! are that `path()` methods return `&str` instead of `&Path`, lookups take
+//! anything implementing `AsRef<str>`, and everything which needs the
+//! filesystem, I/O traits, or allocating (extraction, `DirBuilder`,
+//! `Dir::merge()`, `File::reader()`, `Dir::read_file()`, etc.) is left out.
+//! Only the `attributes`, `build-id`, `example-output`, `ffi`, `hash`, and
+//! `nightly` features can be used without `std`, the others enable it
+//! automatically.
+//!
+//! # Recompiling When Files Change
+//!
+//! Editing an embedded file always triggers a rebuild because the generated
+//! code reads each file with `include_bytes!()`. Files below the
+//! `inline_threshold` (and any the macro reads itself for hashing or
+//! compression) are written into the generated code instead, but they still
+//! get an unused `include_bytes!()` so the compiler tracks them the same way.
+//! That means on a stable compiler every file's absolute path still ends up
+//! in an `include_bytes!()`, so inlining doesn't avoid problems with long or
+//! unusual paths on Windows. With the `nightly` feature inlined files are
+//! tracked through the compiler's unstable API instead, which avoids both
+//! that and the compiler reading small files twice. The compiler doesn't know
+//! about the directories themselves though, so on a stable compiler adding,
+//! removing, or renaming a file won't be noticed until something else causes
+//! the crate to recompile. Either enable the `nightly` feature or add a
+//! build script which tells cargo to watch the directory:
+//!
+//! ```rust,ignore
+//! // build.rs
+//! fn main() {
+//!     println!("cargo:rerun-if-changed=assets");
+//! }
+//! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(
     elided_lifetimes_in_paths,
     future_incompatible,
     missing_copy_implementations,
     missing_debug_implementations,
     missing_docs,
-    rust_2018_idioms,
+    rust_2018_idioms
 )]
 
 #[allow(unused_imports)]
@@ -52,21 +309,122 @@ extern crate include_dir_impl;
 #[macro_use]
 extern crate proc_macro_hack;
 
+#[cfg(feature = "embedded-assets")]
+mod assets;
+#[cfg(feature = "attributes")]
+mod attributes;
+#[cfg(feature = "std")]
+mod builder;
+#[cfg(feature = "bytes")]
+mod bytes;
+mod case_fold;
+#[cfg(feature = "compression")]
+mod chunks;
+mod const_lookup;
+#[cfg(feature = "std")]
+mod diff;
 mod dir;
+mod dir_entry;
+#[cfg(feature = "std")]
+mod etag;
+#[cfg(feature = "std")]
+mod extract;
+#[cfg(feature = "tokio")]
+mod extract_async;
+#[cfg(feature = "ffi")]
+mod ffi;
 mod file;
+#[cfg(feature = "frontmatter")]
+mod front_matter;
+#[cfg(feature = "hash")]
+mod hash;
+#[cfg(feature = "http")]
+mod http;
+mod ignore_case;
+mod lookup_error;
+#[cfg(feature = "std")]
+mod merge;
+#[cfg(feature = "metadata")]
+mod metadata;
+mod mime;
+mod path;
+#[cfg(feature = "precache")]
+mod precache;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "std")]
+mod sanitize;
+mod scoped;
+#[cfg(feature = "serde")]
+mod serialize;
+mod statics;
+#[cfg(feature = "tar")]
+mod tar_export;
+#[cfg(feature = "tera")]
+mod templates;
+mod tree;
+#[cfg(feature = "hash")]
+mod verify;
+mod walk;
+#[cfg(feature = "zip")]
+mod zip_export;
 
 #[cfg(feature = "search")]
 mod globs;
 
+#[cfg(feature = "embedded-assets")]
+pub use crate::assets::EmbeddedAssets;
+#[cfg(feature = "std")]
+pub use crate::builder::{DirBuilder, OwnedDir};
+#[cfg(feature = "compression")]
+pub use crate::chunks::DecompressedChunks;
+#[cfg(feature = "std")]
+pub use crate::diff::DirDiff;
 pub use crate::dir::Dir;
+pub use crate::dir_entry::{DirEntry, Entries, EntryKind};
+#[cfg(feature = "std")]
+pub use crate::extract::{Collision, ExtractEvent, ExtractOptions, ExtractReport, PrefixBehavior};
+#[cfg(feature = "ffi")]
+pub use crate::ffi::RawFile;
 pub use crate::file::File;
-#[cfg(feature = "search")]
-pub use crate::globs::DirEntry;
+#[cfg(feature = "http")]
+pub use crate::http::Asset;
+pub use crate::lookup_error::LookupError;
+#[cfg(feature = "std")]
+pub use crate::merge::{MergedDir, MergedEntry};
+#[cfg(feature = "metadata")]
+pub use crate::metadata::Metadata;
+#[cfg(feature = "precache")]
+pub use crate::precache::PrecacheEntry;
+#[cfg(feature = "std")]
+pub use crate::reader::FileReader;
+#[cfg(feature = "std")]
+pub use crate::sanitize::sanitize_path;
+pub use crate::scoped::ScopedDir;
+#[cfg(feature = "serde")]
+pub use crate::serialize::DirManifest;
+pub use crate::statics::{StaticDir, StaticFile};
+#[cfg(feature = "tera")]
+pub use crate::templates::TemplateOptions;
+pub use crate::tree::Tree;
+#[cfg(feature = "hash")]
+pub use crate::verify::{Digest, VerifyError};
+pub use crate::walk::Walk;
 
 #[doc(hidden)]
 #[proc_macro_hack]
 pub use include_dir_impl::include_dir;
 
+#[doc(hidden)]
+#[proc_macro_hack]
+pub use include_dir_impl::include_dir_path;
+
 /// Example the output generated when running `include_dir!()` on itself.
 #[cfg(feature = "example-output")]
 pub static GENERATED_EXAMPLE: Dir<'_> = include_dir!(".");
+
+/// Get one of the paths out of the table generated by `include_dir!()`.
+#[doc(hidden)]
+pub const fn __path(table: &str, start: usize, end: usize) -> &str {
+    table.split_at(end).0.split_at(start).1
+}
@@ -0,0 +1,33 @@
+use core::fmt::{self, Display, Formatter};
+
+/// Why [`Dir::try_get_file()`](crate::Dir::try_get_file) and friends
+/// couldn't find what they were asked for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LookupError {
+    /// Nothing is stored at the path.
+    NotFound,
+    /// A file was wanted, but the path is a directory.
+    NotAFile,
+    /// A directory was wanted, but the path is a file.
+    NotADir,
+    /// The path could never refer to an embedded entry (e.g. it is absolute
+    /// or contains `..`).
+    InvalidPath {
+        /// What is wrong with the path.
+        reason: &'static str,
+    },
+}
+
+impl Display for LookupError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            LookupError::NotFound => write!(f, "Nothing is embedded at that path"),
+            LookupError::NotAFile => write!(f, "The path is a directory, not a file"),
+            LookupError::NotADir => write!(f, "The path is a file, not a directory"),
+            LookupError::InvalidPath { reason } => write!(f, "Invalid path, {}", reason),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LookupError {}
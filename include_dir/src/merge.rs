@@ -0,0 +1,273 @@
+use crate::dir::{lookup_names, stored_names, Dir};
+use crate::dir_entry::DirEntry;
+use crate::extract::{annotate, ExtractOptions, ExtractReport, Extractor};
+use crate::file::File;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+impl<'a> Dir<'a> {
+    /// Lay `overlay` on top of this directory.
+    ///
+    /// Lookups on the result check `overlay` first and fall back to this
+    /// directory, so a file in `overlay` shadows the file with the same path
+    /// here. This is handy for letting a theme or user customisations
+    /// override a set of defaults without copying them.
+    ///
+    /// Entries are matched up by name, so both directories should normally
+    /// be embedded with the same `prefix`.
+    pub fn merge(&self, overlay: &Dir<'a>) -> MergedDir<'a> {
+        MergedDir {
+            base: Some(*self),
+            overlay: Some(*overlay),
+        }
+    }
+}
+
+/// One directory laid on top of another, created by [`Dir::merge()`].
+///
+/// This has the same read API as [`Dir`], but the listing methods have to
+/// combine both directories so they return owned collections instead of
+/// slices.
+///
+/// When the overlay has a file where the base has a directory (or the
+/// other way around), the overlay's entry wins and the base's entry is
+/// hidden entirely, along with everything inside it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MergedDir<'a> {
+    base: Option<Dir<'a>>,
+    overlay: Option<Dir<'a>>,
+}
+
+impl<'a> MergedDir<'a> {
+    /// Get the directory's path.
+    ///
+    /// This is the base directory's path, unless the directory only exists
+    /// in the overlay.
+    pub fn path(&self) -> &'a Path {
+        self.layer().path()
+    }
+
+    /// The last component of the directory's path.
+    pub fn file_name(&self) -> &'a str {
+        self.layer().file_name()
+    }
+
+    /// Get a list of the files in this directory, sorted by path.
+    pub fn files(&self) -> Vec<File<'a>> {
+        self.children()
+            .into_values()
+            .filter_map(|entry| entry.as_file())
+            .collect()
+    }
+
+    /// Get a list of the sub-directories inside this directory, sorted by
+    /// path.
+    pub fn dirs(&self) -> Vec<MergedDir<'a>> {
+        self.children()
+            .into_values()
+            .filter_map(|entry| entry.as_dir())
+            .collect()
+    }
+
+    /// Iterate over the files and sub-directories immediately inside this
+    /// directory, sorted by path.
+    pub fn entries(&self) -> impl Iterator<Item = MergedEntry<'a>> {
+        self.children().into_values()
+    }
+
+    /// Recursively iterate over every file and sub-directory in either
+    /// directory, in the same order as [`Dir::walk()`].
+    pub fn walk(&self) -> impl Iterator<Item = MergedEntry<'a>> {
+        let mut entries = Vec::new();
+        self.walk_into(&mut entries);
+        entries.into_iter()
+    }
+
+    /// Does either directory contain `path`?
+    pub fn contains<S: AsRef<Path>>(&self, path: S) -> bool {
+        self.get_entry(path).is_some()
+    }
+
+    /// Fetch a file or sub-directory by *exactly* matching its path, checking
+    /// the overlay before the base.
+    pub fn get_entry<S: AsRef<Path>>(&self, path: S) -> Option<MergedEntry<'a>> {
+        let mut names = lookup_names(path.as_ref()).ok()?;
+
+        // paths always include this directory's own path
+        for own in stored_names(self.layer().path) {
+            if names.next() != Some(own) {
+                return None;
+            }
+        }
+
+        let mut names = names.peekable();
+        let mut dir = *self;
+
+        while let Some(name) = names.next() {
+            let child = dir.child(name)?;
+            if names.peek().is_none() {
+                return Some(child);
+            }
+
+            dir = child.as_dir()?;
+        }
+
+        None
+    }
+
+    /// Fetch a sub-directory by *exactly* matching its path.
+    pub fn get_dir<S: AsRef<Path>>(&self, path: S) -> Option<MergedDir<'a>> {
+        self.get_entry(path)?.as_dir()
+    }
+
+    /// Fetch a file by *exactly* matching its path, preferring the overlay's
+    /// copy.
+    pub fn get_file<S: AsRef<Path>>(&self, path: S) -> Option<File<'a>> {
+        self.get_entry(path)?.as_file()
+    }
+
+    /// Extract the union of both directories, writing the overlay's copy
+    /// of any file which exists in both.
+    ///
+    /// See [`Dir::extract()`].
+    pub fn extract<S: AsRef<Path>>(&self, path: S) -> io::Result<()> {
+        self.extract_with(path, &ExtractOptions::default())
+            .map(|_| ())
+    }
+
+    /// Extract the union of both directories with the given options.
+    ///
+    /// See [`Dir::extract_with()`].
+    pub fn extract_with<S: AsRef<Path>>(
+        &self,
+        path: S,
+        options: &ExtractOptions,
+    ) -> io::Result<ExtractReport> {
+        let root = path.as_ref();
+        fs::create_dir_all(root).map_err(|e| annotate(e, root))?;
+
+        let mut extractor = Extractor {
+            root,
+            dir_path: self.path(),
+            options,
+            report: ExtractReport::default(),
+            progress: None,
+        };
+        extractor.create_dir(self.path())?;
+
+        for entry in self.walk() {
+            match entry {
+                MergedEntry::Dir(d) => extractor.create_dir(d.path())?,
+                MergedEntry::File(f) => extractor.write_file(&f)?,
+            }
+        }
+
+        Ok(extractor.report)
+    }
+}
+
+impl<'a> MergedDir<'a> {
+    /// The layer whose path and name this directory uses.
+    fn layer(&self) -> Dir<'a> {
+        self.base
+            .or(self.overlay)
+            .expect("A merged directory always has at least one layer")
+    }
+
+    /// Find an immediate child by name, letting the overlay shadow the
+    /// base.
+    fn child(&self, name: &str) -> Option<MergedEntry<'a>> {
+        match self.overlay.and_then(|o| o.child(name)) {
+            Some(DirEntry::File(f)) => Some(MergedEntry::File(f)),
+            Some(DirEntry::Dir(d)) => Some(MergedEntry::Dir(MergedDir {
+                base: self.base.and_then(|b| b.child_dir(name)),
+                overlay: Some(d),
+            })),
+            None => self.base?.child(name).map(MergedEntry::from_base),
+        }
+    }
+
+    /// Every immediate child of either layer, keyed by name.
+    fn children(&self) -> BTreeMap<&'a str, MergedEntry<'a>> {
+        // later layers replace earlier ones, and directories are left for
+        // child() to work out which layers they come from
+        let mut children = BTreeMap::new();
+
+        for layer in self.base.iter().chain(&self.overlay) {
+            for file in layer.files() {
+                children.insert(file.file_name(), Some(MergedEntry::File(*file)));
+            }
+            for dir in layer.dirs() {
+                children.insert(dir.file_name(), None);
+            }
+        }
+
+        children
+            .into_iter()
+            .map(|(name, entry)| {
+                let entry = entry.unwrap_or_else(|| {
+                    self.child(name)
+                        .expect("The directory was found in one of the layers")
+                });
+                (name, entry)
+            })
+            .collect()
+    }
+
+    fn walk_into(&self, entries: &mut Vec<MergedEntry<'a>>) {
+        for entry in self.entries() {
+            entries.push(entry);
+
+            if let MergedEntry::Dir(d) = entry {
+                d.walk_into(entries);
+            }
+        }
+    }
+}
+
+/// A file or sub-directory of a [`MergedDir`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MergedEntry<'a> {
+    /// A file from whichever layer it was found in.
+    File(File<'a>),
+    /// A directory, which may exist in either layer or both.
+    Dir(MergedDir<'a>),
+}
+
+impl<'a> MergedEntry<'a> {
+    /// Get the entry's path.
+    pub fn path(&self) -> &'a Path {
+        match *self {
+            MergedEntry::File(f) => f.path(),
+            MergedEntry::Dir(d) => d.path(),
+        }
+    }
+
+    /// Get the file, if this entry is a file.
+    pub fn as_file(&self) -> Option<File<'a>> {
+        match *self {
+            MergedEntry::File(f) => Some(f),
+            MergedEntry::Dir(_) => None,
+        }
+    }
+
+    /// Get the directory, if this entry is a directory.
+    pub fn as_dir(&self) -> Option<MergedDir<'a>> {
+        match *self {
+            MergedEntry::File(_) => None,
+            MergedEntry::Dir(d) => Some(d),
+        }
+    }
+
+    fn from_base(entry: DirEntry<'a>) -> MergedEntry<'a> {
+        match entry {
+            DirEntry::File(f) => MergedEntry::File(f),
+            DirEntry::Dir(d) => MergedEntry::Dir(MergedDir {
+                base: Some(d),
+                overlay: None,
+            }),
+        }
+    }
+}
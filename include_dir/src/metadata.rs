@@ -0,0 +1,42 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Basic metadata for a file, captured when it was embedded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    #[doc(hidden)]
+    pub modified: u64,
+    #[doc(hidden)]
+    pub len: u64,
+    #[doc(hidden)]
+    pub mode: Option<u32>,
+}
+
+impl Metadata {
+    /// When the file was last modified.
+    ///
+    /// This only has a resolution of seconds. If the `SOURCE_DATE_EPOCH`
+    /// environment variable was set at compile time, modification times
+    /// later than it are clamped to `SOURCE_DATE_EPOCH`.
+    pub fn modified(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.modified)
+    }
+
+    /// The file's size, in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// The file's Unix permission bits (e.g. `0o755` for an executable
+    /// script).
+    ///
+    /// These are only recorded when the `permissions` feature is enabled and
+    /// the code was compiled on a Unix machine.
+    pub fn mode(&self) -> Option<u32> {
+        self.mode
+    }
+
+    /// Is the file empty?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
@@ -0,0 +1,20 @@
+//! The path type used by the public API.
+//!
+//! With the `std` feature this is just [`std::path::Path`]. Without it
+//! there is no `Path`, so embedded paths are handed out as plain `&str`s
+//! and lookups take anything implementing `AsRef<str>`. Code which only
+//! deals in stored paths can use this alias and work either way.
+
+#[cfg(feature = "std")]
+pub(crate) use std::path::Path;
+
+#[cfg(not(feature = "std"))]
+pub(crate) type Path = str;
+
+/// View a stored path as this crate's path type.
+pub(crate) fn new(path: &str) -> &Path {
+    #[cfg(feature = "std")]
+    return Path::new(path);
+    #[cfg(not(feature = "std"))]
+    return path;
+}
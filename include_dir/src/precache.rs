@@ -0,0 +1,116 @@
+use crate::dir::Dir;
+use crate::file::File;
+use crate::sanitize::percent_encode;
+use std::fmt::Write;
+
+/// One entry in a service worker's precache manifest, created by
+/// [`Dir::precache_entries()`].
+///
+/// With the `serde` feature this serializes as `{"url": ..., "revision": ...}`,
+/// the shape tools like Workbox expect.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PrecacheEntry {
+    url: String,
+    revision: String,
+}
+
+impl PrecacheEntry {
+    /// Where the file is served from.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// A hex string which changes whenever the file's contents do, taken
+    /// from the file's compile-time hash.
+    pub fn revision(&self) -> &str {
+        &self.revision
+    }
+}
+
+impl<'a> Dir<'a> {
+    /// List every file inside this directory along with a revision for its
+    /// contents, ready to be precached by a service worker.
+    ///
+    /// Each URL is `url_prefix` followed by the file's path relative to this
+    /// directory, the same path [`Dir::serve()`] would find it with. The
+    /// prefix may or may not end with a `/`, paths always use `/`, and
+    /// characters which aren't allowed in a URL path are percent-encoded.
+    /// Entries are in the same order as [`Dir::walk()`].
+    ///
+    /// [`Dir::serve()`]: crate::Dir::serve
+    pub fn precache_entries(&self, url_prefix: &str) -> Vec<PrecacheEntry> {
+        self.walk()
+            .filter_map(|entry| entry.as_file())
+            .map(|file| PrecacheEntry {
+                url: self.precache_url(url_prefix, &file),
+                revision: revision(&file),
+            })
+            .collect()
+    }
+
+    /// The same as [`Dir::precache_entries()`], but formatted as a JSON array
+    /// of `{"url": ..., "revision": ...}` objects.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir};
+    ///
+    /// static PROJECT: Dir<'_> = include_dir!(".");
+    ///
+    /// let src = PROJECT.get_dir("src").unwrap();
+    /// let manifest = src.precache_manifest("/static/");
+    /// assert!(manifest.starts_with(r#"[{"url":"/static/"#));
+    /// assert!(manifest.contains(r#""url":"/static/lib.rs","revision":""#));
+    /// ```
+    pub fn precache_manifest(&self, url_prefix: &str) -> String {
+        let mut manifest = String::from("[");
+
+        for (i, entry) in self.precache_entries(url_prefix).iter().enumerate() {
+            if i > 0 {
+                manifest.push(',');
+            }
+            manifest.push_str("{\"url\":");
+            write_json_string(&mut manifest, &entry.url);
+            manifest.push_str(",\"revision\":");
+            write_json_string(&mut manifest, &entry.revision);
+            manifest.push('}');
+        }
+
+        manifest.push(']');
+        manifest
+    }
+
+    fn precache_url(&self, url_prefix: &str, file: &File<'_>) -> String {
+        let path = crate::dir::relative_path(file.path, self.path).unwrap_or(file.path);
+        let path = percent_encode(path);
+
+        if url_prefix.is_empty() {
+            path.into_owned()
+        } else {
+            format!("{}/{}", url_prefix.trim_end_matches('/'), path)
+        }
+    }
+}
+
+/// The first half of the file's SHA-256 hash, as hex.
+fn revision(file: &File<'_>) -> String {
+    file.hash()[..16]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Append `value` to `json` as a quoted JSON string.
+fn write_json_string(json: &mut String, value: &str) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
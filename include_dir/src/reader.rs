@@ -0,0 +1,100 @@
+use crate::file::File;
+use std::fmt::{self, Debug, Formatter};
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// A reader over an embedded file's contents, created with
+/// [`File::reader()`].
+///
+/// This works like a [`std::io::Cursor`] over [`File::contents()`], but
+/// keeps the `'a` lifetime and remembers which file it is reading so errors
+/// can mention the path.
+#[allow(missing_copy_implementations)]
+#[derive(Clone, PartialEq)]
+pub struct FileReader<'a> {
+    path: &'a str,
+    contents: &'a [u8],
+    position: u64,
+}
+
+impl<'a> FileReader<'a> {
+    pub(crate) fn new(file: &File<'a>) -> FileReader<'a> {
+        FileReader {
+            path: file.path,
+            contents: file.contents,
+            position: 0,
+        }
+    }
+
+    /// The path of the file being read.
+    pub fn path(&self) -> &'a Path {
+        Path::new(self.path)
+    }
+
+    /// How far into the file the reader is. This may be past the end after
+    /// seeking.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Everything after the current position.
+    fn remaining(&self) -> &'a [u8] {
+        let start = self.position.min(self.contents.len() as u64) as usize;
+        &self.contents[start..]
+    }
+}
+
+impl<'a> Read for FileReader<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.remaining().read(buf)?;
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl<'a> BufRead for FileReader<'a> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.remaining())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.position += amt as u64;
+    }
+}
+
+impl<'a> Seek for FileReader<'a> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => {
+                self.position = n;
+                return Ok(n);
+            }
+            SeekFrom::End(n) => (self.contents.len() as u64, n),
+            SeekFrom::Current(n) => (self.position, n),
+        };
+
+        match base.checked_add_signed(offset) {
+            Some(n) => {
+                self.position = n;
+                Ok(n)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Unable to seek before the start of \"{}\"",
+                    self.path().display()
+                ),
+            )),
+        }
+    }
+}
+
+impl<'a> Debug for FileReader<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileReader")
+            .field("path", &self.path)
+            .field("contents", &format!("<{} bytes>", self.contents.len()))
+            .field("position", &self.position)
+            .finish()
+    }
+}
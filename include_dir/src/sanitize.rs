@@ -0,0 +1,144 @@
+use crate::dir::Dir;
+use crate::file::File;
+use std::borrow::Cow;
+
+/// Clean up a path which came from somewhere untrusted (e.g. a URL) so it
+/// can be used for lookups.
+///
+/// The path is percent-decoded once, and then rejected (with `None`) if it
+/// contains anything which could be used to escape a directory or confuse
+/// a lookup:
+///
+/// - absolute paths, including Windows drive letters like `C:`
+/// - `..` components
+/// - backslashes, which are separators on Windows
+/// - NUL bytes and other control characters
+/// - malformed or double percent-encoding (e.g. `%252e%252e`)
+///
+/// Empty and `.` components are dropped, so `./css//main.css` becomes
+/// `css/main.css`, and the path is only copied if something needed to
+/// change.
+///
+/// ```rust
+/// use include_dir::sanitize_path;
+///
+/// assert_eq!(sanitize_path("css/main%20file.css").unwrap(), "css/main file.css");
+/// assert_eq!(sanitize_path("../../etc/passwd"), None);
+/// assert_eq!(sanitize_path("..%2f..%2fetc/passwd"), None);
+/// ```
+pub fn sanitize_path(untrusted: &str) -> Option<Cow<'_, str>> {
+    let decoded = percent_decode(untrusted)?;
+
+    if decoded.starts_with('/') || has_percent_escape(&decoded) {
+        return None;
+    }
+    if decoded.chars().any(|c| c == '\\' || c.is_control()) {
+        return None;
+    }
+
+    let names = decoded.split('/').filter(|n| !n.is_empty() && *n != ".");
+    if names.clone().any(|n| n == "..") {
+        return None;
+    }
+    // a drive letter or URL scheme
+    if names
+        .clone()
+        .next()
+        .is_some_and(|first| first.contains(':'))
+    {
+        return None;
+    }
+
+    if names.clone().eq(decoded.split('/')) {
+        return Some(decoded);
+    }
+
+    let mut clean = String::with_capacity(decoded.len());
+    for name in names {
+        if !clean.is_empty() {
+            clean.push('/');
+        }
+        clean.push_str(name);
+    }
+
+    Some(Cow::Owned(clean))
+}
+
+impl<'a> Dir<'a> {
+    /// Fetch a file using a path from somewhere untrusted, like a URL.
+    ///
+    /// This runs the path through [`sanitize_path()`] first, then does a
+    /// normal [`Dir::get_file()`] lookup. Anything which `sanitize_path()`
+    /// rejects never matches.
+    pub fn get_file_sanitized(&self, untrusted: &str) -> Option<File<'a>> {
+        self.get_file(&*sanitize_path(untrusted)?)
+    }
+}
+
+/// Decode `%XX` escapes, giving up on malformed escapes or anything which
+/// isn't valid UTF-8 afterwards.
+pub(crate) fn percent_decode(path: &str) -> Option<Cow<'_, str>> {
+    if !path.contains('%') {
+        return Some(Cow::Borrowed(path));
+    }
+
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path.as_bytes();
+
+    while let Some((&b, tail)) = rest.split_first() {
+        if b == b'%' {
+            let (high, low) = match tail {
+                [high, low, ..] => (hex_digit(*high)?, hex_digit(*low)?),
+                _ => return None,
+            };
+            bytes.push(high << 4 | low);
+            rest = &tail[2..];
+        } else {
+            bytes.push(b);
+            rest = tail;
+        }
+    }
+
+    String::from_utf8(bytes).ok().map(Cow::Owned)
+}
+
+/// The value of a single hex digit. Unlike `u8::from_str_radix()`, this
+/// doesn't let a sign like `%+f` through.
+fn hex_digit(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'a'..=b'f' => Some(b - b'a' + 10),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Encode everything which isn't allowed in a URL path as `%XX` escapes,
+/// leaving the `/` separators alone.
+#[cfg(feature = "precache")]
+pub(crate) fn percent_encode(path: &str) -> Cow<'_, str> {
+    let allowed = |b: u8| b.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@/".contains(&b);
+
+    if path.bytes().all(allowed) {
+        return Cow::Borrowed(path);
+    }
+
+    let mut encoded = String::with_capacity(path.len());
+    for b in path.bytes() {
+        if allowed(b) {
+            encoded.push(char::from(b));
+        } else {
+            encoded.push_str(&format!("%{:02X}", b));
+        }
+    }
+
+    Cow::Owned(encoded)
+}
+
+/// Is there still something which looks like a `%XX` escape, which would
+/// mean the original was encoded twice?
+fn has_percent_escape(path: &str) -> bool {
+    path.as_bytes()
+        .windows(3)
+        .any(|w| w[0] == b'%' && w[1].is_ascii_hexdigit() && w[2].is_ascii_hexdigit())
+}
@@ -0,0 +1,111 @@
+use crate::dir::Dir;
+use crate::dir_entry::DirEntry;
+use crate::file::File;
+use crate::lookup_error::LookupError;
+use crate::path::Path;
+
+impl<'a> Dir<'a> {
+    /// A view of the sub-directory at `path` where every lookup is relative
+    /// to that sub-directory, or `None` if there isn't one.
+    ///
+    /// This is handy for handing part of the tree to code which shouldn't
+    /// know (or care) where it lives, like giving each plugin its own
+    /// `plugins/<name>` directory.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir};
+    ///
+    /// static TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+    ///
+    /// let migrations = TREE.scoped("migrations").unwrap();
+    /// assert!(migrations.get_file("0000_bootstrap.sql").is_some());
+    /// // the view can't see anything outside itself
+    /// assert!(migrations.get_file("../README.md").is_none());
+    /// ```
+    pub fn scoped<S: AsRef<Path>>(&self, path: S) -> Option<ScopedDir<'a>> {
+        self.get_dir(path).map(ScopedDir::from)
+    }
+}
+
+/// A directory which is looked up relative to itself, created with
+/// [`Dir::scoped()`] or from any [`Dir`] with [`ScopedDir::from()`].
+///
+/// A `Dir` uses the full paths stored by `include_dir!()` everywhere, so
+/// `TREE.get_dir("migrations").unwrap().get_file("README.md")` doesn't find
+/// `migrations/README.md`. A `ScopedDir` takes paths relative to itself
+/// instead, in lookups, walks, and when extracting. Paths can't use `..`, so
+/// nothing above the view can be reached.
+///
+/// It is just a wrapper around the [`Dir`], so creating one doesn't copy
+/// anything. The entries it hands out are the same as the underlying
+/// `Dir`'s, which means methods like [`File::path()`] still return the full
+/// stored path.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ScopedDir<'a> {
+    dir: Dir<'a>,
+}
+
+impl<'a> ScopedDir<'a> {
+    /// The directory being viewed.
+    pub fn as_dir(&self) -> Dir<'a> {
+        self.dir
+    }
+
+    /// Does the view contain `path`?
+    pub fn contains<S: AsRef<Path>>(&self, path: S) -> bool {
+        self.try_get_entry(path).is_ok()
+    }
+
+    /// Fetch a file or sub-directory by its path relative to the view.
+    pub fn get_entry<S: AsRef<Path>>(&self, path: S) -> Option<DirEntry<'a>> {
+        self.try_get_entry(path).ok()
+    }
+
+    /// Fetch a file by its path relative to the view.
+    pub fn get_file<S: AsRef<Path>>(&self, path: S) -> Option<File<'a>> {
+        self.get_entry(path)?.as_file()
+    }
+
+    /// Fetch a sub-directory by its path relative to the view, as another
+    /// view.
+    pub fn get_dir<S: AsRef<Path>>(&self, path: S) -> Option<ScopedDir<'a>> {
+        self.get_entry(path)?.as_dir().map(ScopedDir::from)
+    }
+
+    /// Like [`ScopedDir::get_entry()`], but saying why nothing was found.
+    ///
+    /// See [`Dir::try_get_entry()`].
+    pub fn try_get_entry<S: AsRef<Path>>(&self, path: S) -> Result<DirEntry<'a>, LookupError> {
+        self.dir.try_get_relative(path.as_ref())
+    }
+
+    /// Recursively iterate over everything inside the view, in the same
+    /// order as [`Dir::walk()`], along with each entry's path relative to
+    /// the view.
+    pub fn walk(&self) -> impl Iterator<Item = (&'a Path, DirEntry<'a>)> {
+        let dir = self.dir;
+
+        dir.walk().map(move |entry| {
+            let relative = entry
+                .relative_to(&dir)
+                .expect("Everything in the walk is inside the directory");
+            (relative, entry)
+        })
+    }
+
+    /// Extract everything inside the view into `path`, using paths relative
+    /// to the view.
+    ///
+    /// This is the same as [`Dir::extract()`], which already strips the
+    /// directory's own path.
+    #[cfg(feature = "std")]
+    pub fn extract<S: AsRef<Path>>(&self, path: S) -> std::io::Result<()> {
+        self.dir.extract(path)
+    }
+}
+
+impl<'a> From<Dir<'a>> for ScopedDir<'a> {
+    fn from(dir: Dir<'a>) -> ScopedDir<'a> {
+        ScopedDir { dir }
+    }
+}
@@ -0,0 +1,101 @@
+use crate::dir::Dir;
+use crate::file::File;
+#[cfg(feature = "precache")]
+use crate::precache::PrecacheEntry;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// Serializes a [`Dir`]'s structure without any file contents.
+///
+/// Serializing a [`Dir`] directly includes every file's contents, which is
+/// usually more than you want when sending a listing to a client. This
+/// wrapper gives the same shape, but files only have a `path` and `len`.
+///
+/// ```rust
+/// # use include_dir::{include_dir, Dir, DirManifest};
+/// const ASSETS: Dir = include_dir!("src");
+///
+/// let manifest = serde_json::to_string(&DirManifest(&ASSETS)).unwrap();
+/// assert!(manifest.contains(r#""path":"lib.rs""#));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DirManifest<'a>(pub &'a Dir<'a>);
+
+/// A [`File`] without its contents.
+struct FileManifest<'a>(&'a File<'a>);
+
+impl<'a> Serialize for Dir<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("Dir", 3)?;
+        s.serialize_field("path", self.path)?;
+        s.serialize_field("files", self.files)?;
+        s.serialize_field("dirs", self.dirs)?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for File<'a> {
+    /// Files are serialized with their original contents, decompressing them
+    /// if necessary.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let contents = self.uncompressed();
+
+        let mut s = serializer.serialize_struct("File", 3)?;
+        s.serialize_field("path", self.path)?;
+        s.serialize_field("len", &contents.len())?;
+        s.serialize_field("contents", &Bytes(&contents))?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for DirManifest<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let dir = self.0;
+
+        let mut s = serializer.serialize_struct("Dir", 3)?;
+        s.serialize_field("path", dir.path)?;
+        s.serialize_field("files", &Seq(dir.files.iter().map(FileManifest)))?;
+        s.serialize_field("dirs", &Seq(dir.dirs.iter().map(DirManifest)))?;
+        s.end()
+    }
+}
+
+impl<'a> Serialize for FileManifest<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("File", 2)?;
+        s.serialize_field("path", self.0.path)?;
+        s.serialize_field("len", &self.0.uncompressed().len())?;
+        s.end()
+    }
+}
+
+#[cfg(feature = "precache")]
+impl Serialize for PrecacheEntry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("PrecacheEntry", 2)?;
+        s.serialize_field("url", self.url())?;
+        s.serialize_field("revision", self.revision())?;
+        s.end()
+    }
+}
+
+/// Use `serialize_bytes()` so formats with a native byte type can use it.
+struct Bytes<'b>(&'b [u8]);
+
+impl<'b> Serialize for Bytes<'b> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// Serialize the items from an iterator as a sequence.
+struct Seq<I>(I);
+
+impl<I> Serialize for Seq<I>
+where
+    I: Iterator + Clone,
+    I::Item: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.clone())
+    }
+}
@@ -0,0 +1,54 @@
+use crate::dir::Dir;
+use crate::file::File;
+
+/// A directory embedded by `include_dir!()`.
+///
+/// The macro only ever generates `'static` data, so everything reached
+/// through a `StaticDir` (its files, sub-directories, paths, and contents)
+/// is `'static` as well. Use this in signatures instead of `Dir<'_>` when
+/// the borrows need to outlive the call, e.g. to hand file contents to
+/// something like `Bytes::from_static()`.
+///
+/// Directories created at runtime with `DirBuilder` borrow from their
+/// `OwnedDir`, so they are only `'static` once it has been leaked with
+/// `OwnedDir::leak()`.
+pub type StaticDir = Dir<'static>;
+
+/// A file embedded by `include_dir!()`. See [`StaticDir`].
+pub type StaticFile = File<'static>;
+
+impl File<'static> {
+    /// The file's [raw contents](File::contents), spelling out that they
+    /// live for the rest of the program.
+    ///
+    /// This is always available for files embedded by `include_dir!()`.
+    /// For a [compressed](File::is_compressed) file these are the
+    /// compressed bytes, since decompressing has to allocate.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, StaticDir};
+    ///
+    /// static TREE: StaticDir = include_dir!("tests/fixtures/tree");
+    ///
+    /// fn readme() -> &'static [u8] {
+    ///     TREE.get_file("README.md").unwrap().contents_static()
+    /// }
+    ///
+    /// assert!(!readme().is_empty());
+    /// ```
+    pub fn contents_static(&self) -> &'static [u8] {
+        self.contents
+    }
+
+    /// The file's contents as a `'static` string, or `None` if they aren't
+    /// valid UTF-8. See [`File::contents_utf8()`].
+    pub fn contents_utf8_static(&self) -> Option<&'static str> {
+        self.contents_utf8()
+    }
+
+    /// The file's path, spelling out that it lives for the rest of the
+    /// program.
+    pub fn path_static(&self) -> &'static str {
+        self.path
+    }
+}
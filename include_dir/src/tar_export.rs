@@ -0,0 +1,56 @@
+use crate::dir::Dir;
+use crate::dir_entry::DirEntry;
+use crate::file::File;
+use std::io::{self, Write};
+use tar::{Builder, EntryType, Header};
+
+impl<'a> Dir<'a> {
+    /// Write everything inside this directory to a POSIX (ustar) tar
+    /// archive.
+    ///
+    /// Entries use the same paths as [`Dir::walk()`], with files given mode
+    /// `0644` and directories `0755`. The archive is written sequentially,
+    /// so `writer` doesn't need to be seekable and can be a pipe or socket.
+    ///
+    /// When the `metadata` feature is enabled, files keep the modification
+    /// time they had when they were embedded. Otherwise every entry is
+    /// timestamped with the Unix epoch so archives are reproducible.
+    pub fn write_tar<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut tar = Builder::new(writer);
+
+        for entry in self.walk() {
+            let mut header = Header::new_ustar();
+
+            match entry {
+                DirEntry::Dir(dir) => {
+                    header.set_entry_type(EntryType::Directory);
+                    header.set_mode(0o755);
+                    header.set_size(0);
+                    header.set_mtime(0);
+                    tar.append_data(&mut header, dir.path, io::empty())?;
+                }
+                DirEntry::File(file) => {
+                    let contents = file.uncompressed();
+
+                    header.set_entry_type(EntryType::Regular);
+                    header.set_mode(0o644);
+                    header.set_size(contents.len() as u64);
+                    header.set_mtime(last_modified(&file));
+                    tar.append_data(&mut header, file.path, &*contents)?;
+                }
+            }
+        }
+
+        tar.into_inner()?.flush()
+    }
+}
+
+#[cfg(feature = "metadata")]
+fn last_modified(file: &File<'_>) -> u64 {
+    file.metadata().map(|m| m.modified).unwrap_or(0)
+}
+
+#[cfg(not(feature = "metadata"))]
+fn last_modified(_file: &File<'_>) -> u64 {
+    0
+}
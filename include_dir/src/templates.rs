@@ -0,0 +1,96 @@
+use crate::dir::Dir;
+use std::str;
+use tera::Tera;
+
+/// Options controlling how [`Dir::add_to_tera()`] turns files into
+/// templates.
+///
+/// By default every file is added, named by its path with `/` separators,
+/// and a file which isn't valid UTF-8 is an error.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct TemplateOptions<'s> {
+    strip_suffix: Option<&'s str>,
+    skip_non_utf8: bool,
+}
+
+impl<'s> TemplateOptions<'s> {
+    /// Create a new set of options with the default settings.
+    pub fn new() -> TemplateOptions<'s> {
+        TemplateOptions::default()
+    }
+
+    /// Remove `suffix` from the end of template names, so `index.html.tera`
+    /// can be rendered as `index.html`. Names without the suffix are left
+    /// alone.
+    pub fn strip_suffix(mut self, suffix: &'s str) -> TemplateOptions<'s> {
+        self.strip_suffix = Some(suffix);
+        self
+    }
+
+    /// Should files which aren't valid UTF-8 (e.g. images sitting next to
+    /// the templates) be skipped instead of causing an error?
+    pub fn skip_non_utf8(mut self, skip: bool) -> TemplateOptions<'s> {
+        self.skip_non_utf8 = skip;
+        self
+    }
+}
+
+impl<'a> Dir<'a> {
+    /// Add every file inside this directory to `tera` as a template.
+    ///
+    /// Templates are named by their path relative to this directory, always
+    /// using `/` as the separator (e.g. `emails/welcome.html`), so templates
+    /// can extend and include each other the same way on every platform.
+    /// They are all added at once, so the order they are stored in doesn't
+    /// matter for inheritance.
+    ///
+    /// If a template fails to parse, the error says which one it was.
+    ///
+    /// ```rust
+    /// use include_dir::{include_dir, Dir, TemplateOptions};
+    /// use tera::{Context, Tera};
+    ///
+    /// static TEMPLATES: Dir<'_> = include_dir!("tests/fixtures/templates");
+    ///
+    /// let mut tera = Tera::default();
+    /// let options = TemplateOptions::new()
+    ///     .strip_suffix(".tera")
+    ///     .skip_non_utf8(true);
+    /// TEMPLATES.add_to_tera(&mut tera, &options)?;
+    ///
+    /// let mut context = Context::new();
+    /// context.insert("name", "World");
+    /// assert!(tera.render("index.html", &context)?.contains("Hello, World!"));
+    /// # Ok::<(), tera::Error>(())
+    /// ```
+    pub fn add_to_tera(&self, tera: &mut Tera, options: &TemplateOptions<'_>) -> tera::Result<()> {
+        let mut templates = Vec::new();
+
+        for file in self.walk().filter_map(|entry| entry.as_file()) {
+            let contents = file.uncompressed();
+            let source = match str::from_utf8(&contents) {
+                Ok(source) => source.to_string(),
+                Err(_) if options.skip_non_utf8 => continue,
+                Err(e) => {
+                    return Err(tera::Error::msg(format!(
+                        "The template \"{}\" isn't valid UTF-8: {}",
+                        file.path, e
+                    )))
+                }
+            };
+
+            let name = file
+                .relative_to(self)
+                .unwrap_or(file.path())
+                .to_string_lossy();
+            let name = match options.strip_suffix {
+                Some(suffix) => name.strip_suffix(suffix).unwrap_or(&name).to_string(),
+                None => name.into_owned(),
+            };
+            // stored paths could have come from somewhere using backslashes
+            templates.push((name.replace('\\', "/"), source));
+        }
+
+        tera.add_raw_templates(templates)
+    }
+}
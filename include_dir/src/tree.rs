@@ -0,0 +1,110 @@
+use crate::dir::Dir;
+use crate::dir_entry::DirEntry;
+use core::fmt::{self, Display, Formatter};
+
+/// Renders a [`Dir`] like the Unix `tree` command, created with
+/// [`Dir::tree()`].
+///
+/// Entries are listed in sorted order and each file is followed by the
+/// number of bytes it takes up in the binary (so compressed files show
+/// their compressed size). File contents are never printed.
+///
+/// ```text
+/// assets/
+/// ├── css/
+/// │   └── main.css (4.1 KB)
+/// └── index.html (812 B)
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Tree<'a> {
+    dir: Dir<'a>,
+}
+
+impl<'a> Dir<'a> {
+    /// Get something which will render this directory's contents like the
+    /// Unix `tree` command when displayed.
+    pub fn tree(&self) -> Tree<'a> {
+        Tree { dir: *self }
+    }
+}
+
+impl<'a> Display for Tree<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.dir.path.is_empty() {
+            writeln!(f, ".")?;
+        } else {
+            writeln!(f, "{}/", self.dir.path)?;
+        }
+
+        write_children(f, self.dir, None)
+    }
+}
+
+/// The indentation for one level of the tree, linked to its parent so we
+/// don't need to allocate a string for every line.
+struct Indent<'p> {
+    parent: Option<&'p Indent<'p>>,
+    last: bool,
+}
+
+impl<'p> Display for Indent<'p> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(parent) = self.parent {
+            parent.fmt(f)?;
+        }
+
+        f.write_str(if self.last { "    " } else { "│   " })
+    }
+}
+
+fn write_children(f: &mut Formatter<'_>, dir: Dir<'_>, indent: Option<&Indent<'_>>) -> fmt::Result {
+    let mut entries = dir.entries().peekable();
+
+    while let Some(entry) = entries.next() {
+        let last = entries.peek().is_none();
+
+        if let Some(indent) = indent {
+            indent.fmt(f)?;
+        }
+        f.write_str(if last { "└── " } else { "├── " })?;
+
+        match entry {
+            DirEntry::File(file) => {
+                writeln!(f, "{} ({})", file.file_name(), Size(file.contents.len()))?
+            }
+            DirEntry::Dir(child) => {
+                writeln!(f, "{}/", child.file_name())?;
+
+                let indent = Indent {
+                    parent: indent,
+                    last,
+                };
+                write_children(f, child, Some(&indent))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A human-readable number of bytes.
+struct Size(usize);
+
+impl Display for Size {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+
+        let mut size = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+
+        write!(f, "{:.1} {}", size, UNITS[unit])
+    }
+}
@@ -0,0 +1,126 @@
+use crate::dir::Dir;
+use crate::hash::{hash_children, hash_contents};
+use crate::path::{self, Path};
+use core::fmt::{self, Display, Formatter};
+
+impl<'a> Dir<'a> {
+    /// The aggregate SHA-256 digest of everything inside this directory, as
+    /// calculated when it was embedded.
+    ///
+    /// This is the same as [`Dir::hash()`], wrapped up so it can be printed
+    /// as hex and compared against a digest recorded somewhere else (e.g. a
+    /// build manifest produced by CI).
+    pub fn digest(&self) -> Digest {
+        Digest(self.hash)
+    }
+
+    /// Re-hash every embedded file and make sure it still matches the hash
+    /// recorded at compile time.
+    ///
+    /// Each directory's recorded hash is also checked against the hashes of
+    /// its children, so once this passes, [`Dir::digest()`] is a trustworthy
+    /// summary of the whole tree. On its own this only shows the contents
+    /// match the hashes embedded alongside them; compare the digest against
+    /// a value from somewhere you trust to detect both being replaced.
+    ///
+    /// Every byte is read (and compressed files decompressed), so this takes
+    /// time proportional to the size of the tree.
+    pub fn verify(&self) -> Result<(), VerifyError<'a>> {
+        for file in self.files {
+            #[allow(clippy::needless_borrow)]
+            let actual = hash_contents(&file.uncompressed());
+            if actual != file.hash {
+                return Err(VerifyError {
+                    path: file.path,
+                    kind: Kind::File,
+                });
+            }
+        }
+
+        for dir in self.dirs {
+            dir.verify()?;
+        }
+
+        let expected = hash_children(
+            self.files.iter().map(|f| (f.file_name(), &f.hash)),
+            self.dirs.iter().map(|d| (d.file_name(), &d.hash)),
+        );
+        if expected != self.hash {
+            return Err(VerifyError {
+                path: self.path,
+                kind: Kind::Dir,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// A SHA-256 digest, created with [`Dir::digest()`].
+///
+/// This displays as 64 lowercase hex characters.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Digest([u8; 32]);
+
+impl Digest {
+    /// The raw bytes of the digest.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Display for Digest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The error returned by [`Dir::verify()`] when something doesn't match its
+/// compile-time hash.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VerifyError<'a> {
+    path: &'a str,
+    kind: Kind,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Kind {
+    File,
+    Dir,
+}
+
+impl<'a> VerifyError<'a> {
+    /// The first file or directory which didn't match.
+    pub fn path(&self) -> &'a Path {
+        path::new(self.path)
+    }
+
+    /// Was it a directory which didn't match its children?
+    pub fn is_dir(&self) -> bool {
+        self.kind == Kind::Dir
+    }
+}
+
+impl<'a> Display for VerifyError<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            Kind::File => write!(
+                f,
+                "The contents of \"{}\" don't match the hash recorded when it was embedded",
+                self.path
+            ),
+            Kind::Dir => write!(
+                f,
+                "The hash of \"{}\" doesn't match the hashes of its contents",
+                self.path
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> std::error::Error for VerifyError<'a> {}
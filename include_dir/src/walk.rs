@@ -0,0 +1,73 @@
+use crate::dir::{file_name, Dir};
+use crate::dir_entry::{DirEntry, Entries};
+
+/// A depth-first iterator over every file and sub-directory inside a
+/// [`Dir`], created by [`Dir::walk()`].
+///
+/// Each directory is yielded immediately before its contents, and siblings
+/// are visited in order of their paths.
+///
+/// This doesn't allocate. Instead of keeping a stack of parent directories,
+/// it looks a directory's parent up again (from the top of the walk) once
+/// everything inside it has been visited.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Walk<'a> {
+    root: Dir<'a>,
+    current: Dir<'a>,
+    depth: usize,
+    entries: Entries<'a>,
+}
+
+impl<'a> Walk<'a> {
+    pub(crate) fn new(root: Dir<'a>) -> Walk<'a> {
+        Walk {
+            root,
+            current: root,
+            depth: 0,
+            entries: Entries::new(root),
+        }
+    }
+
+    /// Go back up to the parent of the current directory, carrying on from
+    /// just after it.
+    fn ascend(&mut self) -> Option<()> {
+        self.depth = self.depth.checked_sub(1)?;
+
+        let parent = if self.depth == 0 {
+            self.root
+        } else {
+            let name = file_name(self.current.path);
+            let parent_path = &self.current.path[..self.current.path.len() - name.len()];
+            *self.root.find_stored_dir(parent_path)?
+        };
+
+        self.entries = Entries::after(parent, &self.current);
+        self.current = parent;
+
+        Some(())
+    }
+}
+
+impl<'a> Iterator for Walk<'a> {
+    type Item = DirEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let entry = match self.entries.next() {
+                Some(entry) => entry,
+                None => {
+                    self.ascend()?;
+                    continue;
+                }
+            };
+
+            if let DirEntry::Dir(dir) = entry {
+                self.current = dir;
+                self.depth += 1;
+                self.entries = Entries::new(dir);
+            }
+
+            return Some(entry);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::dir::Dir;
+use crate::dir_entry::DirEntry;
+use crate::file::File;
+use std::io::{self, Seek, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, DateTime, ZipWriter};
+
+impl<'a> Dir<'a> {
+    /// Write everything inside this directory to a zip archive.
+    ///
+    /// Entries use the same paths as [`Dir::walk()`], and every
+    /// sub-directory gets its own entry so empty directories are kept. Files
+    /// are compressed with DEFLATE, using their original contents if they
+    /// were embedded with `compress = true`.
+    ///
+    /// When the `metadata` feature is enabled, files keep the modification
+    /// time they had when they were embedded. Otherwise every entry is
+    /// timestamped 1980-01-01 (the earliest time zip supports) so archives
+    /// are reproducible.
+    pub fn write_zip<W: Write + Seek>(&self, writer: W) -> io::Result<()> {
+        let mut zip = ZipWriter::new(writer);
+
+        for entry in self.walk() {
+            match entry {
+                DirEntry::Dir(dir) => {
+                    let options = SimpleFileOptions::default()
+                        .last_modified_time(DateTime::DEFAULT)
+                        .unix_permissions(0o755);
+                    zip.add_directory(dir.path, options)?;
+                }
+                DirEntry::File(file) => {
+                    let options = SimpleFileOptions::default()
+                        .compression_method(CompressionMethod::Deflated)
+                        .last_modified_time(last_modified(&file))
+                        .unix_permissions(0o644);
+                    zip.start_file(file.path, options)?;
+                    zip.write_all(&file.uncompressed())?;
+                }
+            }
+        }
+
+        zip.finish()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "metadata")]
+fn last_modified(file: &File<'_>) -> DateTime {
+    let secs = match file.metadata() {
+        Some(metadata) => metadata.modified,
+        None => return DateTime::DEFAULT,
+    };
+
+    let days = (secs / 86_400) as i64;
+    let (year, month, day) = civil_from_days(days);
+    let time = secs % 86_400;
+
+    DateTime::from_date_and_time(
+        year as u16,
+        month,
+        day,
+        (time / 3600) as u8,
+        (time / 60 % 60) as u8,
+        (time % 60) as u8,
+    )
+    .unwrap_or(DateTime::DEFAULT)
+}
+
+#[cfg(not(feature = "metadata"))]
+fn last_modified(_file: &File<'_>) -> DateTime {
+    DateTime::DEFAULT
+}
+
+/// Convert days since the Unix epoch to a `(year, month, day)` in the
+/// proleptic Gregorian calendar.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+#[cfg(feature = "metadata")]
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    (year, month as u8, day as u8)
+}
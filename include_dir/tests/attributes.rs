@@ -0,0 +1,63 @@
+#![cfg(feature = "attributes")]
+
+use include_dir::{include_dir, Dir};
+
+const ASSETS: Dir<'_> = include_dir!(
+    "tests/fixtures/attributes/assets",
+    attributes = "tests/fixtures/attributes/attributes.toml"
+);
+
+#[test]
+fn every_matching_glob_adds_its_attributes() {
+    let index = ASSETS.get_file("fr/index.html").unwrap();
+
+    assert_eq!(
+        index.attrs(),
+        [
+            ("cache", "no-cache"),
+            ("content-language", "fr"),
+            ("public", "true"),
+        ]
+    );
+}
+
+#[test]
+fn later_globs_override_earlier_ones() {
+    let logo = ASSETS.get_file("img/logo.svg").unwrap();
+    assert_eq!(logo.attr("cache"), Some("public, max-age=31536000"));
+    assert_eq!(logo.attr("public"), Some("true"));
+
+    let report = ASSETS.get_file("private/report.txt").unwrap();
+    assert_eq!(report.attr("cache"), Some("no-cache"));
+    assert_eq!(report.attr("public"), Some("false"));
+}
+
+#[test]
+fn missing_keys_are_none() {
+    let index = ASSETS.get_file("index.html").unwrap();
+
+    assert_eq!(index.attr("content-language"), None);
+    assert_eq!(index.attr(""), None);
+}
+
+#[test]
+fn globs_ignore_the_prefix() {
+    const PREFIXED: Dir<'_> = include_dir!(
+        "tests/fixtures/attributes/assets",
+        prefix = "static",
+        attributes = "tests/fixtures/attributes/attributes.toml"
+    );
+
+    let logo = PREFIXED.get_file("static/img/logo.svg").unwrap();
+    assert_eq!(logo.attr("cache"), Some("public, max-age=31536000"));
+}
+
+#[test]
+fn files_without_a_manifest_have_no_attributes() {
+    const PLAIN: Dir<'_> = include_dir!("tests/fixtures/attributes/assets");
+
+    for file in PLAIN.walk().filter_map(|e| e.as_file()) {
+        assert!(file.attrs().is_empty());
+        assert_eq!(file.attr("cache"), None);
+    }
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "build-id")]
+
+use include_dir::{include_dir, Dir, DirBuilder};
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+const PREFIXED: Dir<'_> = include_dir!("tests/fixtures/tree", prefix = "assets/v1");
+const MIGRATIONS: Dir<'_> = include_dir!("tests/fixtures/tree/migrations");
+
+#[test]
+fn ids_are_short_hex_strings() {
+    for dir in TREE.walk().filter_map(|e| e.as_dir()).chain(Some(TREE)) {
+        let id = dir.build_id();
+        assert_eq!(id.len(), 16, "{}", dir.path().display());
+        assert!(id
+            .bytes()
+            .all(|b| b.is_ascii_hexdigit() && !b.is_ascii_uppercase()));
+    }
+}
+
+#[test]
+fn ids_only_depend_on_the_contents() {
+    assert_eq!(TREE.build_id(), PREFIXED.build_id());
+
+    // the same directory embedded on its own, from a different root
+    let nested = TREE.get_dir("migrations").unwrap();
+    assert_eq!(nested.build_id(), MIGRATIONS.build_id());
+}
+
+#[test]
+fn different_trees_have_different_ids() {
+    let nested = TREE.get_dir("migrations/2023").unwrap();
+    assert_ne!(TREE.build_id(), MIGRATIONS.build_id());
+    assert_ne!(nested.build_id(), MIGRATIONS.build_id());
+
+    let light = include_dir!("tests/fixtures/dedup/light");
+    let dark = include_dir!("tests/fixtures/dedup/dark");
+    assert_ne!(light.build_id(), dark.build_id());
+}
+
+#[test]
+fn built_dirs_have_no_id() {
+    let mut builder = DirBuilder::new();
+    builder.add_file("a.txt", &b"a"[..]).unwrap();
+
+    assert_eq!(builder.build().as_dir().build_id(), "");
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn ids_come_from_the_hash() {
+    let hex: String = TREE.hash()[..8]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    assert_eq!(TREE.build_id(), hex);
+}
@@ -0,0 +1,159 @@
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, Dir, DirBuilder};
+use std::io;
+use std::path::Path;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn build_a_tree_with_intermediate_directories() {
+    let mut builder = DirBuilder::new();
+    builder
+        .add_file("b.txt", &b"b"[..])
+        .unwrap()
+        .add_file("a/nested/deep.txt", b"deep".to_vec())
+        .unwrap()
+        .add_file("a/file.txt", &b"file"[..])
+        .unwrap();
+    let owned = builder.build();
+    let dir = owned.as_dir();
+
+    let paths: Vec<_> = dir.walk().map(|e| e.path()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            Path::new("a"),
+            Path::new("a/file.txt"),
+            Path::new("a/nested"),
+            Path::new("a/nested/deep.txt"),
+            Path::new("b.txt"),
+        ]
+    );
+    let deep = dir.get_file("a/nested/deep.txt").unwrap();
+    assert_eq!(deep.contents(), b"deep");
+    assert_eq!(dir.get_dir("a/nested").unwrap().files().len(), 1);
+}
+
+#[test]
+fn adding_a_file_twice_replaces_it() {
+    let mut builder = DirBuilder::new();
+    builder.add_file("config.toml", &b"old"[..]).unwrap();
+    builder.add_file("./config.toml", &b"new"[..]).unwrap();
+
+    let owned = builder.build();
+    let dir = owned.as_dir();
+
+    assert_eq!(dir.files().len(), 1);
+    assert_eq!(dir.get_file("config.toml").unwrap().contents(), b"new");
+}
+
+#[test]
+fn empty_directories() {
+    let mut builder = DirBuilder::new();
+    builder
+        .add_dir("logs")
+        .unwrap()
+        .add_dir("cache/images")
+        .unwrap();
+    let owned = builder.build();
+    let dir = owned.as_dir();
+
+    assert!(dir.get_dir("logs").unwrap().entries().next().is_none());
+    assert!(dir.contains("cache/images"));
+}
+
+#[test]
+fn invalid_paths_are_rejected() {
+    let mut builder = DirBuilder::new();
+    builder.add_file("a/file.txt", &b""[..]).unwrap();
+
+    for path in &[
+        "",
+        ".",
+        "../escape.txt",
+        "a/../b.txt",
+        "/etc/passwd",
+        "a/file.txt/nested",
+    ] {
+        let err = builder.add_file(path, &b""[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput, "{}", path);
+    }
+    assert!(builder.add_file("a", &b""[..]).is_err());
+    assert!(builder.add_dir("a/file.txt").is_err());
+    assert!(builder.add_dir("").is_err());
+}
+
+#[test]
+fn start_from_an_embedded_tree() {
+    let mut builder = DirBuilder::from_dir(&TREE);
+    builder
+        .add_file(
+            "migrations/2024/003_comments.sql",
+            &b"CREATE TABLE comments;"[..],
+        )
+        .unwrap();
+    let owned = builder.build();
+    let dir = owned.as_dir();
+
+    assert_eq!(dir.file_count(), TREE.file_count() + 1);
+    let readme = dir.get_file("README.md").unwrap();
+    assert_eq!(
+        readme.contents(),
+        TREE.get_file("README.md").unwrap().contents()
+    );
+    assert!(dir.get_file("migrations/2024/003_comments.sql").is_some());
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn hashes_match_the_macro() {
+    let owned = DirBuilder::from_dir(&TREE).build();
+    let rebuilt = owned.as_dir();
+
+    assert_eq!(rebuilt.hash(), TREE.hash());
+    for (built, embedded) in rebuilt.walk().zip(TREE.walk()) {
+        assert_eq!(built.path(), embedded.path());
+        if let (Some(a), Some(b)) = (built.as_file(), embedded.as_file()) {
+            assert_eq!(a.hash(), b.hash());
+        }
+    }
+}
+
+#[test]
+fn owned_dirs_can_be_rebuilt_over_and_over() {
+    for i in 0..100 {
+        let mut builder = DirBuilder::new();
+        builder
+            .add_file(format!("reports/{}.txt", i), i.to_string().into_bytes())
+            .unwrap();
+        let owned = builder.build();
+
+        let report = owned
+            .as_dir()
+            .get_file(format!("reports/{}.txt", i))
+            .unwrap();
+        assert_eq!(report.contents(), i.to_string().as_bytes());
+    }
+}
+
+#[test]
+fn moving_an_owned_dir_keeps_it_valid() {
+    let mut builder = DirBuilder::new();
+    builder.add_file("a/b/c.txt", b"moved".to_vec()).unwrap();
+    let owned = builder.build();
+
+    let moved = Box::new(owned);
+    let dir = moved.as_dir();
+    assert_eq!(dir.get_file("a/b/c.txt").unwrap().contents(), b"moved");
+    assert_eq!(dir.get_dir("a/b").unwrap().path(), Path::new("a/b"));
+}
+
+#[test]
+fn leaked_trees_outlive_the_owned_dir() {
+    let mut builder = DirBuilder::new();
+    builder.add_file("startup.toml", b"once".to_vec()).unwrap();
+    let dir: Dir<'static> = builder.build().leak();
+
+    assert_eq!(dir.get_file("startup.toml").unwrap().contents(), b"once");
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "bytes")]
+
+use bytes::Bytes;
+use include_dir::{include_dir, Dir, DirBuilder};
+
+static TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn embedded_files_are_not_copied() {
+    for file in TREE.walk().filter_map(|e| e.as_file()) {
+        let bytes = file.as_bytes();
+
+        assert_eq!(bytes, file.contents());
+        assert_eq!(bytes.as_ptr(), file.contents().as_ptr());
+        assert_eq!(bytes.len(), file.contents().len());
+    }
+}
+
+#[test]
+fn files_convert_into_bytes() {
+    let readme = TREE.get_file("README.md").unwrap();
+    let bytes = Bytes::from(readme);
+
+    assert_eq!(bytes.as_ptr(), readme.contents().as_ptr());
+}
+
+#[test]
+fn short_lived_files_are_copied() {
+    let contents = b"temporary".to_vec();
+    let mut builder = DirBuilder::new();
+    builder.add_file("temp.txt", &contents[..]).unwrap();
+    let owned = builder.build();
+    let dir = owned.as_dir();
+
+    let bytes = dir.get_file("temp.txt").unwrap().to_bytes();
+
+    assert_eq!(bytes, "temporary");
+    assert_ne!(bytes.as_ptr(), contents.as_ptr());
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn decompressed_bytes() {
+    static COMPRESSED: Dir<'_> = include_dir!("tests/fixtures/tree", compress = true);
+
+    for file in COMPRESSED.walk().filter_map(|e| e.as_file()) {
+        let original = TREE.get_file(file.path()).unwrap();
+        let bytes = file.decompressed_bytes();
+
+        assert_eq!(bytes, original.contents());
+        if !file.is_compressed() {
+            assert_eq!(bytes.as_ptr(), file.contents().as_ptr());
+        }
+    }
+}
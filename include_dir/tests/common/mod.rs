@@ -0,0 +1,50 @@
+//! Building trees by hand, for tests which need entries the macro would
+//! never create.
+//!
+//! `Dir` and `File` have a field for each optional feature, so these fill in
+//! the defaults and the tests only spell out what they care about, with
+//! struct update syntax for anything else (e.g.
+//! `File { hash, ..file("a.txt", b"") }`).
+
+#![allow(dead_code)]
+
+use include_dir::{Dir, File};
+
+/// A file with none of the optional information recorded.
+pub const fn file(path: &'static str, contents: &'static [u8]) -> File<'static> {
+    File {
+        path,
+        contents,
+        #[cfg(feature = "metadata")]
+        metadata: None,
+        #[cfg(feature = "hash")]
+        hash: [0; 32],
+        #[cfg(feature = "compression")]
+        compressed: false,
+        #[cfg(feature = "attributes")]
+        attrs: &[],
+    }
+}
+
+/// A directory with none of the optional information recorded.
+pub const fn dir(
+    path: &'static str,
+    files: &'static [File<'static>],
+    dirs: &'static [Dir<'static>],
+) -> Dir<'static> {
+    Dir {
+        path,
+        files,
+        dirs,
+        #[cfg(feature = "hash")]
+        hash: [0; 32],
+        #[cfg(feature = "hot-reload")]
+        source: "",
+        #[cfg(feature = "source-info")]
+        source_path: "",
+        #[cfg(feature = "source-info")]
+        embedded_at: None,
+        #[cfg(feature = "build-id")]
+        build_id: "",
+    }
+}
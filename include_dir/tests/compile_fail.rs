@@ -0,0 +1,134 @@
+#[test]
+fn compile_fail() {
+    // trybuild compiles each case from its own directory, so relative paths
+    // to fixtures won't work
+    std::env::set_var(
+        "INCLUDE_DIR_FIXTURES",
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"),
+    );
+
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+
+    #[cfg(not(feature = "compression"))]
+    t.compile_fail("tests/ui/without-compression/*.rs");
+
+    #[cfg(feature = "attributes")]
+    {
+        t.compile_fail("tests/ui/attributes/*.rs");
+        t.pass("tests/ui/attributes/pass/*.rs");
+    }
+    #[cfg(not(feature = "attributes"))]
+    t.compile_fail("tests/ui/without-attributes/*.rs");
+
+    #[cfg(feature = "source-info")]
+    {
+        std::env::set_var("SOURCE_DATE_EPOCH", "1714559520");
+        t.pass("tests/ui/source-info/*.rs");
+    }
+
+    #[cfg(feature = "metadata")]
+    {
+        make_mtime_fixtures();
+        std::env::set_var("SOURCE_DATE_EPOCH", "1714559520");
+        t.pass("tests/ui/metadata/*.rs");
+    }
+
+    #[cfg(unix)]
+    {
+        make_symlink_cycle();
+        make_broken_symlink();
+        make_non_utf8_names();
+        std::env::set_var(
+            "INCLUDE_DIR_SYMLINKS",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/symlinks"),
+        );
+        t.compile_fail("tests/ui/unix/*.rs");
+        #[cfg(feature = "std")]
+        t.pass("tests/ui/unix/pass/*.rs");
+    }
+
+    #[cfg(target_os = "linux")]
+    t.pass("tests/ui/linux/pass/*.rs");
+}
+
+/// One file modified long before the `SOURCE_DATE_EPOCH` used by the metadata
+/// cases and one modified after it. Git doesn't preserve mtimes, so they are
+/// set here.
+#[cfg(feature = "metadata")]
+fn make_mtime_fixtures() {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("mtimes");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let touch = |name: &str, mtime: SystemTime| {
+        let path = dir.join(name);
+        std::fs::write(&path, name).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(mtime)
+            .unwrap();
+    };
+    touch("old.txt", UNIX_EPOCH + Duration::from_secs(1_000_000_000));
+    touch("new.txt", UNIX_EPOCH + Duration::from_secs(1_800_000_000));
+
+    std::env::set_var("INCLUDE_DIR_MTIMES", &dir);
+}
+
+/// A directory containing a symlink to its own parent. This can't be checked
+/// in because it would break every `include_dir!(".")` in the crate.
+#[cfg(unix)]
+fn make_symlink_cycle() {
+    let cycle = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("symlink-cycle");
+    let nested = cycle.join("nested");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    let link = nested.join("loop");
+    if link.symlink_metadata().is_err() {
+        std::os::unix::fs::symlink("..", &link).unwrap();
+    }
+
+    std::env::set_var("INCLUDE_DIR_SYMLINK_CYCLE", &cycle);
+}
+
+/// A directory containing a symlink to a file which doesn't exist.
+#[cfg(unix)]
+fn make_broken_symlink() {
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("broken-symlink");
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let link = dir.join("missing.txt");
+    if link.symlink_metadata().is_err() {
+        std::os::unix::fs::symlink("nowhere.txt", &link).unwrap();
+    }
+
+    std::env::set_var("INCLUDE_DIR_BROKEN_SYMLINK", &dir);
+}
+
+/// Directories containing names which aren't valid UTF-8. Not every
+/// filesystem (or git checkout) can store these, so they are created here.
+#[cfg(unix)]
+fn make_non_utf8_names() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let root = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("non-utf8");
+
+    let names = root.join("names");
+    let latin1_dir = names.join(OsStr::from_bytes(b"d\xe9j\xe0"));
+    std::fs::create_dir_all(&latin1_dir).unwrap();
+    std::fs::write(names.join("plain.txt"), "plain").unwrap();
+    std::fs::write(names.join(OsStr::from_bytes(b"caf\xe9.txt")), "coffee").unwrap();
+    std::fs::write(latin1_dir.join("inner.txt"), "inner").unwrap();
+
+    // both of these become "caf\u{fffd}.txt"
+    let clashing = root.join("clashing");
+    std::fs::create_dir_all(&clashing).unwrap();
+    std::fs::write(clashing.join(OsStr::from_bytes(b"caf\xe9.txt")), "e acute").unwrap();
+    std::fs::write(clashing.join(OsStr::from_bytes(b"caf\xe8.txt")), "e grave").unwrap();
+
+    std::env::set_var("INCLUDE_DIR_NON_UTF8", &root);
+}
@@ -0,0 +1,121 @@
+#![cfg(feature = "compression")]
+
+mod common;
+
+use common::file;
+use include_dir::{include_dir, Dir, File};
+use std::borrow::Cow;
+use std::fs;
+use std::path::Path;
+use tempdir::TempDir;
+
+const COMPRESSED: Dir<'_> = include_dir!("tests/fixtures/compression", compress = true);
+const UNCOMPRESSED: Dir<'_> = include_dir!("tests/fixtures/compression");
+
+fn on_disk(path: &Path) -> Vec<u8> {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/compression");
+    fs::read(fixtures.join(path)).unwrap()
+}
+
+#[test]
+fn large_compressible_files_are_compressed() {
+    let big = COMPRESSED.get_file("big.html").unwrap();
+
+    assert!(big.is_compressed());
+    assert!(big.contents().len() < on_disk(big.path()).len());
+    assert_eq!(big.decompressed_contents(), on_disk(big.path()));
+}
+
+#[test]
+fn small_or_incompressible_files_are_stored_as_is() {
+    for name in &["small.txt", "noise.bin"] {
+        let file = COMPRESSED.get_file(name).unwrap();
+
+        assert!(!file.is_compressed(), "{}", name);
+        assert_eq!(file.contents(), &on_disk(file.path())[..]);
+    }
+}
+
+#[test]
+fn nothing_is_compressed_by_default() {
+    for file in UNCOMPRESSED.files() {
+        assert!(!file.is_compressed());
+        assert_eq!(file.decompressed_contents(), file.contents());
+    }
+}
+
+#[test]
+fn compressed_files_are_never_text() {
+    let big = COMPRESSED.get_file("big.html").unwrap();
+    assert_eq!(big.contents_utf8(), None);
+    assert!(big.lines().is_none());
+
+    // DEFLATE output is almost never valid UTF-8, so make some which is
+    let compressed = File {
+        compressed: true,
+        ..file("notes.txt", b"valid UTF-8")
+    };
+    assert_eq!(compressed.contents_utf8(), None);
+    assert!(compressed.lines().is_none());
+    assert_eq!(
+        UNCOMPRESSED.get_file("big.html").unwrap().contents_utf8(),
+        std::str::from_utf8(&on_disk(Path::new("big.html"))).ok()
+    );
+}
+
+#[test]
+fn compressed_files_are_binary() {
+    let compressed = File {
+        compressed: true,
+        ..file("notes.txt", b"valid UTF-8")
+    };
+    assert!(compressed.is_binary());
+
+    for file in COMPRESSED.files().iter().chain(&[compressed]) {
+        assert_eq!(file.is_binary(), file.contents_utf8().is_none());
+        if file.is_compressed() {
+            assert!(file.is_binary(), "{}", file.path().display());
+        }
+    }
+    assert!(!COMPRESSED.get_file("small.txt").unwrap().is_binary());
+}
+
+#[test]
+fn extracting_writes_the_original_bytes() {
+    let tmp = TempDir::new("extract-compressed").unwrap();
+
+    COMPRESSED.extract(tmp.path()).unwrap();
+
+    for file in COMPRESSED.files() {
+        let extracted = fs::read(tmp.path().join(file.path())).unwrap();
+        assert_eq!(extracted, on_disk(file.path()));
+    }
+}
+
+#[test]
+fn decompressed_chunks_stream_the_original_bytes() {
+    let big = COMPRESSED.get_file("big.html").unwrap();
+    let original = on_disk(big.path());
+
+    for &chunk_size in &[1, 7, 4096, 1 << 20] {
+        let chunks: Vec<_> = big.decompressed_chunks(chunk_size).collect();
+
+        let (last, rest) = chunks.split_last().unwrap();
+        assert!(rest.iter().all(|c| c.len() == chunk_size), "{}", chunk_size);
+        assert!(!last.is_empty() && last.len() <= chunk_size);
+        assert_eq!(chunks.concat(), original, "{}", chunk_size);
+    }
+}
+
+#[test]
+fn decompressed_chunks_borrow_uncompressed_files() {
+    let small = COMPRESSED.get_file("small.txt").unwrap();
+
+    for chunk in small.decompressed_chunks(3) {
+        assert!(matches!(chunk, Cow::Borrowed(_)));
+    }
+    assert_eq!(
+        small.decompressed_chunks(3).collect::<Vec<_>>().concat(),
+        small.contents()
+    );
+}
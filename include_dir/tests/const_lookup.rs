@@ -0,0 +1,66 @@
+use include_dir::{include_dir, Dir, File};
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+const MIGRATIONS: Dir<'_> = match TREE.get_dir_const("migrations") {
+    Some(dir) => dir,
+    None => panic!("missing"),
+};
+const README: File<'_> = match TREE.get_file_const("README.md") {
+    Some(file) => file,
+    None => panic!("missing"),
+};
+
+const _: () = assert!(TREE.contains_const("migrations/2023/001_posts.sql"));
+const _: () = assert!(!TREE.contains_const("missing.txt"));
+
+#[test]
+fn const_lookups_happen_at_compile_time() {
+    assert_eq!(MIGRATIONS, TREE.get_dir("migrations").unwrap());
+    assert_eq!(README, TREE.get_file("README.md").unwrap());
+}
+
+#[test]
+fn const_lookups_agree_with_the_runtime_ones() {
+    let paths = [
+        "README.md",
+        "./README.md",
+        "migrations//2023/",
+        "migrations\\2023\\001_posts.sql",
+        "migrations/2023/q1/002_titles.sql",
+        "migrations/./2023",
+        "migrations/../README.md",
+        "/README.md",
+        "\\README.md",
+        "README.md/nope",
+        "README",
+        "missing.txt",
+        "",
+    ];
+
+    for path in paths.iter() {
+        assert_eq!(TREE.get_file_const(path), TREE.get_file(path), "{}", path);
+        assert_eq!(TREE.get_dir_const(path), TREE.get_dir(path), "{}", path);
+        assert_eq!(TREE.contains_const(path), TREE.contains(path), "{}", path);
+    }
+}
+
+#[test]
+fn const_lookups_from_a_sub_directory_use_full_paths() {
+    let sub = MIGRATIONS.get_dir_const("migrations/2023").unwrap();
+
+    assert!(sub.contains_const("migrations/2023/001_posts.sql"));
+    assert!(!sub.contains_const("001_posts.sql"));
+    assert_eq!(
+        sub.get_file_const("migrations/2023/q1/002_titles.sql"),
+        sub.get_file("migrations/2023/q1/002_titles.sql"),
+    );
+}
+
+#[test]
+fn prefixed_trees_need_the_prefix() {
+    const PREFIXED: Dir<'_> = include_dir!("tests/fixtures/tree", prefix = "static/assets");
+
+    assert!(PREFIXED.contains_const("static/assets/README.md"));
+    assert!(!PREFIXED.contains_const("README.md"));
+    assert!(PREFIXED.get_dir_const("static").is_none());
+}
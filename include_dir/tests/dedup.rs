@@ -0,0 +1,62 @@
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, Dir};
+use std::ptr;
+use tempdir::TempDir;
+
+static ICONS: Dir<'_> = include_dir!("tests/fixtures/dedup", deduplicate = true);
+static DUPLICATED: Dir<'_> = include_dir!("tests/fixtures/dedup");
+
+#[test]
+fn identical_files_share_their_contents() {
+    let light = ICONS.get_file("light/icon.svg").unwrap();
+    let dark = ICONS.get_file("dark/icon.svg").unwrap();
+
+    assert_eq!(light.contents(), dark.contents());
+    assert!(ptr::eq(light.contents(), dark.contents()));
+}
+
+#[test]
+fn every_path_is_still_there() {
+    let paths: Vec<_> = ICONS.file_paths().collect();
+    let expected: Vec<_> = DUPLICATED.file_paths().collect();
+    assert_eq!(paths, expected);
+
+    for path in paths {
+        let shared = ICONS.get_file(path).unwrap();
+        let original = DUPLICATED.get_file(path).unwrap();
+        assert_eq!(shared.contents(), original.contents(), "{}", path);
+    }
+
+    let sun = ICONS.get_file("light/sun.svg").unwrap();
+    let moon = ICONS.get_file("dark/moon.svg").unwrap();
+    assert_ne!(sun.contents(), moon.contents());
+}
+
+#[test]
+fn extraction_writes_each_copy() {
+    let tmp = TempDir::new("dedup").unwrap();
+    ICONS.extract(tmp.path()).unwrap();
+
+    for path in ICONS.file_paths() {
+        let written = std::fs::read(tmp.path().join(path)).unwrap();
+        assert_eq!(written, ICONS.get_file(path).unwrap().contents());
+    }
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn compressed_files_are_shared_too() {
+    static COMPRESSED: Dir<'_> =
+        include_dir!("tests/fixtures/dedup", deduplicate = true, compress = true);
+
+    let light = COMPRESSED.get_file("light/icon.svg").unwrap();
+    let dark = COMPRESSED.get_file("dark/icon.svg").unwrap();
+
+    assert!(light.is_compressed());
+    assert!(ptr::eq(light.contents(), dark.contents()));
+    assert_eq!(
+        light.decompressed_contents(),
+        DUPLICATED.get_file("light/icon.svg").unwrap().contents()
+    );
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, Dir, PrefixBehavior};
+use std::fs;
+use std::path::Path;
+use tempdir::TempDir;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+fn extracted() -> TempDir {
+    let tmp = TempDir::new("diff").unwrap();
+    TREE.extract_into(tmp.path(), PrefixBehavior::StripRoot)
+        .unwrap();
+    tmp
+}
+
+#[test]
+fn a_fresh_extraction_is_identical() {
+    let tmp = extracted();
+
+    let diff = TREE.diff(tmp.path()).unwrap();
+
+    assert!(diff.is_identical());
+    assert_eq!(
+        diff.identical().count(),
+        TREE.walk().filter_map(|e| e.as_file()).count()
+    );
+    assert_eq!(diff.missing().count(), 0);
+    assert_eq!(diff.modified().count(), 0);
+    assert_eq!(diff.extra().count(), 0);
+}
+
+#[test]
+fn changes_on_disk_are_categorised() {
+    let tmp = extracted();
+    // same size, different bytes
+    let readme = TREE.get_file("README.md").unwrap();
+    fs::write(tmp.path().join("README.md"), vec![b'x'; readme.len()]).unwrap();
+    // different size
+    fs::write(tmp.path().join("top.rs"), "fn main() {}\n// edited\n").unwrap();
+    fs::remove_file(tmp.path().join("migrations/0000_bootstrap.sql")).unwrap();
+    fs::create_dir_all(tmp.path().join("migrations/2024")).unwrap();
+    fs::write(tmp.path().join("migrations/2024/003_tags.sql"), "").unwrap();
+    fs::write(tmp.path().join("notes.txt"), "todo").unwrap();
+
+    let diff = TREE.diff(tmp.path()).unwrap();
+
+    assert!(!diff.is_identical());
+    assert_eq!(
+        diff.modified().collect::<Vec<_>>(),
+        [Path::new("README.md"), Path::new("top.rs")]
+    );
+    assert_eq!(
+        diff.missing().collect::<Vec<_>>(),
+        [Path::new("migrations/0000_bootstrap.sql")]
+    );
+    assert_eq!(
+        diff.extra().collect::<Vec<_>>(),
+        [
+            Path::new("migrations/2024/003_tags.sql"),
+            Path::new("notes.txt")
+        ]
+    );
+    assert!(diff
+        .identical()
+        .any(|p| p == Path::new("migrations/README.md")));
+}
+
+#[test]
+fn a_directory_in_place_of_a_file_is_modified() {
+    let tmp = extracted();
+    fs::remove_file(tmp.path().join("top.rs")).unwrap();
+    fs::create_dir(tmp.path().join("top.rs")).unwrap();
+    fs::write(tmp.path().join("top.rs/inner.rs"), "").unwrap();
+
+    let diff = TREE.diff(tmp.path()).unwrap();
+
+    assert_eq!(diff.modified().collect::<Vec<_>>(), [Path::new("top.rs")]);
+    assert_eq!(
+        diff.extra().collect::<Vec<_>>(),
+        [Path::new("top.rs/inner.rs")]
+    );
+}
+
+#[test]
+fn paths_are_relative_to_a_sub_directory() {
+    let migrations = TREE.get_dir("migrations").unwrap();
+
+    let diff = migrations.diff("tests/fixtures/tree/migrations").unwrap();
+
+    assert!(diff.is_identical());
+    assert!(diff
+        .identical()
+        .any(|p| p == Path::new("2023/q1/002_titles.sql")));
+}
+
+#[test]
+fn everything_is_missing_when_the_directory_does_not_exist() {
+    let tmp = TempDir::new("diff-missing").unwrap();
+
+    let diff = TREE.diff(tmp.path().join("nope")).unwrap();
+
+    assert_eq!(
+        diff.missing().count(),
+        TREE.walk().filter_map(|e| e.as_file()).count()
+    );
+    assert_eq!(diff.extra().count(), 0);
+}
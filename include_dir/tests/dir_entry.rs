@@ -0,0 +1,34 @@
+use include_dir::{include_dir, Dir};
+use std::path::Path;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn entries_merges_files_and_dirs_by_path() {
+    let paths: Vec<_> = TREE.entries().map(|e| e.path()).collect();
+
+    assert_eq!(
+        paths,
+        vec![
+            Path::new("README.md"),
+            Path::new("migrations"),
+            Path::new("top.rs"),
+        ]
+    );
+    assert_eq!(TREE.entries().len(), TREE.files().len() + TREE.dirs().len());
+}
+
+#[test]
+fn entry_helpers() {
+    let entries: Vec<_> = TREE.get_dir("migrations").unwrap().entries().collect();
+
+    let first = entries[0];
+    assert_eq!(first.path(), Path::new("migrations/0000_bootstrap.sql"));
+    assert!(first.as_file().is_some());
+    assert!(first.as_dir().is_none());
+
+    let second = entries[1];
+    assert_eq!(second.path(), Path::new("migrations/2023"));
+    assert!(second.as_file().is_none());
+    assert_eq!(second.as_dir(), TREE.get_dir("migrations/2023"));
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "embedded-assets")]
+
+use include_dir::{include_dir, Dir, EmbeddedAssets};
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+const BASE: Dir<'_> = include_dir!("tests/fixtures/merge/base");
+const OVERLAY: Dir<'_> = include_dir!("tests/fixtures/merge/overlay");
+
+/// Glue code which only knows about the trait.
+fn render(assets: &dyn EmbeddedAssets, path: &str) -> Option<String> {
+    let contents = assets.get(path)?;
+    Some(String::from_utf8_lossy(&contents).into_owned())
+}
+
+#[test]
+fn files_can_be_fetched_through_the_trait() {
+    let readme = TREE.get_file("README.md").unwrap();
+
+    assert_eq!(
+        EmbeddedAssets::get(&TREE, "README.md").unwrap(),
+        readme.contents()
+    );
+    assert_eq!(
+        render(&TREE, "README.md").as_deref(),
+        readme.contents_utf8()
+    );
+    assert!(EmbeddedAssets::get(&TREE, "migrations").is_none());
+    assert!(EmbeddedAssets::get(&TREE, "missing.txt").is_none());
+}
+
+#[test]
+fn iter_lists_every_file() {
+    let expected: Vec<_> = TREE
+        .walk()
+        .filter_map(|e| e.as_file())
+        .map(|f| f.path().to_str().unwrap().to_string())
+        .collect();
+
+    let paths: Vec<_> = EmbeddedAssets::iter(&TREE)
+        .map(|p| p.into_owned())
+        .collect();
+
+    assert_eq!(paths, expected);
+    assert!(paths.iter().all(|p| TREE.get_file(p).is_some()));
+}
+
+#[test]
+fn merged_directories_work_too() {
+    let merged = BASE.merge(&OVERLAY);
+
+    for path in EmbeddedAssets::iter(&merged) {
+        let file = merged.get_file(&*path).unwrap();
+        assert_eq!(render(&merged, &path).unwrap().as_bytes(), file.contents());
+    }
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, Dir};
+use tempdir::TempDir;
+
+const KEPT: Dir<'_> = include_dir!(
+    "tests/fixtures/empty_dirs",
+    exclude = ["**/.gitkeep"],
+    include_empty_dirs = true,
+);
+const DROPPED: Dir<'_> = include_dir!("tests/fixtures/empty_dirs", exclude = ["**/.gitkeep"]);
+
+#[test]
+fn empty_directories_can_be_kept() {
+    assert!(KEPT.contains("logs"));
+    let logs = KEPT.get_dir("logs").unwrap();
+    assert!(logs.files().is_empty());
+    assert!(logs.dirs().is_empty());
+
+    let thumbnails = KEPT.get_dir("cache/thumbnails").unwrap();
+    assert!(thumbnails.entries().next().is_none());
+}
+
+#[test]
+fn directories_emptied_by_filters_are_dropped_by_default() {
+    assert!(!DROPPED.contains("logs"));
+    assert!(!DROPPED.contains("cache"));
+    assert!(DROPPED.contains("config.toml"));
+}
+
+#[test]
+fn extract_creates_kept_empty_directories() {
+    let tmp = TempDir::new("extract-empty-dirs").unwrap();
+
+    KEPT.extract(tmp.path()).unwrap();
+
+    assert!(tmp.path().join("logs").is_dir());
+    assert!(tmp.path().join("cache/thumbnails").is_dir());
+    assert!(!tmp.path().join("logs/.gitkeep").exists());
+}
@@ -0,0 +1,15 @@
+use include_dir::{include_dir, Dir};
+
+#[test]
+fn expand_a_bare_variable() {
+    const TREE: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/tests/fixtures/tree");
+
+    assert!(TREE.contains("migrations/2023/001_posts.sql"));
+}
+
+#[test]
+fn expand_a_braced_variable() {
+    const TEXT: Dir<'_> = include_dir!("${CARGO_MANIFEST_DIR}/tests/fixtures/text");
+
+    assert!(TEXT.contains("plain.txt"));
+}
@@ -0,0 +1,489 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::{dir, file};
+use include_dir::{
+    include_dir, Collision, Dir, DirEntry, ExtractEvent, ExtractOptions, ExtractReport,
+    PrefixBehavior,
+};
+use std::fs;
+use std::io;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use tempdir::TempDir;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn extract_recreates_nested_directories() {
+    let tmp = TempDir::new("extract-nested").unwrap();
+
+    TREE.extract(tmp.path()).unwrap();
+
+    for entry in TREE.walk() {
+        let on_disk = tmp.path().join(entry.path());
+
+        match entry {
+            DirEntry::Dir(_) => assert!(on_disk.is_dir(), "{}", on_disk.display()),
+            DirEntry::File(f) => assert_eq!(fs::read(&on_disk).unwrap(), f.contents()),
+        }
+    }
+    assert!(tmp
+        .path()
+        .join("migrations/2023/q1/002_titles.sql")
+        .is_file());
+}
+
+#[test]
+fn extract_creates_empty_nested_directories() {
+    const EMPTY: Dir<'_> = dir("", &[], &[dir("a", &[], &[dir("a/b", &[], &[])])]);
+    let tmp = TempDir::new("extract-empty").unwrap();
+
+    EMPTY.extract(tmp.path()).unwrap();
+
+    assert!(tmp.path().join("a/b").is_dir());
+}
+
+fn extract_over_edited_readme(options: &ExtractOptions) -> (TempDir, io::Result<ExtractReport>) {
+    let tmp = TempDir::new("extract-collision").unwrap();
+    fs::write(tmp.path().join("README.md"), "edited by the user").unwrap();
+
+    let result = TREE.extract_with(tmp.path(), options);
+
+    (tmp, result)
+}
+
+#[test]
+fn collisions_are_an_error_by_default() {
+    let (tmp, result) = extract_over_edited_readme(&ExtractOptions::default());
+
+    let err = result.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+    let readme = tmp.path().join("README.md");
+    assert!(err.to_string().contains(&readme.display().to_string()));
+}
+
+#[test]
+fn overwrite_existing_files() {
+    let options = ExtractOptions::new().collision(Collision::Overwrite);
+    let (tmp, result) = extract_over_edited_readme(&options);
+
+    result.unwrap();
+    let readme = TREE.get_file("README.md").unwrap();
+    assert_eq!(
+        fs::read(tmp.path().join("README.md")).unwrap(),
+        readme.contents()
+    );
+}
+
+#[test]
+fn skip_existing_files() {
+    let options = ExtractOptions::new().collision(Collision::Skip).sync(false);
+    let (tmp, result) = extract_over_edited_readme(&options);
+
+    let report = result.unwrap();
+    assert_eq!(report.skipped_files(), &[PathBuf::from("README.md")]);
+    assert!(!report.created_files().contains(&PathBuf::from("README.md")));
+    let readme = fs::read_to_string(tmp.path().join("README.md")).unwrap();
+    assert_eq!(readme, "edited by the user");
+    assert!(tmp
+        .path()
+        .join("migrations/2023/q1/002_titles.sql")
+        .is_file());
+}
+
+#[test]
+fn report_lists_everything_that_was_created() {
+    let tmp = TempDir::new("extract-report").unwrap();
+
+    let report = TREE
+        .extract_with(tmp.path(), &ExtractOptions::default())
+        .unwrap();
+
+    let mut expected_files: Vec<_> = TREE
+        .walk()
+        .filter_map(|e| e.as_file())
+        .map(|f| f.path().to_path_buf())
+        .collect();
+    expected_files.sort();
+    let mut created_files = report.created_files().to_vec();
+    created_files.sort();
+    assert_eq!(created_files, expected_files);
+
+    let mut created_dirs = report.created_dirs().to_vec();
+    created_dirs.sort();
+    assert_eq!(
+        created_dirs,
+        vec![
+            PathBuf::from("migrations"),
+            PathBuf::from("migrations/2023"),
+            PathBuf::from("migrations/2023/q1"),
+        ]
+    );
+    assert!(report.skipped_files().is_empty());
+}
+
+#[test]
+fn progress_events_match_the_report() {
+    let tmp = TempDir::new("extract-progress").unwrap();
+    let mut dirs = Vec::new();
+    let mut started = Vec::new();
+    let mut done = Vec::new();
+    let mut bytes = 0;
+
+    let options = ExtractOptions::new().sync(false);
+    let report = TREE
+        .extract_with_progress(tmp.path(), &options, |event| {
+            match event {
+                ExtractEvent::DirCreated(path) => dirs.push(path.to_path_buf()),
+                ExtractEvent::FileStart { path, size } => {
+                    started.push(path.to_path_buf());
+                    bytes += size;
+                }
+                ExtractEvent::FileDone { path } => {
+                    // every file is finished before the next one starts
+                    assert_eq!(started.last().map(|p| p.as_path()), Some(path));
+                    done.push(path.to_path_buf());
+                }
+                ExtractEvent::FileSkipped { path } => panic!("{} was skipped", path.display()),
+            }
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+
+    assert_eq!(dirs, report.created_dirs());
+    assert_eq!(started, report.created_files());
+    assert_eq!(done, report.created_files());
+    assert_eq!(bytes, TREE.total_size());
+}
+
+#[test]
+fn progress_reports_skipped_files() {
+    let tmp = TempDir::new("extract-progress-skip").unwrap();
+    fs::write(tmp.path().join("README.md"), "edited by the user").unwrap();
+    let mut skipped = Vec::new();
+
+    let options = ExtractOptions::new().collision(Collision::Skip).sync(false);
+    TREE.extract_with_progress(tmp.path(), &options, |event| {
+        if let ExtractEvent::FileSkipped { path } = event {
+            skipped.push(path.to_path_buf());
+        }
+        ControlFlow::Continue(())
+    })
+    .unwrap();
+
+    assert_eq!(skipped, vec![PathBuf::from("README.md")]);
+}
+
+#[test]
+fn the_progress_callback_can_cancel_extraction() {
+    let tmp = TempDir::new("extract-progress-cancel").unwrap();
+    let mut files = 0;
+
+    let err = TREE
+        .extract_with_progress(tmp.path(), &ExtractOptions::new(), |event| match event {
+            ExtractEvent::FileDone { .. } => {
+                files += 1;
+                ControlFlow::Break(())
+            }
+            _ => ControlFlow::Continue(()),
+        })
+        .unwrap_err();
+
+    assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+    assert_eq!(files, 1);
+    let written = TREE
+        .walk()
+        .filter_map(|e| e.as_file())
+        .filter(|f| tmp.path().join(f.path()).exists())
+        .count();
+    assert_eq!(written, 1);
+}
+
+fn leftovers(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.file_name().unwrap().to_str().unwrap().starts_with('.'))
+        .collect()
+}
+
+#[test]
+fn atomic_extraction_into_a_new_directory() {
+    let tmp = TempDir::new("extract-atomic").unwrap();
+    let target = tmp.path().join("cache");
+
+    let report = TREE
+        .extract_atomic(&target, &ExtractOptions::default())
+        .unwrap();
+
+    assert_eq!(report.created_files().len(), 6);
+    assert!(target.join("migrations/2023/q1/002_titles.sql").is_file());
+    assert!(leftovers(tmp.path()).is_empty());
+}
+
+#[test]
+fn atomic_extraction_respects_the_collision_policy() {
+    let tmp = TempDir::new("extract-atomic-collision").unwrap();
+    let target = tmp.path().join("cache");
+    fs::create_dir(&target).unwrap();
+    fs::write(target.join("stale.txt"), "old").unwrap();
+
+    let err = TREE
+        .extract_atomic(&target, &ExtractOptions::default())
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+
+    let skip = ExtractOptions::new().collision(Collision::Skip);
+    let report = TREE.extract_atomic(&target, &skip).unwrap();
+    assert_eq!(report.skipped_files().len(), 6);
+    assert!(target.join("stale.txt").is_file());
+    assert!(!target.join("README.md").exists());
+
+    let overwrite = ExtractOptions::new().collision(Collision::Overwrite);
+    TREE.extract_atomic(&target, &overwrite).unwrap();
+    assert!(!target.join("stale.txt").exists());
+    assert!(target.join("README.md").is_file());
+    assert!(leftovers(tmp.path()).is_empty());
+}
+
+#[test]
+fn failed_atomic_extraction_leaves_nothing_behind() {
+    // the file's parent directory is missing, so writing it will fail
+    const BROKEN: Dir<'_> = dir("", &[file("missing/file.txt", b"")], &[]);
+    let tmp = TempDir::new("extract-atomic-failed").unwrap();
+    let target = tmp.path().join("cache");
+
+    assert!(BROKEN
+        .extract_atomic(&target, &ExtractOptions::default())
+        .is_err());
+
+    assert!(!target.exists());
+    assert!(leftovers(tmp.path()).is_empty());
+}
+
+const PREFIXED: Dir<'_> = include_dir!("tests/fixtures/tree", prefix = "assets");
+
+#[test]
+fn extracting_strips_the_directory_path_by_default() {
+    let tmp = TempDir::new("extract-strip").unwrap();
+
+    PREFIXED.extract(tmp.path()).unwrap();
+
+    assert!(tmp.path().join("README.md").is_file());
+    assert!(tmp.path().join("migrations/2023/001_posts.sql").is_file());
+    assert!(!tmp.path().join("assets").exists());
+}
+
+#[test]
+fn sub_directories_are_extracted_relative_to_themselves() {
+    let tmp = TempDir::new("extract-sub-dir").unwrap();
+    let migrations = TREE.get_dir("migrations/2023").unwrap();
+
+    let report = migrations
+        .extract_with(tmp.path(), &ExtractOptions::default())
+        .unwrap();
+
+    assert!(tmp.path().join("001_posts.sql").is_file());
+    assert!(tmp.path().join("q1/002_titles.sql").is_file());
+    assert_eq!(report.created_dirs(), &[PathBuf::from("q1")]);
+    assert!(report
+        .created_files()
+        .contains(&PathBuf::from("q1/002_titles.sql")));
+}
+
+#[test]
+fn the_root_can_be_kept() {
+    let tmp = TempDir::new("extract-keep-root").unwrap();
+
+    PREFIXED
+        .extract_into(tmp.path(), PrefixBehavior::KeepRoot)
+        .unwrap();
+    TREE.get_dir("migrations/2023")
+        .unwrap()
+        .extract_into(tmp.path(), PrefixBehavior::KeepRoot)
+        .unwrap();
+
+    assert!(tmp.path().join("assets/README.md").is_file());
+    assert!(tmp
+        .path()
+        .join("assets/migrations/2023/001_posts.sql")
+        .is_file());
+    assert!(tmp
+        .path()
+        .join("migrations/2023/q1/002_titles.sql")
+        .is_file());
+    assert!(!tmp.path().join("README.md").exists());
+}
+
+#[test]
+fn strip_root_and_keep_root_agree_at_the_top_level() {
+    let strip = TempDir::new("extract-top-strip").unwrap();
+    let keep = TempDir::new("extract-top-keep").unwrap();
+
+    TREE.extract_into(strip.path(), PrefixBehavior::StripRoot)
+        .unwrap();
+    TREE.extract_into(keep.path(), PrefixBehavior::KeepRoot)
+        .unwrap();
+
+    for file in TREE.walk().filter_map(|e| e.as_file()) {
+        assert!(strip.path().join(file.path()).is_file());
+        assert!(keep.path().join(file.path()).is_file());
+    }
+}
+
+/// Every path under `dir`, relative to `root`.
+fn list_recursively(root: &Path, dir: &Path, paths: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(root.join(dir)).unwrap() {
+        let relative = dir.join(entry.unwrap().file_name());
+        if root.join(&relative).is_dir() {
+            list_recursively(root, &relative, paths);
+        }
+        paths.push(relative);
+    }
+}
+
+/// A destination left over from an older version, with files and
+/// directories which aren't in `TREE` any more.
+fn stale_destination() -> TempDir {
+    let tmp = TempDir::new("extract-clean").unwrap();
+    fs::create_dir_all(tmp.path().join("gone/nested")).unwrap();
+    fs::create_dir_all(tmp.path().join("migrations")).unwrap();
+    fs::write(tmp.path().join("gone/nested/old.txt"), "old").unwrap();
+    fs::write(tmp.path().join("migrations/stale.sql"), "old").unwrap();
+    fs::write(tmp.path().join("old.txt"), "old").unwrap();
+    fs::write(tmp.path().join("README.md"), "old").unwrap();
+    tmp
+}
+
+#[test]
+fn stale_files_are_kept_by_default() {
+    let tmp = stale_destination();
+    let options = ExtractOptions::new()
+        .collision(Collision::Overwrite)
+        .sync(false);
+
+    let report = TREE.extract_with(tmp.path(), &options).unwrap();
+
+    assert!(report.removed().is_empty());
+    assert!(tmp.path().join("old.txt").is_file());
+}
+
+#[test]
+fn cleaning_removes_everything_which_isnt_embedded() {
+    let tmp = stale_destination();
+    let options = ExtractOptions::new()
+        .collision(Collision::Overwrite)
+        .sync(false)
+        .clean(true);
+
+    let report = TREE.extract_with(tmp.path(), &options).unwrap();
+
+    assert_eq!(
+        report.removed(),
+        &[
+            PathBuf::from("gone"),
+            PathBuf::from("migrations/stale.sql"),
+            PathBuf::from("old.txt"),
+        ]
+    );
+    let mut on_disk = Vec::new();
+    list_recursively(tmp.path(), Path::new(""), &mut on_disk);
+    on_disk.sort();
+    let mut embedded: Vec<_> = TREE.walk().map(|e| e.path().to_path_buf()).collect();
+    embedded.sort();
+    assert_eq!(on_disk, embedded);
+    assert_eq!(
+        fs::read(tmp.path().join("README.md")).unwrap(),
+        TREE.get_file("README.md").unwrap().contents()
+    );
+}
+
+#[test]
+fn cleaning_keeps_the_root_when_asked_to() {
+    let tmp = stale_destination();
+    let options = ExtractOptions::new()
+        .collision(Collision::Overwrite)
+        .prefix_behavior(PrefixBehavior::KeepRoot)
+        .sync(false)
+        .clean(true);
+
+    let report = TREE
+        .get_dir("migrations/2023")
+        .unwrap()
+        .extract_with(tmp.path(), &options)
+        .unwrap();
+
+    assert!(report.removed().contains(&PathBuf::from("README.md")));
+    assert!(report
+        .removed()
+        .contains(&PathBuf::from("migrations/stale.sql")));
+    assert!(tmp
+        .path()
+        .join("migrations/2023/q1/002_titles.sql")
+        .is_file());
+}
+
+#[test]
+fn cleaning_refuses_a_destination_which_isnt_a_directory() {
+    let tmp = TempDir::new("extract-clean-file").unwrap();
+    let file = tmp.path().join("file.txt");
+    fs::write(&file, "not a directory").unwrap();
+
+    let err = TREE
+        .extract_with(&file, &ExtractOptions::new().clean(true))
+        .unwrap_err();
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert_eq!(fs::read_to_string(&file).unwrap(), "not a directory");
+}
+
+#[test]
+fn cleaning_refuses_the_root_of_the_filesystem() {
+    let root = std::env::current_dir()
+        .unwrap()
+        .ancestors()
+        .last()
+        .unwrap()
+        .to_path_buf();
+    let options = ExtractOptions::new().clean(true);
+
+    // if the check ever stops working, cancel before anything is written
+    let err = TREE
+        .extract_with_progress(&root, &options, |_| ControlFlow::Break(()))
+        .unwrap_err();
+
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[cfg(unix)]
+#[test]
+fn cleaning_removes_symlinks_without_following_them() {
+    let outside = TempDir::new("extract-clean-outside").unwrap();
+    fs::write(outside.path().join("precious.txt"), "keep me").unwrap();
+    let tmp = TempDir::new("extract-clean-symlinks").unwrap();
+    std::os::unix::fs::symlink(outside.path(), tmp.path().join("linked-dir")).unwrap();
+    std::os::unix::fs::symlink(
+        outside.path().join("precious.txt"),
+        tmp.path().join("linked-file.txt"),
+    )
+    .unwrap();
+
+    let report = TREE
+        .extract_with(tmp.path(), &ExtractOptions::new().sync(false).clean(true))
+        .unwrap();
+
+    assert_eq!(
+        report.removed(),
+        &[
+            PathBuf::from("linked-dir"),
+            PathBuf::from("linked-file.txt")
+        ]
+    );
+    assert!(tmp.path().join("linked-dir").symlink_metadata().is_err());
+    assert_eq!(
+        fs::read_to_string(outside.path().join("precious.txt")).unwrap(),
+        "keep me"
+    );
+}
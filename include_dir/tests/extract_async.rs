@@ -0,0 +1,104 @@
+#![cfg(feature = "tokio")]
+
+use include_dir::{include_dir, Collision, Dir, ExtractOptions, PrefixBehavior};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use tempdir::TempDir;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[tokio::test]
+async fn same_result_as_the_sync_version() {
+    let sync_dir = TempDir::new("extract-sync").unwrap();
+    let async_dir = TempDir::new("extract-async").unwrap();
+    let options = ExtractOptions::new().concurrency(2).sync(false);
+
+    let sync_report = TREE.extract_with(sync_dir.path(), &options).unwrap();
+    let async_report = TREE
+        .extract_async(async_dir.path(), &options)
+        .await
+        .unwrap();
+
+    assert_eq!(async_report, sync_report);
+    for file in TREE.walk().filter_map(|e| e.as_file()) {
+        let written = fs::read(async_dir.path().join(file.path())).unwrap();
+        assert_eq!(written, file.contents());
+    }
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn collision_policies_are_shared() {
+    let tmp = TempDir::new("extract-async-collision").unwrap();
+    fs::write(tmp.path().join("README.md"), "edited by the user").unwrap();
+
+    let err = TREE
+        .extract_async(tmp.path(), &ExtractOptions::default())
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+
+    let skip = ExtractOptions::new().collision(Collision::Skip);
+    let report = TREE.extract_async(tmp.path(), &skip).await.unwrap();
+    assert!(report.skipped_files().contains(&PathBuf::from("README.md")));
+    let readme = fs::read_to_string(tmp.path().join("README.md")).unwrap();
+    assert_eq!(readme, "edited by the user");
+
+    let overwrite = ExtractOptions::new().collision(Collision::Overwrite);
+    TREE.extract_async(tmp.path(), &overwrite).await.unwrap();
+    let readme = fs::read(tmp.path().join("README.md")).unwrap();
+    assert_eq!(readme, TREE.get_file("README.md").unwrap().contents());
+}
+
+#[test]
+#[should_panic(expected = "at least 1")]
+fn concurrency_must_be_positive() {
+    let _ = ExtractOptions::new().concurrency(0);
+}
+
+#[tokio::test]
+async fn prefix_behavior_matches_the_sync_version() {
+    let migrations = TREE.get_dir("migrations").unwrap();
+
+    for prefix in [PrefixBehavior::StripRoot, PrefixBehavior::KeepRoot] {
+        let sync_dir = TempDir::new("extract-prefix-sync").unwrap();
+        let async_dir = TempDir::new("extract-prefix-async").unwrap();
+        let options = ExtractOptions::new().prefix_behavior(prefix).sync(false);
+
+        let sync_report = migrations.extract_with(sync_dir.path(), &options).unwrap();
+        let async_report = migrations
+            .extract_async(async_dir.path(), &options)
+            .await
+            .unwrap();
+
+        assert_eq!(async_report, sync_report);
+        for file in sync_report.created_files() {
+            assert!(async_dir.path().join(file).is_file());
+        }
+    }
+}
+
+#[tokio::test]
+async fn cleaning_matches_the_sync_version() {
+    let sync_dir = TempDir::new("extract-clean-sync").unwrap();
+    let async_dir = TempDir::new("extract-clean-async").unwrap();
+    for dir in [&sync_dir, &async_dir] {
+        fs::create_dir_all(dir.path().join("gone")).unwrap();
+        fs::write(dir.path().join("gone/old.txt"), "old").unwrap();
+        fs::write(dir.path().join("old.txt"), "old").unwrap();
+    }
+    let options = ExtractOptions::new().sync(false).clean(true);
+
+    let sync_report = TREE.extract_with(sync_dir.path(), &options).unwrap();
+    let async_report = TREE
+        .extract_async(async_dir.path(), &options)
+        .await
+        .unwrap();
+
+    assert_eq!(async_report, sync_report);
+    assert_eq!(
+        async_report.removed(),
+        &[PathBuf::from("gone"), PathBuf::from("old.txt")]
+    );
+    assert!(!async_dir.path().join("gone").exists());
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "ffi")]
+
+use include_dir::{include_dir, Dir, RawFile};
+use std::mem::{align_of, offset_of, size_of};
+use std::slice;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn pointers_refer_to_the_embedded_data() {
+    let readme = TREE.get_file("README.md").unwrap();
+
+    let (ptr, len) = readme.as_ptr_len();
+    assert_eq!(ptr, readme.contents().as_ptr());
+    assert_eq!(len, readme.contents().len());
+
+    let raw = readme.as_raw();
+    assert_eq!(raw.contents, ptr);
+    assert_eq!(raw.path, readme.path.as_ptr());
+    assert_eq!(raw.path_len, "README.md".len());
+    assert!(!raw.compressed);
+}
+
+#[test]
+fn the_table_covers_every_file() {
+    let raw = TREE.raw_files();
+    let files: Vec<_> = TREE.walk().filter_map(|e| e.as_file()).collect();
+    assert_eq!(raw.len(), files.len());
+
+    for (raw, file) in raw.iter().zip(files) {
+        // this is what C code would do with the table
+        let path = unsafe { slice::from_raw_parts(raw.path, raw.path_len) };
+        let contents = unsafe { slice::from_raw_parts(raw.contents, raw.contents_len) };
+
+        assert_eq!(path, file.path.as_bytes());
+        assert_eq!(contents, file.contents());
+    }
+}
+
+#[test]
+fn layout_matches_the_documented_c_struct() {
+    let word = size_of::<usize>();
+
+    assert_eq!(offset_of!(RawFile, path), 0);
+    assert_eq!(offset_of!(RawFile, path_len), word);
+    assert_eq!(offset_of!(RawFile, contents), 2 * word);
+    assert_eq!(offset_of!(RawFile, contents_len), 3 * word);
+    assert_eq!(offset_of!(RawFile, compressed), 4 * word);
+    assert_eq!(size_of::<RawFile>(), 5 * word);
+    assert_eq!(align_of::<RawFile>(), align_of::<usize>());
+}
@@ -0,0 +1,320 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::{dir, file};
+use include_dir::{include_dir, Dir, File};
+use std::collections::{BTreeSet, HashSet};
+
+const TEXT: Dir<'_> = include_dir!("tests/fixtures/text");
+
+#[test]
+fn contents_utf8_for_text_files() {
+    let plain = TEXT.get_file("plain.txt").unwrap();
+    assert_eq!(plain.contents_utf8(), Some("plain ascii\n"));
+}
+
+#[test]
+fn contents_utf8_for_an_empty_file() {
+    let empty = TEXT.get_file("empty.txt").unwrap();
+    assert_eq!(empty.contents_utf8(), Some(""));
+}
+
+#[test]
+fn contents_utf8_keeps_the_byte_order_mark() {
+    let bom = TEXT.get_file("bom.txt").unwrap();
+    assert_eq!(bom.contents_utf8(), Some("\u{feff}hello\n"));
+}
+
+#[test]
+fn contents_utf8_rejects_binary_files() {
+    let binary = TEXT.get_file("binary.bin").unwrap();
+    assert_eq!(binary.contents_utf8(), None);
+}
+
+#[test]
+fn binary_files_are_the_ones_which_arent_utf8() {
+    for file in TEXT.files() {
+        assert_eq!(
+            file.is_binary(),
+            file.contents_utf8().is_none(),
+            "{}",
+            file.path().display()
+        );
+    }
+
+    assert!(TEXT.get_file("binary.bin").unwrap().is_binary());
+    assert!(!TEXT.get_file("plain.txt").unwrap().is_binary());
+    assert!(!TEXT.get_file("empty.txt").unwrap().is_binary());
+    assert!(TEXT.get_file("utf16.txt").unwrap().is_binary());
+}
+
+#[test]
+fn lines_ignore_the_trailing_newline() {
+    let plain = TEXT.get_file("plain.txt").unwrap();
+    assert_eq!(plain.lines().unwrap().collect::<Vec<_>>(), ["plain ascii"]);
+}
+
+#[test]
+fn lines_strip_crlf_line_endings() {
+    let crlf = TEXT.get_file("crlf.txt").unwrap();
+    assert_eq!(
+        crlf.lines().unwrap().collect::<Vec<_>>(),
+        ["first", "second", "", "last"]
+    );
+}
+
+#[test]
+fn an_empty_file_has_no_lines() {
+    let empty = TEXT.get_file("empty.txt").unwrap();
+    assert_eq!(empty.lines().unwrap().count(), 0);
+    assert_eq!(empty.split(b'\n').count(), 0);
+}
+
+#[test]
+fn binary_files_have_no_lines() {
+    assert!(TEXT.get_file("binary.bin").unwrap().lines().is_none());
+}
+
+#[test]
+fn lines_outlive_the_file() {
+    let words: Vec<&'static str> = {
+        let crlf = TEXT.get_file("crlf.txt").unwrap();
+        crlf.lines().unwrap().filter(|l| !l.is_empty()).collect()
+    };
+
+    assert_eq!(words, ["first", "second", "last"]);
+}
+
+#[test]
+fn split_on_a_separator_byte() {
+    let records = TEXT.get_file("records.bin").unwrap();
+    let records: Vec<&'static [u8]> = records.split(0).collect();
+
+    assert_eq!(records, [&b"one"[..], b"two", b"", b"three"]);
+}
+
+#[test]
+fn split_without_a_trailing_separator() {
+    let crlf = TEXT.get_file("crlf.txt").unwrap();
+    let records: Vec<&[u8]> = crlf.split(b'\n').collect();
+
+    assert_eq!(records, [&b"first\r"[..], b"second\r", b"\r", b"last"]);
+}
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn name_helpers_use_the_last_component() {
+    let posts = TREE.get_file("migrations/2023/001_posts.sql").unwrap();
+    assert_eq!(posts.file_name(), "001_posts.sql");
+    assert_eq!(posts.stem(), Some("001_posts"));
+    assert_eq!(posts.extension(), Some("sql"));
+
+    let q1 = TREE.get_dir("migrations/2023/q1").unwrap();
+    assert_eq!(q1.file_name(), "q1");
+    assert_eq!(TREE.file_name(), "");
+}
+
+#[test]
+fn name_helpers_for_unusual_names() {
+    let gitignore = file("assets/.gitignore", b"");
+    assert_eq!(gitignore.file_name(), ".gitignore");
+    assert_eq!(gitignore.stem(), Some(".gitignore"));
+    assert_eq!(gitignore.extension(), None);
+
+    let archive = file("archive.tar.gz", b"");
+    assert_eq!(archive.stem(), Some("archive.tar"));
+    assert_eq!(archive.extension(), Some("gz"));
+
+    let makefile = file("Makefile", b"");
+    assert_eq!(makefile.stem(), Some("Makefile"));
+    assert_eq!(makefile.extension(), None);
+
+    let trailing_dot = file("notes.", b"");
+    assert_eq!(trailing_dot.stem(), Some("notes"));
+    assert_eq!(trailing_dot.extension(), Some(""));
+}
+
+#[test]
+fn name_helpers_keep_the_original_lifetime() {
+    let names: Vec<&'static str> = TREE.files().iter().map(|f| f.file_name()).collect();
+    assert_eq!(names, vec!["README.md", "top.rs"]);
+}
+
+#[test]
+fn files_can_be_used_as_byte_slices() {
+    let plain = TEXT.get_file("plain.txt").unwrap();
+
+    fn len_of<B: AsRef<[u8]>>(bytes: B) -> usize {
+        bytes.as_ref().len()
+    }
+    assert_eq!(len_of(plain), 12);
+    assert!(plain.starts_with(b"plain"));
+    assert_eq!(plain.to_string(), "plain.txt");
+}
+
+#[test]
+fn length_accessors() {
+    let plain = TEXT.get_file("plain.txt").unwrap();
+    let empty = TEXT.get_file("empty.txt").unwrap();
+
+    assert_eq!(plain.len(), 12);
+    assert!(!plain.is_empty());
+    assert_eq!(empty.len(), 0);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn chunks_borrow_the_contents() {
+    let plain = TEXT.get_file("plain.txt").unwrap();
+
+    let chunks: Vec<&'static [u8]> = plain.chunks(5).collect();
+
+    assert_eq!(chunks, vec![&b"plain"[..], b" asci", b"i\n"]);
+    assert_eq!(
+        plain.chunks(100).collect::<Vec<_>>(),
+        vec![plain.contents()]
+    );
+    assert_eq!(TEXT.get_file("empty.txt").unwrap().chunks(5).count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "The chunk size must be at least 1")]
+fn chunks_must_not_be_empty() {
+    let _ = TEXT.get_file("plain.txt").unwrap().chunks(0);
+}
+
+#[test]
+fn files_are_compared_by_path() {
+    let a = file("a.txt", b"zzz");
+    let other_a = file("a.txt", b"aaa");
+    let b = file("b.txt", b"");
+
+    assert_eq!(a, other_a);
+    assert!(a < b);
+
+    let mut set = HashSet::new();
+    set.insert(a);
+    assert!(!set.insert(other_a));
+
+    let sorted: BTreeSet<_> = TREE.walk().filter_map(|e| e.as_file()).collect();
+    let paths: Vec<_> = sorted.iter().map(|f| f.to_string()).collect();
+    assert_eq!(paths[0], "README.md");
+    assert_eq!(paths.last().unwrap(), "top.rs");
+}
+
+#[test]
+fn directories_compare_files_by_path_too() {
+    static ONE: [File<'static>; 1] = [file("a.txt", b"one")];
+    static TWO: [File<'static>; 1] = [file("a.txt", b"two")];
+
+    assert_eq!(dir("", &ONE, &[]), dir("", &TWO, &[]));
+    assert_ne!(ONE[0].contents(), TWO[0].contents());
+}
+
+#[test]
+fn files_with_extension() {
+    let sql: Vec<_> = TREE.files_with_extension("sql").map(|f| f.path).collect();
+    assert_eq!(
+        sql,
+        vec![
+            "migrations/0000_bootstrap.sql",
+            "migrations/2023/001_posts.sql",
+            "migrations/2023/q1/002_titles.sql",
+        ]
+    );
+
+    let markdown: Vec<_> = TREE.files_with_extension(".MD").map(|f| f.path).collect();
+    assert_eq!(markdown, vec!["README.md", "migrations/README.md"]);
+
+    assert_eq!(TREE.files_with_extension("txt").count(), 0);
+}
+
+#[test]
+fn files_with_unusual_extensions() {
+    const FILES: Dir<'_> = dir(
+        "",
+        &[
+            file(".gitignore", b""),
+            file("Makefile", b""),
+            file("archive.TAR.GZ", b""),
+            file("notes.gz", b""),
+        ],
+        &[],
+    );
+    let matching = |ext| -> Vec<_> { FILES.files_with_extension(ext).map(|f| f.path).collect() };
+
+    assert_eq!(matching("gz"), vec!["archive.TAR.GZ", "notes.gz"]);
+    assert_eq!(matching("tar.gz"), vec!["archive.TAR.GZ"]);
+    assert!(matching("gitignore").is_empty());
+    assert!(matching("Makefile").is_empty());
+}
+
+#[test]
+fn mime_types_from_the_extension() {
+    assert_eq!(file("index.html", b"").mime_type(), Some("text/html"));
+    assert_eq!(file("js/app.mjs", b"").mime_type(), Some("text/javascript"));
+    assert_eq!(
+        file("fonts/inter.woff2", b"").mime_type(),
+        Some("font/woff2")
+    );
+    assert_eq!(
+        file("pkg/app_bg.wasm", b"").mime_type(),
+        Some("application/wasm")
+    );
+    assert_eq!(file("img/icon.svg", b"").mime_type(), Some("image/svg+xml"));
+    assert_eq!(
+        file("archive.tar.gz", b"").mime_type(),
+        Some("application/gzip")
+    );
+}
+
+#[test]
+fn mime_types_ignore_case() {
+    assert_eq!(file("LOGO.PNG", b"").mime_type(), Some("image/png"));
+    assert_eq!(file("Photo.JpG", b"").mime_type(), Some("image/jpeg"));
+}
+
+#[test]
+fn unknown_mime_types() {
+    assert_eq!(file("data.unknown", b"").mime_type(), None);
+    assert_eq!(file("Makefile", b"").mime_type(), None);
+    assert_eq!(file(".gitignore", b"").mime_type(), None);
+}
+
+#[test]
+fn etags_are_quoted_and_follow_the_contents() {
+    let plain = TEXT.get_file("plain.txt").unwrap();
+    let utf16 = TEXT.get_file("utf16.txt").unwrap();
+
+    let etag = plain.etag();
+    assert!(etag.starts_with('"') && etag.ends_with('"') && etag.len() > 2);
+    assert_eq!(etag, plain.etag());
+    assert_ne!(etag, utf16.etag());
+
+    let copy = File {
+        path: "elsewhere.txt",
+        ..plain
+    };
+    assert_eq!(copy.etag(), etag);
+}
+
+#[test]
+fn if_none_match_headers() {
+    let file = TEXT.get_file("plain.txt").unwrap();
+    let etag = file.etag();
+
+    assert!(file.matches_etag(&etag));
+    assert!(file.matches_etag(&format!("W/{}", etag)));
+    assert!(file.matches_etag(&format!("\"a\", \"b,c\" ,W/{} ", etag)));
+    assert!(file.matches_etag("*"));
+    assert!(file.matches_etag(" \"a\", *"));
+
+    assert!(!file.matches_etag(""));
+    assert!(!file.matches_etag("\"a\", W/\"b\""));
+    assert!(!file.matches_etag(&etag[1..etag.len() - 1]));
+    // everything after a malformed tag is ignored
+    assert!(!file.matches_etag(&format!("\"a\", oops, {}", etag)));
+    assert!(!file.matches_etag(&format!("\"unterminated, {}", etag)));
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, Dir};
+use std::path::Path;
+
+fn paths(dir: Dir<'static>) -> Vec<&'static Path> {
+    dir.walk().map(|e| e.path()).collect()
+}
+
+#[test]
+fn excluded_entries_are_not_embedded() {
+    const ASSETS: Dir<'_> = include_dir!(
+        "tests/fixtures/filters",
+        exclude = ["**/.DS_Store", "**/*.psd", "node_modules/**"],
+    );
+
+    assert_eq!(
+        paths(ASSETS),
+        vec![
+            Path::new("css"),
+            Path::new("css/main.css"),
+            Path::new("index.html"),
+        ]
+    );
+}
+
+#[test]
+fn exclude_patterns_are_relative_to_the_included_directory() {
+    const ASSETS: Dir<'_> = include_dir!("tests/fixtures/filters", exclude = ["*.psd"]);
+
+    assert!(!ASSETS.contains("logo.psd"));
+    assert!(ASSETS.contains("design/mockup.psd"));
+}
+
+#[test]
+fn excluding_a_directory_skips_everything_inside_it() {
+    const ASSETS: Dir<'_> = include_dir!("tests/fixtures/filters", exclude = ["node_modules"]);
+
+    assert!(!ASSETS.contains("node_modules"));
+    assert!(!ASSETS.contains("node_modules/left-pad/index.js"));
+    assert!(ASSETS.contains("css/.DS_Store"));
+}
+
+#[test]
+fn only_included_files_are_embedded() {
+    const MIGRATIONS: Dir<'_> = include_dir!("tests/fixtures/tree", include = ["**/*.sql"]);
+
+    assert_eq!(
+        paths(MIGRATIONS),
+        vec![
+            Path::new("migrations"),
+            Path::new("migrations/0000_bootstrap.sql"),
+            Path::new("migrations/2023"),
+            Path::new("migrations/2023/001_posts.sql"),
+            Path::new("migrations/2023/q1"),
+            Path::new("migrations/2023/q1/002_titles.sql"),
+        ]
+    );
+}
+
+#[test]
+fn exclude_takes_priority_over_include() {
+    const MIGRATIONS: Dir<'_> = include_dir!(
+        "tests/fixtures/tree",
+        include = ["**/*.sql", "*.md"],
+        exclude = ["**/0000_*", "migrations/2023/q1"],
+    );
+
+    assert_eq!(
+        paths(MIGRATIONS),
+        vec![
+            Path::new("README.md"),
+            Path::new("migrations"),
+            Path::new("migrations/2023"),
+            Path::new("migrations/2023/001_posts.sql"),
+        ]
+    );
+}
+
+#[test]
+fn requirements_which_are_met_compile() {
+    const MIGRATIONS: Dir<'_> = include_dir!(
+        "tests/fixtures/tree/migrations",
+        prefix = "migrations",
+        deny_empty = true,
+        require = ["0000_bootstrap.sql", "**/*.sql", "2023/q1/*"]
+    );
+
+    assert_eq!(MIGRATIONS.file_count(), 4);
+}
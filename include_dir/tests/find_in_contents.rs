@@ -0,0 +1,55 @@
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, Dir, File};
+use std::path::Path;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+fn matches<'a>(found: impl Iterator<Item = (File<'a>, usize)>) -> Vec<(&'a Path, usize)> {
+    found.map(|(f, ix)| (f.path(), ix)).collect()
+}
+
+#[test]
+fn finds_every_file_containing_the_needle() {
+    let found = matches(TREE.find_in_contents(b"TABLE"));
+
+    assert_eq!(
+        found,
+        vec![
+            (Path::new("migrations/0000_bootstrap.sql"), 7),
+            (Path::new("migrations/2023/001_posts.sql"), 7),
+            (Path::new("migrations/2023/q1/002_titles.sql"), 6),
+        ]
+    );
+}
+
+#[test]
+fn str_needles_work_too() {
+    let found = matches(TREE.find_str_in_contents("ADD COLUMN"));
+
+    assert_eq!(
+        found,
+        vec![(Path::new("migrations/2023/q1/002_titles.sql"), 18)]
+    );
+    assert_eq!(TREE.find_str_in_contents("not in any file").count(), 0);
+}
+
+#[test]
+fn an_empty_needle_matches_everything() {
+    let found = matches(TREE.find_in_contents(b""));
+
+    assert_eq!(found.len(), TREE.file_count());
+    assert!(found.iter().all(|&(_, ix)| ix == 0));
+}
+
+#[test]
+#[cfg(feature = "compression")]
+fn compressed_files_are_searched_by_their_original_contents() {
+    const PLAIN: Dir<'_> = include_dir!("tests/fixtures/compression");
+    const COMPRESSED: Dir<'_> = include_dir!("tests/fixtures/compression", compress = true);
+    let needle = "Paragraph number 99 ";
+
+    let found = matches(COMPRESSED.find_str_in_contents(needle));
+    assert_eq!(found.len(), 1);
+    assert_eq!(found, matches(PLAIN.find_str_in_contents(needle)));
+}
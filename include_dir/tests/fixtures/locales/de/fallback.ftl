@@ -0,0 +1 @@
+fallback = Rückfall
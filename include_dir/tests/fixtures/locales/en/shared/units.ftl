@@ -0,0 +1 @@
+units = imperial
@@ -0,0 +1 @@
+v3/app.js
\ No newline at end of file
@@ -0,0 +1,122 @@
+#![cfg(feature = "frontmatter")]
+
+use include_dir::{include_dir, Dir, DirBuilder};
+
+static CONTENT: Dir<'_> = include_dir!("tests/fixtures/content");
+
+/// Split the front matter from `contents` as if it had been embedded.
+fn split(contents: &str) -> Option<(String, String)> {
+    let mut builder = DirBuilder::new();
+    builder.add_file("post.md", contents.as_bytes()).unwrap();
+    let owned = builder.build();
+    let dir = owned.as_dir();
+
+    let (front_matter, body) = dir.get_file("post.md").unwrap().split_front_matter()?;
+    Some((front_matter.to_string(), body.to_string()))
+}
+
+#[test]
+fn yaml_and_toml_headers_are_split_off() {
+    let hello = CONTENT.get_file("posts/hello.md").unwrap();
+    assert_eq!(
+        hello.split_front_matter(),
+        Some((
+            "title: Hello, World\ndate: 2023-01-02\n",
+            "# Hello\n\nThe first post.\n"
+        ))
+    );
+
+    let second = CONTENT.get_file("posts/second.md").unwrap();
+    assert_eq!(
+        second.split_front_matter(),
+        Some((
+            "title = \"Second Post\"\ntags = [\"rust\"]\n",
+            "The second post.\n"
+        ))
+    );
+}
+
+#[test]
+fn files_without_front_matter_are_none() {
+    // a later `---` is just a horizontal rule
+    let about = CONTENT.get_file("about.md").unwrap();
+    assert_eq!(about.split_front_matter(), None);
+
+    assert_eq!(split(""), None);
+    assert_eq!(split("plain text"), None);
+    assert_eq!(split(" ---\ntitle: x\n---\n"), None);
+    assert_eq!(split("----\ntitle: x\n----\n"), None);
+}
+
+#[test]
+fn unclosed_or_mismatched_fences_are_none() {
+    assert_eq!(split("---"), None);
+    assert_eq!(split("---\ntitle: x\n"), None);
+    assert_eq!(split("---\ntitle: x\n+++\nbody"), None);
+    // the closing fence has to be a whole line
+    assert_eq!(split("---\ntitle: x ---\nbody"), None);
+}
+
+#[test]
+fn crlf_fences_are_recognised() {
+    assert_eq!(
+        split("---\r\ntitle: x\r\n---\r\nbody\r\n"),
+        Some(("title: x\r\n".to_string(), "body\r\n".to_string()))
+    );
+    assert_eq!(
+        split("+++  \r\ntitle = 1\r\n+++\t\r\nbody"),
+        Some(("title = 1\r\n".to_string(), "body".to_string()))
+    );
+}
+
+#[test]
+fn a_fence_on_the_last_line_leaves_an_empty_body() {
+    assert_eq!(
+        split("---\ntitle: x\n---"),
+        Some(("title: x\n".to_string(), String::new()))
+    );
+    assert_eq!(
+        split("---\ntitle: x\n---\n"),
+        Some(("title: x\n".to_string(), String::new()))
+    );
+}
+
+#[test]
+fn empty_headers_are_still_front_matter() {
+    assert_eq!(
+        split("---\n---\nbody"),
+        Some((String::new(), "body".to_string()))
+    );
+}
+
+#[test]
+fn a_byte_order_mark_is_skipped() {
+    assert_eq!(
+        split("\u{feff}---\ntitle: x\n---\nbody"),
+        Some(("title: x\n".to_string(), "body".to_string()))
+    );
+}
+
+#[test]
+fn binary_files_have_no_front_matter() {
+    let mut builder = DirBuilder::new();
+    builder
+        .add_file("post.md", &b"---\n\xff\n---\n"[..])
+        .unwrap();
+    let owned = builder.build();
+    let dir = owned.as_dir();
+
+    assert_eq!(dir.get_file("post.md").unwrap().split_front_matter(), None);
+}
+
+#[test]
+fn every_markdown_post_can_be_split() {
+    let mut titles: Vec<&str> = CONTENT
+        .files_with_extension("md")
+        .filter_map(|post| post.split_front_matter())
+        .filter_map(|(front_matter, _)| front_matter.lines().next())
+        .collect();
+    titles.sort_unstable();
+
+    assert_eq!(titles, ["title = \"Second Post\"", "title: Hello, World"]);
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "search")]
+
+use include_dir::{include_dir, Dir};
+use std::path::Path;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+fn find(glob: &str) -> Vec<&'static Path> {
+    let mut paths: Vec<_> = TREE.find(glob).unwrap().map(|e| e.path()).collect();
+    paths.sort();
+    paths
+}
+
+#[test]
+fn single_star_only_matches_the_top_level() {
+    assert_eq!(find("*.rs"), vec![Path::new("top.rs")]);
+    assert_eq!(find("*.md"), vec![Path::new("README.md")]);
+}
+
+#[test]
+fn double_star_matches_nested_entries() {
+    assert_eq!(
+        find("migrations/**/*.sql"),
+        vec![
+            Path::new("migrations/0000_bootstrap.sql"),
+            Path::new("migrations/2023/001_posts.sql"),
+            Path::new("migrations/2023/q1/002_titles.sql"),
+        ]
+    );
+}
+
+#[test]
+fn directories_can_match_too() {
+    assert_eq!(
+        find("migrations/*"),
+        vec![
+            Path::new("migrations/0000_bootstrap.sql"),
+            Path::new("migrations/2023"),
+            Path::new("migrations/README.md"),
+        ]
+    );
+}
+
+#[test]
+fn wildcards_and_character_classes() {
+    assert_eq!(
+        find("migrations/2023/00?_*.sql"),
+        vec![Path::new("migrations/2023/001_posts.sql")]
+    );
+    assert_eq!(
+        find("**/[0-9][0-9][0-9]_[pt]*.sql"),
+        vec![
+            Path::new("migrations/2023/001_posts.sql"),
+            Path::new("migrations/2023/q1/002_titles.sql"),
+        ]
+    );
+}
+
+#[test]
+fn invalid_patterns_are_an_error() {
+    assert!(TREE.find("[").is_err());
+    assert!(TREE.find("a/***").is_err());
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "hash")]
+
+use include_dir::{include_dir, Dir};
+use sha2::{Digest, Sha256};
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn file_hashes_match_their_contents() {
+    for file in TREE.walk().filter_map(|e| e.as_file()) {
+        let expected: [u8; 32] = Sha256::digest(file.contents()).into();
+        assert_eq!(file.hash(), expected, "{}", file.path().display());
+    }
+}
+
+#[test]
+fn directory_hashes_only_depend_on_their_contents() {
+    const MIGRATIONS: Dir<'_> = include_dir!("tests/fixtures/tree/migrations");
+    const PREFIXED: Dir<'_> = include_dir!("tests/fixtures/tree", prefix = "somewhere/else");
+
+    assert_eq!(
+        TREE.get_dir("migrations").unwrap().hash(),
+        MIGRATIONS.hash()
+    );
+    assert_eq!(TREE.hash(), PREFIXED.hash());
+}
+
+#[test]
+fn directory_hashes_change_with_their_contents() {
+    const FILTERED: Dir<'_> = include_dir!("tests/fixtures/tree", exclude = ["top.rs"]);
+    const MIGRATIONS: Dir<'_> = include_dir!("tests/fixtures/tree/migrations");
+
+    assert_ne!(TREE.hash(), FILTERED.hash());
+    assert_ne!(TREE.hash(), MIGRATIONS.hash());
+    assert_ne!(
+        MIGRATIONS.get_dir("2023").unwrap().hash(),
+        MIGRATIONS.get_dir("2023/q1").unwrap().hash()
+    );
+}
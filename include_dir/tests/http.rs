@@ -0,0 +1,78 @@
+#![cfg(feature = "http")]
+
+use include_dir::{include_dir, Dir};
+
+const SITE: Dir<'_> = include_dir!("tests/fixtures/site");
+
+#[test]
+fn serve_a_file() {
+    let asset = SITE.serve("/css/main.css").unwrap();
+
+    assert_eq!(asset.file().path, "css/main.css");
+    assert_eq!(asset.content_type(), "text/css");
+    assert_eq!(&*asset.body(), b"body { margin: 0; }\n");
+    let etag = asset.etag();
+    assert!(etag.starts_with('"') && etag.ends_with('"') && etag.len() > 2);
+}
+
+#[test]
+fn directories_are_served_using_their_index() {
+    assert_eq!(SITE.serve("/").unwrap().file().path, "index.html");
+    assert_eq!(SITE.serve("").unwrap().file().path, "index.html");
+    assert_eq!(SITE.serve("/blog").unwrap().file().path, "blog/index.html");
+    assert_eq!(SITE.serve("/blog/").unwrap().content_type(), "text/html");
+    assert!(SITE.serve("/css/").is_none());
+}
+
+#[test]
+fn query_strings_and_escapes() {
+    let asset = SITE
+        .serve("/blog/first%20post.html?utm=feed#comments")
+        .unwrap();
+    assert_eq!(asset.file().path, "blog/first post.html");
+
+    assert!(SITE.serve("/blog/first%2").is_none());
+    assert!(SITE.serve("/blog/first%zzpost.html").is_none());
+}
+
+#[test]
+fn misses_and_traversal_are_rejected() {
+    assert!(SITE.serve("/missing.html").is_none());
+    assert!(SITE.serve("/blog/../index.html").is_none());
+    assert!(SITE.serve("/blog/%2e%2e/index.html").is_none());
+    assert!(SITE.serve("/blog%2f..%2f..%2fCargo.toml").is_none());
+}
+
+#[test]
+fn unknown_extensions_are_octet_streams() {
+    let asset = SITE.serve("/data.bin").unwrap();
+    assert_eq!(asset.content_type(), "application/octet-stream");
+}
+
+#[test]
+fn paths_are_relative_to_the_directory_being_served() {
+    const PREFIXED: Dir<'_> = include_dir!("tests/fixtures/site", prefix = "public");
+    assert_eq!(
+        PREFIXED.serve("/css/main.css").unwrap().file().path,
+        "public/css/main.css"
+    );
+    assert_eq!(
+        PREFIXED.serve("/").unwrap().file().path,
+        "public/index.html"
+    );
+
+    let blog = SITE.get_dir("blog").unwrap();
+    assert_eq!(blog.serve("/").unwrap().file().path, "blog/index.html");
+    assert_eq!(
+        blog.serve("/first%20post.html").unwrap().file().path,
+        "blog/first post.html"
+    );
+}
+
+#[test]
+fn etags_change_with_the_contents() {
+    let home = SITE.serve("/").unwrap();
+    let blog = SITE.serve("/blog/").unwrap();
+    assert_ne!(home.etag(), blog.etag());
+    assert_eq!(home.etag(), SITE.serve("/index.html").unwrap().etag());
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::{dir, file};
+use include_dir::{include_dir, Dir};
+use std::path::Path;
+
+const CASE: Dir<'_> = include_dir!("tests/fixtures/case");
+
+// names which only differ by case can't be checked out on every platform,
+// so these are written out by hand
+const CLASHING: Dir<'_> = dir(
+    "",
+    &[file("README.TXT", b""), file("readme.txt", b"")],
+    &[
+        dir("Textures", &[file("Textures/wall.png", b"")], &[]),
+        dir(
+            "textures",
+            &[
+                file("textures/floor.png", b""),
+                file("textures/wall.png", b""),
+            ],
+            &[],
+        ),
+    ],
+);
+
+#[test]
+fn case_sensitive_by_default() {
+    assert!(CASE.get_file("textures/wall.png").is_none());
+    assert!(CASE.get_file("Textures/Wall.PNG").is_some());
+}
+
+#[test]
+fn ignore_case_lookups() {
+    let wall = CASE.get_file_ignore_case("textures/wall.png").unwrap();
+    assert_eq!(wall.path(), Path::new("Textures/Wall.PNG"));
+
+    let umlaut = CASE.get_file_ignore_case("äNDERUNGEN.MD").unwrap();
+    assert_eq!(umlaut.path(), Path::new("Änderungen.md"));
+
+    let textures = CASE.get_dir_ignore_case("TEXTURES/").unwrap();
+    assert_eq!(textures.path(), Path::new("Textures"));
+
+    assert!(CASE.get_file_ignore_case("textures").is_none());
+    assert!(CASE.get_dir_ignore_case("textures/wall.png").is_none());
+    assert!(CASE.get_file_ignore_case("textures/missing.png").is_none());
+}
+
+#[test]
+fn first_match_in_sorted_order_wins() {
+    let readme = CLASHING.get_file_ignore_case("ReadMe.txt").unwrap();
+    assert_eq!(readme.path(), Path::new("README.TXT"));
+
+    let wall = CLASHING.get_file_ignore_case("TEXTURES/WALL.PNG").unwrap();
+    assert_eq!(wall.path(), Path::new("Textures/wall.png"));
+
+    let textures = CLASHING.get_dir_ignore_case("TEXTURES").unwrap();
+    assert_eq!(textures.path(), Path::new("Textures"));
+}
+
+#[test]
+fn every_matching_directory_is_searched() {
+    let floor = CLASHING.get_file_ignore_case("TEXTURES/FLOOR.PNG").unwrap();
+    assert_eq!(floor.path(), Path::new("textures/floor.png"));
+}
+
+#[test]
+fn names_are_case_folded_not_just_lowercased() {
+    const FOLDED: Dir<'_> = dir(
+        "",
+        &[
+            file("straße.txt", b""),
+            file("σοφος.md", b""),
+            file("ﬁle.txt", b""),
+        ],
+        &[],
+    );
+
+    let street = FOLDED.get_file_ignore_case("STRASSE.TXT").unwrap();
+    assert_eq!(street.path(), Path::new("straße.txt"));
+    assert!(FOLDED.get_file_ignore_case("Strasse.txt").is_some());
+
+    // the final sigma folds to the same thing as the others
+    let wise = FOLDED.get_file_ignore_case("ΣΟΦΟΣ.MD").unwrap();
+    assert_eq!(wise.path(), Path::new("σοφος.md"));
+    assert!(FOLDED.get_file_ignore_case("σοφοσ.md").is_some());
+
+    // ligatures fold to their letters
+    assert!(FOLDED.get_file_ignore_case("FILE.TXT").is_some());
+
+    assert!(FOLDED.get_file_ignore_case("strase.txt").is_none());
+    assert!(FOLDED.get_file_ignore_case("σοφο.md").is_none());
+}
+
+#[test]
+fn extensions_are_case_folded_too() {
+    const FILES: Dir<'_> = dir("", &[file("notes.ΣΣ", b"")], &[]);
+
+    assert_eq!(FILES.files_with_extension("σς").count(), 1);
+}
@@ -0,0 +1,46 @@
+use include_dir::{include_dir, include_dir_path, Dir};
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn expands_to_the_stored_path() {
+    const README: &str = include_dir_path!("tests/fixtures/tree", "migrations/README.md");
+
+    assert_eq!(README, "migrations/README.md");
+    assert!(TREE.get_file(README).is_some());
+}
+
+#[test]
+fn the_path_is_normalised() {
+    assert_eq!(
+        include_dir_path!("tests/fixtures/tree", "./migrations//README.md/"),
+        "migrations/README.md"
+    );
+    assert_eq!(
+        include_dir_path!("tests/fixtures/tree", "migrations\\README.md"),
+        "migrations/README.md"
+    );
+}
+
+#[test]
+fn directories_can_be_named() {
+    const MIGRATIONS: &str = include_dir_path!("tests/fixtures/tree", "migrations/2023");
+
+    assert!(TREE.get_dir(MIGRATIONS).is_some());
+}
+
+#[test]
+fn the_prefix_is_added() {
+    const PREFIXED: Dir<'_> = include_dir!("tests/fixtures/tree", prefix = "static");
+    const TOP: &str = include_dir_path!("tests/fixtures/tree", "top.rs", prefix = "static");
+
+    assert_eq!(TOP, "static/top.rs");
+    assert!(PREFIXED.get_file(TOP).is_some());
+}
+
+#[test]
+fn paths_resolve_like_include_dir() {
+    const TOP: &str = include_dir_path!("fixtures/tree", "top.rs", relative_to = "file");
+
+    assert_eq!(TOP, "top.rs");
+}
@@ -0,0 +1,33 @@
+use include_dir::{include_dir, Dir};
+
+const DEFAULT: Dir<'_> = include_dir!("tests/fixtures/sizes");
+const NEVER_INLINED: Dir<'_> = include_dir!("tests/fixtures/sizes", inline_threshold = 0);
+const ALWAYS_INLINED: Dir<'_> = include_dir!("tests/fixtures/sizes", inline_threshold = "4MB");
+
+fn check(dir: Dir<'_>) {
+    let tiny = dir.get_file("tiny.txt").unwrap();
+    assert_eq!(tiny.contents(), b"tiny file\n");
+
+    let large = dir.get_file("large.txt").unwrap();
+    assert_eq!(large.len(), 2 * 1024 * 1024);
+    assert!(large.contents().starts_with(b"0000000\n0000001\n"));
+    assert!(large.contents().ends_with(b"0262143\n"));
+}
+
+#[test]
+fn small_and_large_files_load_with_the_default_threshold() {
+    check(DEFAULT);
+}
+
+#[test]
+fn the_threshold_does_not_change_the_contents() {
+    check(NEVER_INLINED);
+    check(ALWAYS_INLINED);
+
+    for dir in [NEVER_INLINED, ALWAYS_INLINED] {
+        for name in ["tiny.txt", "large.txt"] {
+            let expected = DEFAULT.get_file(name).unwrap().contents();
+            assert_eq!(dir.get_file(name).unwrap().contents(), expected);
+        }
+    }
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "std")]
+
 use include_dir::{include_dir, Dir};
 use std::path::Path;
 use tempdir::TempDir;
@@ -50,9 +52,9 @@ fn validate_included(dir: Dir<'_>, path: &Path, root: &Path) {
 fn validate_extracted(dir: Dir, path: &Path) {
     // Check if all the subdirectories exist, recursing on each
     for subdir in dir.dirs() {
-        let subdir_path = path.join(dir.path());
+        let subdir_path = path.join(subdir.path());
         assert!(subdir_path.exists());
-        validate_extracted(*subdir, &subdir_path);
+        validate_extracted(*subdir, path);
     }
 
     // Check if the files at the root of this directory exist
@@ -0,0 +1,36 @@
+use include_dir::{include_dir, Dir};
+use std::fs;
+use std::path::Path;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+/// Paths are stored in a table separate from the contents, so make sure
+/// every entry still gets back its own path and bytes.
+#[test]
+fn first_middle_and_last_entries_are_intact() {
+    let files: Vec<_> = TREE.walk().filter_map(|e| e.as_file()).collect();
+    let picks = [0, files.len() / 2, files.len() - 1];
+
+    for &ix in &picks {
+        let file = files[ix];
+        let on_disk = Path::new("tests/fixtures/tree").join(file.path());
+        assert_eq!(
+            file.contents(),
+            &fs::read(on_disk).unwrap()[..],
+            "{}",
+            file.path
+        );
+        assert_eq!(TREE.get_file(file.path).unwrap(), file);
+    }
+
+    assert_eq!(files[0].path, "README.md");
+    assert_eq!(files[picks[1]].path, "migrations/2023/q1/002_titles.sql");
+    assert_eq!(files[picks[2]].path, "top.rs");
+
+    let dirs: Vec<_> = TREE.dir_paths().collect();
+    assert_eq!(
+        dirs,
+        ["migrations", "migrations/2023", "migrations/2023/q1"]
+    );
+    assert_eq!(TREE.path, "");
+}
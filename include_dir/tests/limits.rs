@@ -0,0 +1,27 @@
+use include_dir::{include_dir, Dir};
+
+#[test]
+fn directories_within_the_limits_are_embedded() {
+    const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+    const LIMITED: Dir<'_> = include_dir!(
+        "tests/fixtures/tree",
+        max_file_size = "1KB",
+        max_total_size = 4096,
+        max_depth = 3,
+    );
+
+    assert_eq!(LIMITED.file_count(), TREE.file_count());
+    assert!(LIMITED.contains("migrations/2023/q1/002_titles.sql"));
+}
+
+#[test]
+fn excluded_files_dont_count_towards_the_limits() {
+    const SMALL: Dir<'_> = include_dir!(
+        "tests/fixtures/compression",
+        exclude = ["big.html"],
+        max_file_size = "2.5 KiB",
+    );
+
+    assert!(SMALL.contains("noise.bin"));
+    assert!(!SMALL.contains("big.html"));
+}
@@ -0,0 +1,275 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::{dir, file};
+use include_dir::{include_dir, Dir, DirEntry, EntryKind, LookupError};
+use std::path::Path;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn lookups_distinguish_files_and_dirs() {
+    assert!(TREE.get_file("migrations/2023").is_none());
+    assert!(TREE.get_dir("migrations/2023").is_some());
+    assert!(TREE.get_dir("migrations/2023/001_posts.sql").is_none());
+    assert!(TREE.get_file("migrations/2023/001_posts.sql").is_some());
+}
+
+#[test]
+fn lookups_need_whole_components() {
+    assert!(!TREE.contains("migrations/202"));
+    assert!(!TREE.contains("migrations/2023/001_posts"));
+    assert!(!TREE.contains("migrations/2023/001_posts.sql/extra"));
+}
+
+#[test]
+fn lookups_from_a_sub_directory_use_full_paths() {
+    let migrations = TREE.get_dir("migrations").unwrap();
+
+    assert!(migrations
+        .get_file("migrations/2023/q1/002_titles.sql")
+        .is_some());
+    assert!(migrations.get_file("2023/q1/002_titles.sql").is_none());
+    assert!(migrations.get_file("top.rs").is_none());
+    assert!(migrations.get_dir("migrations").is_none());
+}
+
+#[test]
+fn stored_paths_always_use_forward_slashes() {
+    for entry in TREE.walk() {
+        let path = entry.path().to_str().unwrap();
+        assert!(!path.contains('\\'), "{}", path);
+    }
+}
+
+#[test]
+fn lookups_accept_either_separator() {
+    assert!(TREE
+        .get_file("migrations\\2023\\q1\\002_titles.sql")
+        .is_some());
+    assert!(TREE
+        .get_file("migrations/2023\\q1/002_titles.sql")
+        .is_some());
+    assert!(TREE.get_dir("migrations\\2023").is_some());
+
+    let joined = Path::new("migrations").join("2023").join("001_posts.sql");
+    assert!(TREE.get_file(joined).is_some());
+}
+
+#[test]
+fn backslash_separated_stored_paths_still_match() {
+    const WINDOWS: Dir<'_> = dir(
+        "",
+        &[],
+        &[dir("assets", &[file("assets\\logo.png", b"")], &[])],
+    );
+
+    assert!(WINDOWS.get_file("assets/logo.png").is_some());
+    assert!(WINDOWS.get_file("assets\\logo.png").is_some());
+}
+
+#[test]
+fn lookups_ignore_trivial_decorations() {
+    assert!(TREE.get_dir("migrations/").is_some());
+    assert!(TREE.get_dir("migrations/2023//q1/").is_some());
+    assert!(TREE.get_file("./top.rs").is_some());
+    assert!(TREE.get_file("./migrations/./2023/001_posts.sql").is_some());
+    assert!(TREE
+        .get_file(".\\migrations\\.\\2023\\001_posts.sql")
+        .is_some());
+    assert!(TREE.contains("migrations//README.md"));
+}
+
+#[test]
+fn lookups_reject_parent_directories_and_absolute_paths() {
+    assert!(TREE.get_file("migrations/../top.rs").is_none());
+    assert!(TREE.get_file("migrations\\..\\top.rs").is_none());
+    assert!(TREE.get_dir("migrations/2023/..").is_none());
+    assert!(TREE.get_file("/top.rs").is_none());
+    assert!(TREE.get_file("\\top.rs").is_none());
+}
+
+#[test]
+fn get_entry_returns_either_kind() {
+    match TREE.get_entry("migrations/2023") {
+        Some(DirEntry::Dir(d)) => assert_eq!(d.path(), Path::new("migrations/2023")),
+        other => panic!("expected a directory, got {:?}", other),
+    }
+    match TREE.get_entry("./migrations\\README.md") {
+        Some(DirEntry::File(f)) => assert_eq!(f.path(), Path::new("migrations/README.md")),
+        other => panic!("expected a file, got {:?}", other),
+    }
+    assert!(TREE.get_entry("migrations/missing").is_none());
+    assert!(TREE.get_entry("").is_none());
+}
+
+#[test]
+fn ref_lookups_borrow_from_the_embedded_tree() {
+    static ROOT: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+    let migrations = ROOT.get_dir_ref("migrations").unwrap();
+    assert!(std::ptr::eq(migrations, &ROOT.dirs()[0]));
+
+    let readme = ROOT.get_file_ref("README.md").unwrap();
+    assert_eq!(Some(*readme), ROOT.get_file("README.md"));
+    assert!(ROOT.get_file_ref("migrations").is_none());
+    assert!(ROOT.get_dir_ref("README.md").is_none());
+}
+
+#[test]
+fn every_lookup_agrees_on_what_a_path_refers_to() {
+    let paths = [
+        "migrations",
+        "migrations/",
+        "./migrations\\2023",
+        "migrations//2023/001_posts.sql",
+        "migrations/./README.md",
+        "top.rs",
+        "migrations/../top.rs",
+        "/top.rs",
+        "migrations/202",
+        "top.rs/extra",
+        "",
+    ];
+
+    for path in paths.iter() {
+        let kind = TREE.entry_kind(path);
+
+        assert_eq!(kind.is_some(), TREE.contains(path), "{}", path);
+        assert_eq!(
+            kind == Some(EntryKind::File),
+            TREE.get_file(path).is_some(),
+            "{}",
+            path
+        );
+        assert_eq!(
+            kind == Some(EntryKind::Dir),
+            TREE.get_dir(path).is_some(),
+            "{}",
+            path
+        );
+        assert_eq!(kind, TREE.get_entry(path).map(|e| e.kind()), "{}", path);
+    }
+}
+
+#[test]
+fn try_lookups_explain_what_went_wrong() {
+    let file = TREE.try_get_file("migrations/README.md").unwrap();
+    assert_eq!(Some(file), TREE.get_file("migrations/README.md"));
+    let dir = TREE.try_get_dir("migrations/2023").unwrap();
+    assert_eq!(Some(dir), TREE.get_dir("migrations/2023"));
+    let entry = TREE.try_get_entry("top.rs").unwrap();
+    assert_eq!(Some(entry), TREE.get_entry("top.rs"));
+
+    assert_eq!(TREE.try_get_file("migrations"), Err(LookupError::NotAFile));
+    assert_eq!(TREE.try_get_dir("top.rs"), Err(LookupError::NotADir));
+    assert_eq!(TREE.try_get_file("missing.rs"), Err(LookupError::NotFound));
+    assert_eq!(TREE.try_get_dir("missing"), Err(LookupError::NotFound));
+    assert_eq!(TREE.try_get_entry("missing"), Err(LookupError::NotFound));
+
+    for invalid in ["/top.rs", "../top.rs", "migrations/../top.rs"] {
+        let err = TREE.try_get_file(invalid).unwrap_err();
+        assert!(
+            matches!(err, LookupError::InvalidPath { .. }),
+            "{}: {:?}",
+            invalid,
+            err
+        );
+        assert_eq!(TREE.get_file(invalid), None);
+    }
+}
+
+#[test]
+fn lookup_errors_can_be_displayed() {
+    let err = TREE.try_get_file("../top.rs").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid path, embedded paths never contain \"..\""
+    );
+    assert_eq!(
+        LookupError::NotAFile.to_string(),
+        "The path is a directory, not a file"
+    );
+
+    let err: Box<dyn std::error::Error> = Box::new(LookupError::NotFound);
+    assert_eq!(err.to_string(), "Nothing is embedded at that path");
+}
+
+#[test]
+fn lookups_accept_os_strings() {
+    use std::ffi::{OsStr, OsString};
+    use std::path::PathBuf;
+
+    assert!(TREE.get_file(OsStr::new("top.rs")).is_some());
+    assert!(TREE
+        .get_file(OsString::from("migrations/README.md"))
+        .is_some());
+    assert!(TREE
+        .get_file(OsString::from(".\\migrations\\README.md"))
+        .is_some());
+    assert!(TREE.get_dir(OsStr::new("./migrations//2023/")).is_some());
+    assert!(TREE.contains(PathBuf::from(OsString::from("migrations")).join("2023")));
+
+    assert!(!TREE.contains(OsStr::new("migrations/missing.sql")));
+    assert_eq!(
+        TREE.try_get_file(OsString::from("migrations/../top.rs")),
+        Err(LookupError::InvalidPath {
+            reason: "embedded paths never contain \"..\""
+        })
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn non_utf8_unix_paths_never_match() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = OsStr::from_bytes(b"migrations/caf\xe9.sql");
+    assert_eq!(TREE.get_file(path), None);
+    assert_eq!(TREE.get_entry(path), None);
+    assert!(!TREE.contains(path));
+    assert_eq!(
+        TREE.try_get_file(path),
+        Err(LookupError::InvalidPath {
+            reason: "embedded paths are always valid UTF-8"
+        })
+    );
+
+    // the rest of the path is still checked
+    let path = OsStr::from_bytes(b"\xff/../top.rs");
+    assert_eq!(
+        TREE.try_get_file(path),
+        Err(LookupError::InvalidPath {
+            reason: "embedded paths never contain \"..\""
+        })
+    );
+}
+
+#[test]
+#[cfg(windows)]
+fn non_unicode_windows_paths_never_match() {
+    use std::ffi::OsString;
+    use std::os::windows::ffi::OsStringExt;
+
+    // "top" followed by an unpaired surrogate
+    let path = OsString::from_wide(&[0x74, 0x6f, 0x70, 0xd800]);
+    assert_eq!(TREE.get_file(&path), None);
+    assert!(!TREE.contains(&path));
+    assert_eq!(
+        TREE.try_get_file(&path),
+        Err(LookupError::InvalidPath {
+            reason: "embedded paths are always valid UTF-8"
+        })
+    );
+
+    let mut path = OsString::from_wide(&[0xd800]);
+    path.push("\\..\\top.rs");
+    assert_eq!(
+        TREE.try_get_file(&path),
+        Err(LookupError::InvalidPath {
+            reason: "embedded paths never contain \"..\""
+        })
+    );
+}
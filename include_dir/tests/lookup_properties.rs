@@ -0,0 +1,193 @@
+//! Randomised checks that lookups only ever find the entry a path names.
+//!
+//! Trees are built from a small alphabet of names chosen so that many of
+//! them are prefixes of each other, or sort right next to each other, which
+//! is where an off-by-one in the binary search or a partial component match
+//! would show up.
+
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, Dir, DirBuilder, DirEntry, EntryKind, OwnedDir};
+use std::collections::HashSet;
+
+const ORDERING: Dir<'_> = include_dir!("tests/fixtures/ordering");
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+const NAMES: &[&str] = &[
+    "a", "a.", "a.b", "a..b", "a-b", "a b", "a_b", "ab", "abc", "b", "A", "_", ".a", "...", "10",
+    "9", "ä", "äb",
+];
+
+/// A small xorshift generator, so failures are reproducible from the seed.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn name(&mut self) -> &'static str {
+        NAMES[self.below(NAMES.len())]
+    }
+
+    fn path(&mut self, max_depth: usize) -> String {
+        let depth = 1 + self.below(max_depth);
+        (0..depth)
+            .map(|_| self.name())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+fn random_tree(rng: &mut Rng) -> OwnedDir<'static> {
+    let mut builder = DirBuilder::new();
+
+    for _ in 0..1 + rng.below(30) {
+        let path = rng.path(4);
+        // anything going through an existing file is rejected, which is fine
+        let _ = if rng.below(4) == 0 {
+            builder.add_dir(&path).map(|_| ())
+        } else {
+            builder
+                .add_file(&path, path.clone().into_bytes())
+                .map(|_| ())
+        };
+    }
+
+    builder.build()
+}
+
+/// The ways a caller might spell a stored path, which should all find it.
+fn spellings(stored: &str) -> Vec<String> {
+    vec![
+        stored.to_string(),
+        format!("./{}", stored),
+        format!("{}/", stored),
+        stored.replace('/', "//"),
+        stored.replace('/', "\\"),
+        stored.replace('/', "/./"),
+    ]
+}
+
+/// The stored path a lookup should find, ignoring empty and `.` components.
+fn normalise(path: &str) -> String {
+    path.split('/')
+        .filter(|name| !name.is_empty() && *name != ".")
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn check_stored_paths(dir: Dir<'_>) {
+    for entry in dir.walk() {
+        let stored = entry.path().to_str().unwrap().to_string();
+
+        for spelling in spellings(&stored) {
+            let found = dir
+                .get_entry(&spelling)
+                .unwrap_or_else(|| panic!("{:?} wasn't found as {:?}", stored, spelling));
+            assert_eq!(found.path(), entry.path(), "{:?}", spelling);
+
+            let is_file = matches!(entry, DirEntry::File(_));
+            assert_eq!(dir.get_file(&spelling).is_some(), is_file, "{:?}", spelling);
+            assert_eq!(dir.get_dir(&spelling).is_some(), !is_file, "{:?}", spelling);
+            assert_eq!(
+                dir.entry_kind(&spelling),
+                Some(if is_file {
+                    EntryKind::File
+                } else {
+                    EntryKind::Dir
+                })
+            );
+            assert!(dir.contains(&spelling));
+            assert!(dir.contains_const(&spelling), "{:?}", spelling);
+
+            // several entries can match when ignoring case, but they all
+            // have to be spelled the same apart from case
+            let ignoring_case = if is_file {
+                dir.get_file_ignore_case(&spelling).map(|f| f.path())
+            } else {
+                dir.get_dir_ignore_case(&spelling).map(|d| d.path())
+            };
+            let ignoring_case = ignoring_case.unwrap().to_str().unwrap().to_lowercase();
+            assert_eq!(ignoring_case, stored.to_lowercase(), "{:?}", spelling);
+        }
+    }
+}
+
+fn check_other_paths(dir: Dir<'_>, rng: &mut Rng) {
+    let stored: HashSet<String> = dir
+        .walk()
+        .map(|e| e.path().to_str().unwrap().to_string())
+        .collect();
+    let mut candidates: Vec<String> = (0..50).map(|_| rng.path(5)).collect();
+
+    // chop a character off (or add one to) the last component of every
+    // stored path, so lookups land right next to real entries
+    for path in &stored {
+        let mut shorter = path.clone();
+        shorter.pop();
+        candidates.push(shorter);
+        candidates.push(format!("{}b", path));
+        candidates.push(format!("{}.", path));
+    }
+
+    for path in candidates {
+        let entry = dir.get_entry(&path);
+
+        assert_eq!(dir.contains(&path), entry.is_some(), "{:?}", path);
+        assert_eq!(dir.contains_const(&path), entry.is_some(), "{:?}", path);
+        assert_eq!(
+            dir.entry_kind(&path).is_some(),
+            entry.is_some(),
+            "{:?}",
+            path
+        );
+        assert_eq!(
+            dir.get_file(&path).is_some() || dir.get_dir(&path).is_some(),
+            entry.is_some(),
+            "{:?}",
+            path
+        );
+
+        // whatever was found has to be exactly what was asked for
+        let normalised = normalise(&path);
+        match entry {
+            Some(entry) => assert_eq!(entry.path().to_str(), Some(normalised.as_str())),
+            None => assert!(!stored.contains(&normalised), "{:?}", path),
+        }
+    }
+}
+
+#[test]
+fn random_trees_find_exactly_what_they_store() {
+    for seed in 1..=50u64 {
+        let mut rng = Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        let owned = random_tree(&mut rng);
+        let dir = owned.as_dir();
+
+        check_stored_paths(dir);
+        check_other_paths(dir, &mut rng);
+
+        for sub in dir.walk().filter_map(|e| e.as_dir()) {
+            check_stored_paths(sub);
+            check_other_paths(sub, &mut rng);
+        }
+    }
+}
+
+#[test]
+fn embedded_trees_find_exactly_what_they_store() {
+    let mut rng = Rng(42);
+
+    for dir in [ORDERING, TREE] {
+        check_stored_paths(dir);
+        check_other_paths(dir, &mut rng);
+    }
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, Dir, MergedEntry};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempdir::TempDir;
+
+const BASE: Dir<'_> = include_dir!("tests/fixtures/merge/base");
+const OVERLAY: Dir<'_> = include_dir!("tests/fixtures/merge/overlay");
+
+#[test]
+fn overlay_files_shadow_the_base() {
+    let merged = BASE.merge(&OVERLAY);
+
+    let readme = merged.get_file("README.md").unwrap();
+    assert_eq!(readme.contents_utf8(), Some("overlay readme\n"));
+    let main = merged.get_file("css/main.css").unwrap();
+    assert_eq!(main.contents_utf8(), Some("body { color: red }\n"));
+}
+
+#[test]
+fn lookups_fall_back_to_the_base() {
+    let merged = BASE.merge(&OVERLAY);
+
+    assert!(merged.contains("css/reset.css"));
+    assert!(merged.contains("theme/dark.css"));
+    assert!(merged.get_dir("css").is_some());
+    assert!(merged.get_file("missing.txt").is_none());
+}
+
+#[test]
+fn an_overlay_file_hides_a_base_directory() {
+    let merged = BASE.merge(&OVERLAY);
+
+    assert!(merged.get_file("assets").is_some());
+    assert!(merged.get_dir("assets").is_none());
+    assert!(!merged.contains("assets/logo.txt"));
+}
+
+#[test]
+fn walking_yields_the_union() {
+    let merged = BASE.merge(&OVERLAY);
+
+    let paths: Vec<_> = merged.walk().map(|e| e.path().to_path_buf()).collect();
+
+    let expected: Vec<PathBuf> = [
+        "README.md",
+        "assets",
+        "css",
+        "css/main.css",
+        "css/reset.css",
+        "theme",
+        "theme/dark.css",
+    ]
+    .iter()
+    .map(PathBuf::from)
+    .collect();
+    assert_eq!(paths, expected);
+    assert!(matches!(
+        merged.walk().nth(1),
+        Some(MergedEntry::File(f)) if f.path() == Path::new("assets")
+    ));
+    assert_eq!(merged.get_dir("css").unwrap().files().len(), 2);
+}
+
+#[test]
+fn extract_writes_the_union() {
+    let tmp = TempDir::new("merge-extract").unwrap();
+
+    let report = BASE
+        .merge(&OVERLAY)
+        .extract_with(tmp.path(), &Default::default())
+        .unwrap();
+
+    assert_eq!(report.created_files().len(), 5);
+    assert_eq!(
+        fs::read_to_string(tmp.path().join("css/main.css")).unwrap(),
+        "body { color: red }\n"
+    );
+    assert!(tmp.path().join("css/reset.css").is_file());
+    assert!(tmp.path().join("assets").is_file());
+}
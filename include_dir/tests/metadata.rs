@@ -0,0 +1,44 @@
+#![cfg(feature = "metadata")]
+
+use include_dir::{include_dir, Dir};
+use std::env;
+use std::fs;
+use std::time::{Duration, UNIX_EPOCH};
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn every_file_has_metadata() {
+    let root = env!("CARGO_MANIFEST_DIR");
+
+    for file in TREE.walk().filter_map(|e| e.as_file()) {
+        let metadata = file.metadata().unwrap();
+        let on_disk = fs::metadata(format!(
+            "{}/tests/fixtures/tree/{}",
+            root,
+            file.path().display()
+        ))
+        .unwrap();
+
+        assert_eq!(metadata.len(), file.contents().len() as u64);
+        assert_eq!(metadata.len(), on_disk.len());
+
+        if env::var_os("SOURCE_DATE_EPOCH").is_none() {
+            let secs = on_disk
+                .modified()
+                .unwrap()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            assert_eq!(metadata.modified(), UNIX_EPOCH + Duration::from_secs(secs));
+        }
+    }
+}
+
+#[cfg(not(feature = "permissions"))]
+#[test]
+fn modes_are_only_recorded_with_the_permissions_feature() {
+    for file in TREE.walk().filter_map(|e| e.as_file()) {
+        assert_eq!(file.metadata().unwrap().mode(), None);
+    }
+}
@@ -0,0 +1,72 @@
+use include_dir::{include_dir, Dir};
+use std::path::Path;
+
+const EN: Dir<'_> = include_dir!("tests/fixtures/locales/common", "tests/fixtures/locales/en");
+const DE: Dir<'_> = include_dir!(
+    "tests/fixtures/locales/common",
+    "tests/fixtures/locales/de",
+    duplicates = "override",
+);
+
+#[test]
+fn directories_are_merged_into_one_tree() {
+    let paths: Vec<_> = EN.walk().map(|e| e.path()).collect();
+
+    assert_eq!(
+        paths,
+        vec![
+            Path::new("fallback.ftl"),
+            Path::new("greeting.ftl"),
+            Path::new("shared"),
+            Path::new("shared/logo.svg"),
+            Path::new("shared/units.ftl"),
+        ]
+    );
+    assert_eq!(
+        EN.get_file("greeting.ftl").unwrap().contents_utf8(),
+        Some("greeting = Hello\n")
+    );
+}
+
+#[test]
+fn later_directories_can_override_earlier_ones() {
+    let names: Vec<_> = DE.files().iter().map(|f| f.file_name()).collect();
+    assert_eq!(names, vec!["fallback.ftl", "greeting.ftl"]);
+
+    assert_eq!(
+        DE.get_file("fallback.ftl").unwrap().contents_utf8(),
+        Some("fallback = Rückfall\n")
+    );
+    assert!(DE.contains("shared/logo.svg"));
+}
+
+#[test]
+fn options_apply_to_every_directory() {
+    const PREFIXED: Dir<'_> = include_dir!(
+        "tests/fixtures/locales/common",
+        "tests/fixtures/locales/en",
+        prefix = "locales",
+        exclude = ["**/*.svg"],
+    );
+
+    assert!(PREFIXED.contains("locales/fallback.ftl"));
+    assert!(PREFIXED.contains("locales/shared/units.ftl"));
+    assert!(!PREFIXED.contains("locales/shared/logo.svg"));
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn merged_trees_are_hashed_as_a_whole() {
+    EN.verify().unwrap();
+    DE.verify().unwrap();
+    assert_ne!(EN.hash(), DE.hash());
+}
+
+#[cfg(feature = "hot-reload")]
+#[test]
+fn merged_trees_fall_back_to_the_embedded_files() {
+    assert_eq!(
+        EN.read_file("shared/logo.svg").unwrap(),
+        EN.get_file("shared/logo.svg").unwrap().contents()
+    );
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, Dir};
+
+const ORDERING: Dir<'_> = include_dir!("tests/fixtures/ordering");
+
+#[test]
+fn files_are_sorted_by_the_bytes_of_their_names() {
+    let files = ORDERING
+        .files()
+        .iter()
+        .map(|f| f.file_name())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        files,
+        [
+            "10.txt",
+            "9.txt",
+            "B.txt",
+            "_x.txt",
+            "a.txt",
+            "item-2.txt",
+            "item_10.txt"
+        ]
+    );
+}
+
+#[test]
+fn dirs_are_sorted_too() {
+    let dirs = ORDERING
+        .dirs()
+        .iter()
+        .map(|d| d.file_name())
+        .collect::<Vec<_>>();
+    assert_eq!(dirs, ["Zeta", "alpha"]);
+
+    let zeta = ORDERING.get_dir("Zeta").unwrap();
+    let files = zeta
+        .files()
+        .iter()
+        .map(|f| f.file_name())
+        .collect::<Vec<_>>();
+    assert_eq!(files, ["10.txt", "2.txt"]);
+
+    let alpha = ORDERING.get_dir("alpha").unwrap();
+    let files = alpha
+        .files()
+        .iter()
+        .map(|f| f.file_name())
+        .collect::<Vec<_>>();
+    assert_eq!(files, ["Beta.txt", "b.txt"]);
+}
+
+#[test]
+fn walking_follows_the_same_order() {
+    let paths: Vec<_> = ORDERING
+        .walk()
+        .map(|e| e.path().to_str().unwrap().to_string())
+        .collect();
+
+    assert_eq!(
+        paths,
+        [
+            "10.txt",
+            "9.txt",
+            "B.txt",
+            "Zeta",
+            "Zeta/10.txt",
+            "Zeta/2.txt",
+            "_x.txt",
+            "a.txt",
+            "alpha",
+            "alpha/Beta.txt",
+            "alpha/b.txt",
+            "item-2.txt",
+            "item_10.txt"
+        ]
+    );
+}
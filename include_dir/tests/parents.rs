@@ -0,0 +1,60 @@
+use include_dir::{include_dir, Dir};
+use std::path::Path;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+const PREFIXED: Dir<'_> = include_dir!("tests/fixtures/tree", prefix = "assets");
+
+#[test]
+fn top_level_entries_belong_to_the_root() {
+    let parent = TREE.parent_of("top.rs").unwrap();
+    assert_eq!(parent, TREE);
+
+    let siblings: Vec<_> = TREE
+        .siblings_of("top.rs")
+        .unwrap()
+        .map(|e| e.path())
+        .collect();
+    assert_eq!(siblings, [Path::new("README.md"), Path::new("migrations")]);
+
+    // a sub-directory's direct children belong to it, not the root
+    let migrations = TREE.get_dir("migrations").unwrap();
+    let parent = migrations.parent_of("migrations/README.md").unwrap();
+    assert_eq!(parent, migrations);
+
+    let parent = PREFIXED.parent_of("assets/top.rs").unwrap();
+    assert_eq!(parent, PREFIXED);
+}
+
+#[test]
+fn nested_entries_belong_to_their_directory() {
+    let path = "migrations/2023/q1/002_titles.sql";
+    let parent = TREE.parent_of(path).unwrap();
+    assert_eq!(parent.path(), Path::new("migrations/2023/q1"));
+    assert_eq!(parent, TREE.get_dir("migrations/2023/q1").unwrap());
+    assert_eq!(TREE.siblings_of(path).unwrap().count(), 0);
+
+    // directories work too, and paths are normalised like the getters
+    let parent = TREE.parent_of("./migrations\\2023/").unwrap();
+    assert_eq!(parent.path(), Path::new("migrations"));
+
+    let siblings: Vec<_> = TREE
+        .siblings_of("migrations/2023")
+        .unwrap()
+        .map(|e| e.path())
+        .collect();
+    assert_eq!(
+        siblings,
+        [
+            Path::new("migrations/0000_bootstrap.sql"),
+            Path::new("migrations/README.md"),
+        ]
+    );
+}
+
+#[test]
+fn missing_paths_have_no_parent() {
+    assert!(TREE.parent_of("migrations/missing.sql").is_none());
+    assert!(TREE.siblings_of("migrations/missing.sql").is_none());
+    assert!(TREE.parent_of("").is_none());
+    assert!(TREE.parent_of("/top.rs").is_none());
+}
@@ -0,0 +1,30 @@
+#![cfg(all(unix, feature = "permissions"))]
+
+use include_dir::{include_dir, Dir};
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use tempdir::TempDir;
+
+const SCRIPTS: Dir<'_> = include_dir!("tests/fixtures/permissions");
+
+fn mode_of(path: &str) -> Option<u32> {
+    SCRIPTS.get_file(path)?.metadata()?.mode()
+}
+
+#[test]
+fn modes_are_recorded() {
+    assert_eq!(mode_of("run.sh"), Some(0o755));
+    assert_eq!(mode_of("config.toml"), Some(0o644));
+}
+
+#[test]
+fn extracted_files_get_their_original_permissions() {
+    let tmp = TempDir::new("extract-permissions").unwrap();
+
+    SCRIPTS.extract(tmp.path()).unwrap();
+
+    let script = fs::metadata(tmp.path().join("run.sh")).unwrap();
+    assert_eq!(script.permissions().mode() & 0o777, 0o755);
+    let config = fs::metadata(tmp.path().join("config.toml")).unwrap();
+    assert_eq!(config.permissions().mode() & 0o777, 0o644);
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "precache")]
+
+use include_dir::{include_dir, Dir};
+use serde_json::Value;
+
+const SITE: Dir<'_> = include_dir!("tests/fixtures/precache");
+const PREFIXED: Dir<'_> = include_dir!("tests/fixtures/precache", prefix = "public");
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[test]
+fn urls_are_joined_to_the_prefix() {
+    for prefix in ["/static", "/static/", "/static//"] {
+        let urls: Vec<_> = SITE
+            .precache_entries(prefix)
+            .into_iter()
+            .map(|e| e.url().to_string())
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                "/static/css/main.css",
+                "/static/img/hello%20world.png",
+                "/static/index.html",
+            ],
+            "{}",
+            prefix
+        );
+    }
+
+    let root: Vec<_> = SITE
+        .precache_entries("/")
+        .into_iter()
+        .map(|e| e.url().to_string())
+        .collect();
+    assert_eq!(root[0], "/css/main.css");
+    let bare: Vec<_> = SITE
+        .precache_entries("")
+        .into_iter()
+        .map(|e| e.url().to_string())
+        .collect();
+    assert_eq!(bare[0], "css/main.css");
+}
+
+#[test]
+fn urls_are_relative_to_the_directory() {
+    let entries = PREFIXED.precache_entries("https://cdn.example.com/");
+    assert_eq!(entries[0].url(), "https://cdn.example.com/css/main.css");
+
+    let css = SITE.get_dir("css").unwrap();
+    let entries = css.precache_entries("/css");
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].url(), "/css/main.css");
+}
+
+#[test]
+fn revisions_come_from_the_hash() {
+    for entry in SITE.precache_entries("/") {
+        let path = entry.url().trim_start_matches('/').replace("%20", " ");
+        let file = SITE.get_file(&path).unwrap();
+        assert_eq!(entry.revision(), hex(&file.hash()[..16]), "{}", path);
+    }
+
+    let entries = SITE.precache_entries("/");
+    assert_ne!(entries[0].revision(), entries[1].revision());
+}
+
+#[test]
+fn the_manifest_is_json() {
+    let manifest: Value = serde_json::from_str(&SITE.precache_manifest("/static")).unwrap();
+    let entries = SITE.precache_entries("/static");
+
+    let manifest = manifest.as_array().unwrap();
+    assert_eq!(manifest.len(), entries.len());
+    for (json, entry) in manifest.iter().zip(&entries) {
+        assert_eq!(json["url"], entry.url());
+        assert_eq!(json["revision"], entry.revision());
+    }
+
+    // quotes in the prefix have to be escaped
+    let manifest: Value = serde_json::from_str(&SITE.precache_manifest("/\"odd\"\\")).unwrap();
+    assert_eq!(manifest[0]["url"], "/\"odd\"\\/css/main.css");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn entries_serialize_like_the_manifest() {
+    let entries = SITE.precache_entries("/static/");
+    let serialized = serde_json::to_string(&entries).unwrap();
+
+    assert_eq!(serialized, SITE.precache_manifest("/static/"));
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, Dir, PrefixBehavior};
+use std::path::Path;
+use tempdir::TempDir;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+const PREFIXED: Dir<'_> = include_dir!("tests/fixtures/tree", prefix = "static/tree");
+
+#[test]
+fn paths_are_relative_to_the_included_directory_by_default() {
+    assert_eq!(TREE.path(), Path::new(""));
+    assert!(TREE.get_file("migrations/2023/001_posts.sql").is_some());
+}
+
+#[test]
+fn prefix_is_added_to_every_path() {
+    assert_eq!(PREFIXED.path(), Path::new("static/tree"));
+
+    for entry in PREFIXED.walk() {
+        assert!(entry.path().starts_with("static/tree"));
+    }
+}
+
+#[test]
+fn lookups_use_the_prefixed_paths() {
+    assert!(PREFIXED.get_file("static/tree/top.rs").is_some());
+    assert!(PREFIXED.get_file("top.rs").is_none());
+
+    let dir = PREFIXED.get_dir("static/tree/migrations/2023").unwrap();
+    assert_eq!(dir.path(), Path::new("static/tree/migrations/2023"));
+    assert!(dir
+        .get_file("static/tree/migrations/2023/q1/002_titles.sql")
+        .is_some());
+
+    assert!(PREFIXED.contains("static/tree/migrations"));
+    assert!(!PREFIXED.contains("migrations"));
+}
+
+#[test]
+fn extracting_can_keep_the_prefix() {
+    let tmp = TempDir::new("extract-prefixed").unwrap();
+
+    PREFIXED
+        .extract_into(tmp.path(), PrefixBehavior::KeepRoot)
+        .unwrap();
+
+    assert!(tmp.path().join("static/tree/top.rs").is_file());
+    assert!(tmp
+        .path()
+        .join("static/tree/migrations/2023/q1/002_titles.sql")
+        .is_file());
+}
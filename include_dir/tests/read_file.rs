@@ -0,0 +1,39 @@
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, Dir};
+
+const ASSETS: Dir<'_> = include_dir!("tests/fixtures/hot_reload");
+
+#[test]
+fn read_embedded_files() {
+    let style = ASSETS.read_file("css/style.css").unwrap();
+    assert_eq!(&*style, b"body { color: red; }\n");
+
+    assert!(ASSETS.read_file("css").is_none());
+    assert!(ASSETS.read_file("css/../css/style.css").is_none());
+}
+
+#[cfg(all(feature = "hot-reload", debug_assertions))]
+#[test]
+fn hot_reload_reads_from_the_source_directory() {
+    use std::fs;
+    use std::path::Path;
+
+    const PREFIXED: Dir<'_> = include_dir!("tests/fixtures/hot_reload", prefix = "assets");
+
+    let scratch =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/hot_reload/css/scratch.css");
+    fs::write(&scratch, "edited").unwrap();
+    let from_disk = ASSETS.read_file("css/scratch.css");
+    let prefixed = PREFIXED.read_file("assets/css/scratch.css");
+    let sub_dir = ASSETS.get_dir("css").unwrap().read_file("css/scratch.css");
+    fs::remove_file(&scratch).unwrap();
+
+    assert_eq!(from_disk.as_deref(), Some(&b"edited"[..]));
+    assert_eq!(prefixed.as_deref(), Some(&b"edited"[..]));
+    assert_eq!(sub_dir.as_deref(), Some(&b"edited"[..]));
+
+    // once it's gone we fall back to what was embedded, which is nothing
+    assert!(ASSETS.read_file("css/scratch.css").is_none());
+    assert!(ASSETS.get_file("css/scratch.css").is_none());
+}
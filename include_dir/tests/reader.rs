@@ -0,0 +1,68 @@
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, Dir};
+use std::io::{BufRead, ErrorKind, Read, Seek, SeekFrom};
+
+const TEXT: Dir<'_> = include_dir!("tests/fixtures/text");
+
+#[test]
+fn partial_reads() {
+    let mut reader = TEXT.get_file("plain.txt").unwrap().reader();
+    let mut buf = [0; 5];
+
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"plain");
+    assert_eq!(reader.position(), 5);
+
+    let mut rest = String::new();
+    reader.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, " ascii\n");
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn clones_read_independently() {
+    let mut reader = TEXT.get_file("plain.txt").unwrap().reader();
+    reader.seek(SeekFrom::Start(6)).unwrap();
+    let mut copy = reader.clone();
+
+    let mut line = String::new();
+    copy.read_line(&mut line).unwrap();
+    assert_eq!(line, "ascii\n");
+    assert_eq!(reader.position(), 6);
+}
+
+#[test]
+fn seek_from_end() {
+    let mut reader = TEXT.get_file("plain.txt").unwrap().reader();
+
+    assert_eq!(reader.seek(SeekFrom::End(-6)).unwrap(), 6);
+    let mut rest = String::new();
+    reader.read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, "ascii\n");
+
+    // seeking past the end is fine, there's just nothing left to read
+    assert_eq!(reader.seek(SeekFrom::End(10)).unwrap(), 22);
+    assert_eq!(reader.read(&mut [0; 4]).unwrap(), 0);
+    assert_eq!(reader.seek(SeekFrom::Current(-22)).unwrap(), 0);
+}
+
+#[test]
+fn seeking_before_the_start_is_an_error() {
+    let mut reader = TEXT.get_file("plain.txt").unwrap().reader();
+
+    let err = reader.seek(SeekFrom::End(-100)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(err.to_string().contains("plain.txt"));
+    assert_eq!(reader.position(), 0);
+}
+
+#[test]
+fn reading_an_empty_file() {
+    let mut reader = TEXT.get_file("empty.txt").unwrap().reader();
+
+    assert!(reader.fill_buf().unwrap().is_empty());
+    let mut contents = Vec::new();
+    assert_eq!(reader.read_to_end(&mut contents).unwrap(), 0);
+    assert_eq!(reader.seek(SeekFrom::End(0)).unwrap(), 0);
+}
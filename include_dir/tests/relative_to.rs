@@ -0,0 +1,72 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::dir;
+use include_dir::{include_dir, Dir};
+use std::path::Path;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn relative_to_the_direct_parent() {
+    let year = TREE.get_dir("migrations/2023").unwrap();
+    let posts = TREE.get_file("migrations/2023/001_posts.sql").unwrap();
+    let q1 = TREE.get_dir("migrations/2023/q1").unwrap();
+
+    assert_eq!(posts.relative_to(&year), Some(Path::new("001_posts.sql")));
+    assert_eq!(q1.relative_to(&year), Some(Path::new("q1")));
+}
+
+#[test]
+fn relative_to_a_distant_ancestor() {
+    let migrations = TREE.get_dir("migrations").unwrap();
+    let titles = TREE.get_file("migrations/2023/q1/002_titles.sql").unwrap();
+
+    assert_eq!(
+        titles.relative_to(&migrations),
+        Some(Path::new("2023/q1/002_titles.sql"))
+    );
+    assert_eq!(titles.relative_to(&TREE), Some(titles.path()));
+}
+
+#[test]
+fn entries_outside_the_directory() {
+    let migrations = TREE.get_dir("migrations").unwrap();
+    let year = TREE.get_dir("migrations/2023").unwrap();
+    let readme = TREE.get_file("README.md").unwrap();
+
+    assert_eq!(readme.relative_to(&migrations), None);
+    assert_eq!(migrations.relative_to(&year), None);
+    assert_eq!(year.relative_to(&year), Some(Path::new("")));
+}
+
+#[test]
+fn only_whole_components_are_stripped() {
+    let partial = dir("migr", &[], &[]);
+    let migrations = TREE.get_dir("migrations").unwrap();
+
+    assert_eq!(migrations.relative_to(&partial), None);
+}
+
+#[test]
+fn walking_a_sub_directory_relative_to_itself() {
+    let migrations = TREE.get_dir("migrations").unwrap();
+
+    let paths: Vec<_> = migrations
+        .walk()
+        .map(|entry| entry.relative_to(&migrations).unwrap())
+        .collect();
+
+    assert_eq!(
+        paths,
+        vec![
+            Path::new("0000_bootstrap.sql"),
+            Path::new("2023"),
+            Path::new("2023/001_posts.sql"),
+            Path::new("2023/q1"),
+            Path::new("2023/q1/002_titles.sql"),
+            Path::new("README.md"),
+        ]
+    );
+}
@@ -0,0 +1,37 @@
+use include_dir::{include_dir, Dir};
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+const NEXT_TO_THIS_FILE: Dir<'_> = include_dir!("fixtures/tree", relative_to = "file");
+
+#[test]
+fn paths_can_be_relative_to_the_calling_file() {
+    let expected: Vec<_> = TREE.walk().map(|e| e.path()).collect();
+    let got: Vec<_> = NEXT_TO_THIS_FILE.walk().map(|e| e.path()).collect();
+    assert_eq!(got, expected);
+
+    let readme = NEXT_TO_THIS_FILE.get_file("README.md").unwrap();
+    assert_eq!(
+        readme.contents(),
+        TREE.get_file("README.md").unwrap().contents()
+    );
+}
+
+mod nested {
+    use include_dir::{include_dir, Dir};
+
+    // inline modules still live in this file
+    pub const MIGRATIONS: Dir<'_> =
+        include_dir!("./fixtures/tree/migrations", relative_to = "file");
+}
+
+#[test]
+fn inline_modules_use_their_own_file() {
+    assert!(nested::MIGRATIONS.contains("0000_bootstrap.sql"));
+}
+
+#[test]
+fn manifest_is_the_default() {
+    const EXPLICIT: Dir<'_> = include_dir!("tests/fixtures/tree", relative_to = "manifest");
+
+    assert_eq!(EXPLICIT, TREE);
+}
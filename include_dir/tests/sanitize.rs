@@ -0,0 +1,75 @@
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, sanitize_path, Dir};
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn traversal_attempts_are_rejected() {
+    let payloads = [
+        "../../etc/passwd",
+        "..",
+        "a/../../b",
+        "migrations/../README.md",
+        "..%2f..%2fetc%2fpasswd",
+        "%2e%2e/%2e%2e/etc/passwd",
+        "%252e%252e%252fetc%252fpasswd",
+        "/etc/passwd",
+        "%2fetc%2fpasswd",
+        "..\\..\\windows\\win.ini",
+        "migrations\\..\\..\\secret",
+        "C:/Windows/win.ini",
+        "C:\\Windows\\win.ini",
+        "README.md\0.png",
+        "README.md%00.png",
+        "%",
+        "%zz",
+        "%ff%fe",
+        "%+2e%+2e/%+2e%+2e/etc/passwd",
+        "%+f",
+        "%-1",
+        "% 2",
+        "%2",
+    ];
+
+    for payload in &payloads {
+        assert_eq!(sanitize_path(payload), None, "{:?}", payload);
+        assert!(TREE.get_file_sanitized(payload).is_none(), "{:?}", payload);
+    }
+}
+
+#[test]
+fn ordinary_paths_are_left_alone() {
+    let path = sanitize_path("migrations/2023/001_posts.sql").unwrap();
+
+    assert_eq!(path, "migrations/2023/001_posts.sql");
+    assert!(matches!(path, std::borrow::Cow::Borrowed(_)));
+}
+
+#[test]
+fn paths_are_decoded_and_normalized() {
+    assert_eq!(
+        sanitize_path("./migrations//2023/").unwrap(),
+        "migrations/2023"
+    );
+    assert_eq!(
+        sanitize_path("blog/first%20post.html").unwrap(),
+        "blog/first post.html"
+    );
+    assert_eq!(sanitize_path("").unwrap(), "");
+}
+
+#[test]
+fn sanitized_lookups_find_files() {
+    let file = TREE
+        .get_file_sanitized("migrations%2F2023/./001_posts.sql")
+        .unwrap();
+
+    assert_eq!(
+        file.path(),
+        TREE.get_file("migrations/2023/001_posts.sql")
+            .unwrap()
+            .path()
+    );
+    assert!(TREE.get_file_sanitized("migrations").is_none());
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, Dir, DirEntry, LookupError, ScopedDir};
+use std::path::Path;
+use tempdir::TempDir;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+fn migrations() -> ScopedDir<'static> {
+    TREE.scoped("migrations").unwrap()
+}
+
+#[test]
+fn lookups_are_relative_to_the_view() {
+    let view = migrations();
+
+    let readme = view.get_file("README.md").unwrap();
+    assert_eq!(readme.path(), Path::new("migrations/README.md"));
+    assert!(view.contains("2023/q1/002_titles.sql"));
+    assert!(view.get_dir("2023").is_some());
+
+    // full paths are what a plain Dir uses, not the view
+    assert!(view.get_file("migrations/README.md").is_none());
+    assert!(TREE
+        .get_dir("migrations")
+        .unwrap()
+        .get_file("README.md")
+        .is_none());
+}
+
+#[test]
+fn nothing_outside_the_view_is_reachable() {
+    let view = migrations();
+
+    assert!(view.get_file("top.rs").is_none());
+    assert!(view.get_file("../top.rs").is_none());
+    assert!(view.get_file("2023/../../top.rs").is_none());
+    assert!(view.get_file("/top.rs").is_none());
+    assert!(matches!(
+        view.try_get_entry("../top.rs"),
+        Err(LookupError::InvalidPath { .. })
+    ));
+    assert_eq!(view.try_get_entry("nope.sql"), Err(LookupError::NotFound));
+}
+
+#[test]
+fn views_nest() {
+    let q1 = migrations().get_dir("2023").unwrap().get_dir("q1").unwrap();
+
+    assert!(q1.get_file("002_titles.sql").is_some());
+    assert_eq!(q1.as_dir().path(), Path::new("migrations/2023/q1"));
+    assert_eq!(TREE.scoped("migrations/2023/q1"), Some(q1));
+}
+
+#[test]
+fn walking_gives_relative_paths() {
+    let view = migrations();
+
+    let paths: Vec<_> = view.walk().map(|(path, _)| path).collect();
+    assert_eq!(
+        paths,
+        [
+            Path::new("0000_bootstrap.sql"),
+            Path::new("2023"),
+            Path::new("2023/001_posts.sql"),
+            Path::new("2023/q1"),
+            Path::new("2023/q1/002_titles.sql"),
+            Path::new("README.md"),
+        ]
+    );
+    for (path, entry) in view.walk() {
+        assert_eq!(view.get_entry(path), Some(entry));
+    }
+}
+
+#[test]
+fn the_whole_tree_can_be_a_view() {
+    let view = ScopedDir::from(TREE);
+
+    assert!(view.get_file("migrations/README.md").is_some());
+    assert!(matches!(view.get_entry("top.rs"), Some(DirEntry::File(_))));
+}
+
+#[test]
+fn extracting_uses_relative_paths() {
+    let tmp = TempDir::new("scoped").unwrap();
+
+    migrations().extract(tmp.path()).unwrap();
+
+    assert!(tmp.path().join("README.md").is_file());
+    assert!(tmp.path().join("2023/q1/002_titles.sql").is_file());
+    assert!(!tmp.path().join("migrations").exists());
+    assert!(!tmp.path().join("top.rs").exists());
+}
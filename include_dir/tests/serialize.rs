@@ -0,0 +1,60 @@
+#![cfg(feature = "serde")]
+
+use include_dir::{include_dir, Dir, DirManifest};
+use serde_json::json;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn serialize_a_nested_tree() {
+    let q1 = TREE.get_dir("migrations/2023").unwrap();
+    let titles = q1.get_file("migrations/2023/q1/002_titles.sql").unwrap();
+    let posts = q1.get_file("migrations/2023/001_posts.sql").unwrap();
+
+    let got = serde_json::to_value(q1).unwrap();
+
+    let expected = json!({
+        "path": "migrations/2023",
+        "files": [
+            {
+                "path": "migrations/2023/001_posts.sql",
+                "len": 45,
+                "contents": posts.contents(),
+            },
+        ],
+        "dirs": [
+            {
+                "path": "migrations/2023/q1",
+                "files": [
+                    {
+                        "path": "migrations/2023/q1/002_titles.sql",
+                        "len": 41,
+                        "contents": titles.contents(),
+                    },
+                ],
+                "dirs": [],
+            },
+        ],
+    });
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn manifests_leave_out_the_contents() {
+    let q1 = TREE.get_dir("migrations/2023").unwrap();
+
+    let got = serde_json::to_value(DirManifest(&q1)).unwrap();
+
+    let expected = json!({
+        "path": "migrations/2023",
+        "files": [{ "path": "migrations/2023/001_posts.sql", "len": 45 }],
+        "dirs": [
+            {
+                "path": "migrations/2023/q1",
+                "files": [{ "path": "migrations/2023/q1/002_titles.sql", "len": 41 }],
+                "dirs": [],
+            },
+        ],
+    });
+    assert_eq!(got, expected);
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "example-output")]
+
+//! `GENERATED_EXAMPLE` is a `static` defined in the library crate, so this
+//! test crate is using it the same way a crate re-exporting assets would.
+
+use include_dir::Dir;
+use std::ptr;
+
+mod reexported {
+    pub use include_dir::GENERATED_EXAMPLE as ASSETS;
+}
+
+mod elsewhere {
+    use include_dir::Dir;
+
+    pub fn assets() -> &'static Dir<'static> {
+        &super::reexported::ASSETS
+    }
+}
+
+fn contents_ptr(dir: &Dir<'_>) -> *const u8 {
+    dir.get_file("Cargo.toml").unwrap().contents().as_ptr()
+}
+
+#[test]
+fn a_reexported_static_is_a_single_copy() {
+    assert!(ptr::eq(
+        elsewhere::assets(),
+        &include_dir::GENERATED_EXAMPLE
+    ));
+    assert!(ptr::eq(
+        &reexported::ASSETS,
+        &include_dir::GENERATED_EXAMPLE
+    ));
+
+    let here = contents_ptr(&include_dir::GENERATED_EXAMPLE);
+    assert_eq!(contents_ptr(elsewhere::assets()), here);
+    assert_eq!(contents_ptr(&reexported::ASSETS), here);
+
+    // copying the Dir out only copies the references, not the data
+    let copy: Dir<'_> = *elsewhere::assets();
+    assert_eq!(contents_ptr(&copy), here);
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "source-info")]
+
+use include_dir::{include_dir, Dir, DirBuilder};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn the_source_path_is_absolute() {
+    let expected = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/tree")
+        .canonicalize()
+        .unwrap();
+
+    assert_eq!(TREE.source_path().map(Path::new), Some(expected.as_path()));
+
+    let migrations = TREE.get_dir("migrations").unwrap();
+    assert_eq!(
+        migrations.source_path().map(Path::new),
+        Some(expected.join("migrations").as_path())
+    );
+}
+
+#[test]
+fn embedded_recently() {
+    // SOURCE_DATE_EPOCH may pin the timestamp to some time in the past
+    if std::env::var_os("SOURCE_DATE_EPOCH").is_some() {
+        return;
+    }
+
+    let embedded_at = TREE.embedded_at().unwrap();
+    let age = SystemTime::now().duration_since(embedded_at).unwrap();
+
+    assert!(age < Duration::from_secs(60 * 60 * 24 * 365));
+}
+
+#[test]
+fn merged_directories_have_no_single_source() {
+    const LOCALES: Dir<'_> =
+        include_dir!("tests/fixtures/locales/common", "tests/fixtures/locales/en");
+
+    assert_eq!(LOCALES.source_path(), None);
+    assert!(LOCALES.embedded_at().is_some());
+}
+
+#[test]
+fn built_directories_have_no_source_info() {
+    let mut builder = DirBuilder::new();
+    builder.add_file("a.txt", &b"a"[..]).unwrap();
+    let owned = builder.build();
+    let dir = owned.as_dir();
+
+    assert_eq!(dir.source_path(), None);
+    assert_eq!(dir.embedded_at(), None);
+}
@@ -0,0 +1,42 @@
+use bytes::Bytes;
+use include_dir::{include_dir, StaticDir, StaticFile};
+
+static TREE: StaticDir = include_dir!("tests/fixtures/tree");
+
+/// Something which, like a lot of web frameworks, only accepts `'static`
+/// data.
+struct Response {
+    content_type: &'static str,
+    body: Bytes,
+}
+
+fn respond(dir: StaticDir, path: &str) -> Option<Response> {
+    let file: StaticFile = dir.get_file(path)?;
+
+    Some(Response {
+        content_type: file.mime_type().unwrap_or("application/octet-stream"),
+        body: Bytes::from_static(file.contents_static()),
+    })
+}
+
+#[test]
+fn contents_can_be_used_as_static_bytes() {
+    let response = respond(TREE, "README.md").unwrap();
+
+    assert_eq!(response.content_type, "text/markdown");
+    assert_eq!(
+        response.body,
+        TREE.get_file("README.md").unwrap().contents()
+    );
+}
+
+#[test]
+fn strings_and_paths_are_static_too() {
+    let (path, contents): (&'static str, &'static str) = {
+        let file = TREE.get_file("top.rs").unwrap();
+        (file.path_static(), file.contents_utf8_static().unwrap())
+    };
+
+    assert_eq!(path, "top.rs");
+    assert!(!contents.is_empty());
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, Dir, DirBuilder};
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn count_everything_recursively() {
+    assert_eq!(TREE.file_count(), 6);
+    assert_eq!(TREE.dir_count(), 3);
+
+    let migrations = TREE.get_dir("migrations").unwrap();
+    assert_eq!(migrations.file_count(), 4);
+    assert_eq!(migrations.dir_count(), 2);
+
+    let q1 = TREE.get_dir("migrations/2023/q1").unwrap();
+    assert_eq!(q1.file_count(), 1);
+    assert_eq!(q1.dir_count(), 0);
+}
+
+#[test]
+fn total_size_adds_up_every_file() {
+    assert_eq!(TREE.total_size(), 224);
+    assert_eq!(TREE.get_dir("migrations").unwrap().total_size(), 164);
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn compressed_and_original_sizes() {
+    const COMPRESSED: Dir<'_> = include_dir!("tests/fixtures/compression", compress = true);
+    const ORIGINAL: Dir<'_> = include_dir!("tests/fixtures/compression");
+
+    assert_eq!(COMPRESSED.total_size(), ORIGINAL.total_size());
+    assert!(COMPRESSED.total_size_compressed() < ORIGINAL.total_size_compressed());
+    assert_eq!(ORIGINAL.total_size_compressed(), ORIGINAL.total_size());
+    assert_eq!(TREE.total_size_compressed(), 224);
+    assert_eq!(
+        COMPRESSED.total_size_const(),
+        COMPRESSED.total_size_compressed()
+    );
+}
+
+#[test]
+fn sizes_can_be_checked_at_compile_time() {
+    const FILES: usize = TREE.file_count();
+    const DIRS: usize = TREE.dir_count();
+    const TOTAL: u64 = TREE.total_size_const();
+    const LARGEST: u64 = TREE.largest_file_size();
+    const _: () = assert!(FILES == 6 && TOTAL < 1024);
+
+    assert_eq!(FILES, TREE.file_count());
+    assert_eq!(DIRS, TREE.dir_count());
+    assert_eq!(TOTAL, TREE.total_size());
+    assert_eq!(
+        LARGEST,
+        TREE.walk()
+            .filter_map(|e| e.as_file())
+            .map(|f| f.len() as u64)
+            .max()
+            .unwrap()
+    );
+}
+
+#[test]
+fn an_empty_dir_has_no_largest_file() {
+    let owned = DirBuilder::new().build();
+    let empty = owned.as_dir();
+
+    assert_eq!(empty.largest_file_size(), 0);
+    assert_eq!(empty.total_size_const(), 0);
+}
@@ -0,0 +1,32 @@
+#![cfg(unix)]
+
+use include_dir::{include_dir, Dir};
+
+#[test]
+fn symlinks_are_followed_by_default() {
+    const FOLLOWED: Dir<'_> = include_dir!("tests/fixtures/symlinks");
+    let original = FOLLOWED.get_file("v3/app.js").unwrap();
+
+    let linked_file = FOLLOWED.get_file("app.js").unwrap();
+    assert_eq!(linked_file.contents(), original.contents());
+
+    let linked_dir = FOLLOWED.get_dir("latest").unwrap();
+    let copy = linked_dir.get_file("latest/app.js").unwrap();
+    assert_eq!(copy.contents(), original.contents());
+}
+
+#[test]
+fn symlinks_can_be_skipped() {
+    const SKIPPED: Dir<'_> = include_dir!("tests/fixtures/symlinks", symlinks = "skip");
+
+    assert!(SKIPPED.get_file("v3/app.js").is_some());
+    assert!(!SKIPPED.contains("app.js"));
+    assert!(!SKIPPED.contains("latest"));
+}
+
+#[test]
+fn explicitly_following_symlinks() {
+    const FOLLOWED: Dir<'_> = include_dir!("tests/fixtures/symlinks", symlinks = "follow");
+
+    assert!(FOLLOWED.get_file("latest/app.js").is_some());
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "tar")]
+
+use include_dir::{include_dir, Dir};
+use std::collections::BTreeMap;
+use std::io::{self, Read, Write};
+use tar::{Archive, EntryType};
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+/// Something which can't seek, like a pipe.
+struct Pipe(Vec<u8>);
+
+impl Write for Pipe {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn round_trip_the_tree_fixture() {
+    let mut pipe = Pipe(Vec::new());
+    TREE.write_tar(&mut pipe).unwrap();
+
+    let mut archive = Archive::new(pipe.0.as_slice());
+    let mut files = BTreeMap::new();
+    let mut dirs = Vec::new();
+
+    for entry in archive.entries().unwrap() {
+        let mut entry = entry.unwrap();
+        let path = entry.path().unwrap().to_str().unwrap().to_string();
+        let header = entry.header();
+
+        match header.entry_type() {
+            EntryType::Directory => {
+                assert_eq!(header.mode().unwrap(), 0o755);
+                dirs.push(path.trim_end_matches('/').to_string());
+            }
+            EntryType::Regular => {
+                assert_eq!(header.mode().unwrap(), 0o644);
+                let mut contents = Vec::new();
+                entry.read_to_end(&mut contents).unwrap();
+                files.insert(path, contents);
+            }
+            other => panic!("unexpected entry type {:?}", other),
+        }
+    }
+
+    assert_eq!(
+        dirs,
+        vec!["migrations", "migrations/2023", "migrations/2023/q1"]
+    );
+    let expected: BTreeMap<_, _> = TREE
+        .walk()
+        .filter_map(|e| e.as_file())
+        .map(|f| (f.path.to_string(), f.contents().to_vec()))
+        .collect();
+    assert_eq!(files, expected);
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "tera")]
+
+use include_dir::{include_dir, Dir, DirBuilder, TemplateOptions};
+use tera::{Context, Tera};
+
+const TEMPLATES: Dir<'_> = include_dir!("tests/fixtures/templates");
+
+fn context() -> Context {
+    let mut context = Context::new();
+    context.insert("name", "World");
+    context
+}
+
+#[test]
+fn templates_extend_and_include_each_other() {
+    let mut tera = Tera::default();
+    let options = TemplateOptions::new()
+        .strip_suffix(".tera")
+        .skip_non_utf8(true);
+    TEMPLATES.add_to_tera(&mut tera, &options).unwrap();
+
+    let rendered = tera.render("index.html", &context()).unwrap();
+    assert!(rendered.starts_with("<html>"));
+    assert!(rendered.contains("<p>Hello, World!</p>"));
+}
+
+#[test]
+fn names_use_forward_slashes_relative_to_the_dir() {
+    let mut tera = Tera::default();
+    let options = TemplateOptions::new().skip_non_utf8(true);
+    TEMPLATES.add_to_tera(&mut tera, &options).unwrap();
+
+    let mut names: Vec<_> = tera.get_template_names().collect();
+    names.sort_unstable();
+    assert_eq!(
+        names,
+        ["base.html", "index.html.tera", "partials/greeting.html"]
+    );
+
+    // nested directories are named relative to themselves
+    let partials = TEMPLATES.get_dir("partials").unwrap();
+    let mut tera = Tera::default();
+    partials.add_to_tera(&mut tera, &options).unwrap();
+    assert_eq!(
+        tera.get_template_names().collect::<Vec<_>>(),
+        ["greeting.html"]
+    );
+}
+
+#[test]
+fn non_utf8_files_are_an_error_by_default() {
+    let mut tera = Tera::default();
+    let err = TEMPLATES
+        .add_to_tera(&mut tera, &TemplateOptions::new())
+        .unwrap_err();
+
+    assert!(err.to_string().contains("logo.png"), "{}", err);
+    assert!(err.to_string().contains("isn't valid UTF-8"), "{}", err);
+}
+
+#[test]
+fn parse_errors_name_the_template() {
+    let mut builder = DirBuilder::from_dir(&TEMPLATES);
+    builder
+        .add_file("emails/broken.html", &b"{% block %}"[..])
+        .unwrap();
+    let owned = builder.build();
+    let dir = owned.as_dir();
+
+    let mut tera = Tera::default();
+    let err = dir
+        .add_to_tera(&mut tera, &TemplateOptions::new().skip_non_utf8(true))
+        .unwrap_err();
+
+    assert!(err.to_string().contains("emails/broken.html"), "{}", err);
+}
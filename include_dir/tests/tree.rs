@@ -0,0 +1,51 @@
+mod common;
+
+use common::{dir, file};
+use include_dir::{include_dir, Dir};
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn render_the_tree_fixture() {
+    let expected = "\
+.
+├── README.md (47 B)
+├── migrations/
+│   ├── 0000_bootstrap.sql (45 B)
+│   ├── 2023/
+│   │   ├── 001_posts.sql (45 B)
+│   │   └── q1/
+│   │       └── 002_titles.sql (41 B)
+│   └── README.md (33 B)
+└── top.rs (13 B)
+";
+
+    assert_eq!(TREE.tree().to_string(), expected);
+}
+
+#[test]
+fn sub_directories_are_labelled_with_their_path() {
+    let q1 = TREE.get_dir("migrations/2023/q1").unwrap();
+
+    assert_eq!(
+        q1.tree().to_string(),
+        "migrations/2023/q1/\n└── 002_titles.sql (41 B)\n"
+    );
+}
+
+#[test]
+fn large_files_use_bigger_units() {
+    const ASSETS: Dir<'_> = dir(
+        "assets",
+        &[
+            file("assets/big.bin", &[0; 3 * 1024 * 1024]),
+            file("assets/main.css", &[0; 4200]),
+        ],
+        &[],
+    );
+
+    assert_eq!(
+        ASSETS.tree().to_string(),
+        "assets/\n├── big.bin (3.0 MB)\n└── main.css (4.1 KB)\n"
+    );
+}
@@ -0,0 +1,8 @@
+use include_dir::{include_dir, Dir};
+
+static ASSETS: Dir<'_> = include_dir!(
+    "$INCLUDE_DIR_FIXTURES/attributes/assets",
+    attributes = "$INCLUDE_DIR_FIXTURES/attributes/invalid.toml"
+);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use include_dir::{include_dir, Dir};
+
+static ASSETS: Dir<'_> = include_dir!(
+    "$INCLUDE_DIR_FIXTURES/attributes/assets",
+    attributes = "$INCLUDE_DIR_FIXTURES/attributes/unmatched.toml",
+    unmatched_attributes = "ignore"
+);
+
+fn main() {}
@@ -0,0 +1,12 @@
+use include_dir::{include_dir, Dir};
+
+static ASSETS: Dir<'_> = include_dir!(
+    "$INCLUDE_DIR_FIXTURES/attributes/assets",
+    attributes = "$INCLUDE_DIR_FIXTURES/attributes/unmatched.toml",
+    unmatched_attributes = "warn"
+);
+
+fn main() {
+    let index = ASSETS.get_file("index.html").unwrap();
+    assert_eq!(index.attr("cache"), Some("no-cache"));
+}
@@ -0,0 +1,9 @@
+use include_dir::{include_dir, Dir};
+
+static MIGRATIONS: Dir<'_> = include_dir!(
+    "$INCLUDE_DIR_FIXTURES/tree",
+    include = ["**/*.surql"],
+    deny_empty = true
+);
+
+fn main() {}
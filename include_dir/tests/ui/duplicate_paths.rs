@@ -0,0 +1,8 @@
+use include_dir::{include_dir, Dir};
+
+static LOCALES: Dir<'_> = include_dir!(
+    "$INCLUDE_DIR_FIXTURES/locales/common",
+    "$INCLUDE_DIR_FIXTURES/locales/de"
+);
+
+fn main() {}
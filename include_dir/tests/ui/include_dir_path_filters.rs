@@ -0,0 +1,15 @@
+use include_dir::include_dir_path;
+
+const README: &str = include_dir_path!(
+    "$INCLUDE_DIR_FIXTURES/tree",
+    "migrations/README.md",
+    exclude = ["**/*.md"],
+);
+const TOP: &str = include_dir_path!(
+    "$INCLUDE_DIR_FIXTURES/tree",
+    "top.rs",
+    include = ["**/*.sql"],
+);
+const PARENT: &str = include_dir_path!("$INCLUDE_DIR_FIXTURES/tree", "migrations/../top.rs");
+
+fn main() {}
@@ -0,0 +1,5 @@
+use include_dir::include_dir_path;
+
+const LOGO: &str = include_dir_path!("$INCLUDE_DIR_FIXTURES/tree", "migrations/READ_ME.md");
+
+fn main() {}
@@ -0,0 +1,5 @@
+use include_dir::{include_dir, Dir};
+
+static ASSETS: Dir<'_> = include_dir!("tests/fixtures/filters", exclude = ["a/***"]);
+
+fn main() {}
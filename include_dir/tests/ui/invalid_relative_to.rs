@@ -0,0 +1,5 @@
+use include_dir::{include_dir, Dir};
+
+static ASSETS: Dir<'_> = include_dir!("fixtures/tree", relative_to = "crate");
+
+fn main() {}
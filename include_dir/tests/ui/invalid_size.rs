@@ -0,0 +1,5 @@
+use include_dir::{include_dir, Dir};
+
+static ASSETS: Dir<'_> = include_dir!("$INCLUDE_DIR_FIXTURES/tree", max_file_size = "ten megabytes");
+
+fn main() {}
@@ -0,0 +1,5 @@
+use include_dir::{include_dir, Dir};
+
+static ASSETS: Dir<'_> = include_dir!("tests/fixtures/tree", vanished = "ignore");
+
+fn main() {}
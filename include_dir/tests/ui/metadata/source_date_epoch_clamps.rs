@@ -0,0 +1,13 @@
+use include_dir::{include_dir, Dir};
+use std::time::{Duration, UNIX_EPOCH};
+
+static MTIMES: Dir<'_> = include_dir!("$INCLUDE_DIR_MTIMES");
+
+fn main() {
+    let modified = |path| MTIMES.get_file(path).unwrap().metadata().unwrap().modified();
+
+    // older than SOURCE_DATE_EPOCH, so left alone
+    assert_eq!(modified("old.txt"), UNIX_EPOCH + Duration::from_secs(1_000_000_000));
+    // newer than SOURCE_DATE_EPOCH, so clamped to it
+    assert_eq!(modified("new.txt"), UNIX_EPOCH + Duration::from_secs(1_714_559_520));
+}
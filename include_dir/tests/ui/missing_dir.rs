@@ -0,0 +1,5 @@
+use include_dir::{include_dir, Dir};
+
+static ASSETS: Dir<'_> = include_dir!("$INCLUDE_DIR_FIXTURES/does-not-exist");
+
+fn main() {}
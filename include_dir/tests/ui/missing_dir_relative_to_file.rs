@@ -0,0 +1,6 @@
+use include_dir::{include_dir, Dir};
+
+// resolved next to this file, not the crate root
+static ASSETS: Dir<'_> = include_dir!("tests/fixtures/tree", relative_to = "file");
+
+fn main() {}
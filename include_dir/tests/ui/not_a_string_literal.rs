@@ -0,0 +1,7 @@
+use include_dir::{include_dir, Dir};
+
+const ASSETS_DIR: &str = "assets";
+
+static ASSETS: Dir<'_> = include_dir!(ASSETS_DIR);
+
+fn main() {}
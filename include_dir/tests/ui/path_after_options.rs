@@ -0,0 +1,9 @@
+use include_dir::{include_dir, Dir};
+
+static LOCALES: Dir<'_> = include_dir!(
+    "$INCLUDE_DIR_FIXTURES/locales/common",
+    prefix = "locales",
+    "$INCLUDE_DIR_FIXTURES/locales/en"
+);
+
+fn main() {}
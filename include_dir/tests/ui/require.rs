@@ -0,0 +1,9 @@
+use include_dir::{include_dir, Dir};
+
+static MIGRATIONS: Dir<'_> = include_dir!(
+    "$INCLUDE_DIR_FIXTURES/tree/migrations",
+    prefix = "migrations",
+    require = ["0000_bootstrap.sql", "**/*.sql", "0001_init.sql", "seeds/*"]
+);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use include_dir::{include_dir, Dir};
+
+const ASSETS: Dir<'_> = include_dir!("$INCLUDE_DIR_FIXTURES/tree");
+const _: () = assert!(ASSETS.total_size_const() <= 100, "Assets are over budget");
+
+fn main() {}
@@ -0,0 +1,11 @@
+use include_dir::{include_dir, Dir};
+use std::time::{Duration, UNIX_EPOCH};
+
+static TREE: Dir<'_> = include_dir!("$INCLUDE_DIR_FIXTURES/tree");
+
+fn main() {
+    let expected = UNIX_EPOCH + Duration::from_secs(1_714_559_520);
+
+    assert_eq!(TREE.embedded_at(), Some(expected));
+    assert_eq!(TREE.get_dir("migrations").unwrap().embedded_at(), Some(expected));
+}
@@ -0,0 +1,5 @@
+use include_dir::{include_dir, Dir};
+
+static ASSETS: Dir<'_> = include_dir!("$INCLUDE_DIR_NON_UTF8/clashing", non_utf8_names = "lossy");
+
+fn main() {}
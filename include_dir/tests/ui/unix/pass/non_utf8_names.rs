@@ -0,0 +1,25 @@
+use include_dir::{include_dir, Dir};
+
+static SKIPPED: Dir<'_> = include_dir!("$INCLUDE_DIR_NON_UTF8/names", non_utf8_names = "skip");
+static LOSSY: Dir<'_> = include_dir!("$INCLUDE_DIR_NON_UTF8/names", non_utf8_names = "lossy");
+
+fn main() {
+    let skipped: Vec<_> = SKIPPED.walk().map(|e| e.path().to_path_buf()).collect();
+    assert_eq!(skipped, vec![std::path::PathBuf::from("plain.txt")]);
+
+    let lossy: Vec<_> = LOSSY.walk().map(|e| e.path().to_path_buf()).collect();
+    assert_eq!(
+        lossy,
+        vec![
+            std::path::PathBuf::from("caf\u{fffd}.txt"),
+            std::path::PathBuf::from("d\u{fffd}j\u{fffd}"),
+            std::path::PathBuf::from("d\u{fffd}j\u{fffd}/inner.txt"),
+            std::path::PathBuf::from("plain.txt"),
+        ]
+    );
+
+    let coffee = LOSSY.get_file("caf\u{fffd}.txt").unwrap();
+    assert_eq!(coffee.contents(), b"coffee");
+    let inner = LOSSY.get_file("d\u{fffd}j\u{fffd}/inner.txt").unwrap();
+    assert_eq!(inner.contents(), b"inner");
+}
@@ -0,0 +1,5 @@
+use include_dir::{include_dir, Dir};
+
+static ASSETS: Dir<'_> = include_dir!("tests/fixtures/attributes/assets", attributes = "attributes.toml");
+
+fn main() {}
@@ -0,0 +1,79 @@
+#![cfg(feature = "hash")]
+
+mod common;
+
+use common::{dir, file};
+use include_dir::{include_dir, Dir, File};
+use std::path::Path;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+/// A file claiming to have `hash`.
+fn hashed_file(path: &'static str, contents: &'static [u8], hash: [u8; 32]) -> File<'static> {
+    File {
+        hash,
+        ..file(path, contents)
+    }
+}
+
+#[test]
+fn embedded_trees_verify() {
+    TREE.verify().unwrap();
+    TREE.get_dir("migrations").unwrap().verify().unwrap();
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn compressed_trees_verify() {
+    const COMPRESSED: Dir<'_> = include_dir!("tests/fixtures/tree", compress = true);
+
+    COMPRESSED.verify().unwrap();
+    assert_eq!(COMPRESSED.digest(), TREE.digest());
+}
+
+#[test]
+fn the_digest_is_the_root_hash() {
+    let digest = TREE.digest();
+
+    assert_eq!(digest.as_bytes(), &TREE.hash());
+
+    let hex = digest.to_string();
+    assert_eq!(hex.len(), 64);
+    assert!(hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')));
+    assert_eq!(&hex[..2], format!("{:02x}", TREE.hash()[0]));
+}
+
+#[test]
+fn tampered_files_are_reported() {
+    let original = TREE.get_file("README.md").unwrap();
+    let files: &'static [File<'static>] = Box::leak(Box::new([hashed_file(
+        "README.md",
+        b"something else entirely",
+        original.hash(),
+    )]));
+    let tampered = dir("", files, &[]);
+
+    let err = tampered.verify().unwrap_err();
+
+    assert_eq!(err.path(), Path::new("README.md"));
+    assert!(!err.is_dir());
+    assert_eq!(
+        err.to_string(),
+        "The contents of \"README.md\" don't match the hash recorded when it was embedded"
+    );
+}
+
+#[test]
+fn directories_with_the_wrong_hash_are_reported() {
+    let migrations = TREE.get_dir("migrations").unwrap();
+    let dirs: &'static [Dir<'static>] = Box::leak(Box::new([Dir {
+        hash: [0; 32],
+        ..migrations
+    }]));
+    let tampered = dir("", &[], dirs);
+
+    let err = tampered.verify().unwrap_err();
+
+    assert_eq!(err.path(), Path::new("migrations"));
+    assert!(err.is_dir());
+}
@@ -0,0 +1,172 @@
+#![cfg(feature = "std")]
+
+use include_dir::{include_dir, Dir, DirEntry};
+use std::cell::Cell;
+use std::path::Path;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+#[test]
+fn walk_is_depth_first_and_sorted() {
+    let paths: Vec<_> = TREE.walk().map(|e| e.path()).collect();
+
+    assert_eq!(
+        paths,
+        vec![
+            Path::new("README.md"),
+            Path::new("migrations"),
+            Path::new("migrations/0000_bootstrap.sql"),
+            Path::new("migrations/2023"),
+            Path::new("migrations/2023/001_posts.sql"),
+            Path::new("migrations/2023/q1"),
+            Path::new("migrations/2023/q1/002_titles.sql"),
+            Path::new("migrations/README.md"),
+            Path::new("top.rs"),
+        ]
+    );
+}
+
+#[test]
+fn walking_a_sub_directory_only_visits_its_descendants() {
+    let dir = TREE.get_dir("migrations/2023").unwrap();
+    let paths: Vec<_> = dir.walk().map(|e| e.path()).collect();
+
+    assert_eq!(
+        paths,
+        vec![
+            Path::new("migrations/2023/001_posts.sql"),
+            Path::new("migrations/2023/q1"),
+            Path::new("migrations/2023/q1/002_titles.sql"),
+        ]
+    );
+}
+
+#[test]
+fn into_iterator_walks_recursively() {
+    let mut files = 0;
+    let mut dirs = 0;
+
+    for entry in &TREE {
+        match entry {
+            DirEntry::File(_) => files += 1,
+            DirEntry::Dir(_) => dirs += 1,
+        }
+    }
+
+    assert_eq!(files, 6);
+    assert_eq!(dirs, 3);
+}
+
+#[test]
+fn file_and_dir_paths_are_slash_separated_and_sorted() {
+    let files: Vec<_> = TREE.file_paths().collect();
+    assert_eq!(
+        files,
+        [
+            "README.md",
+            "migrations/0000_bootstrap.sql",
+            "migrations/2023/001_posts.sql",
+            "migrations/2023/q1/002_titles.sql",
+            "migrations/README.md",
+            "top.rs",
+        ]
+    );
+
+    let dirs: Vec<_> = TREE.dir_paths().collect();
+    assert_eq!(
+        dirs,
+        ["migrations", "migrations/2023", "migrations/2023/q1"]
+    );
+
+    let nested: Vec<_> = TREE
+        .get_dir("migrations/2023")
+        .unwrap()
+        .dir_paths()
+        .collect();
+    assert_eq!(nested, ["migrations/2023/q1"]);
+}
+
+#[test]
+fn flatten_pairs_every_file_with_its_contents() {
+    let flattened: Vec<_> = TREE.flatten().collect();
+    let expected: Vec<_> = TREE
+        .file_paths()
+        .map(|path| (path, TREE.get_file(path).unwrap().contents()))
+        .collect();
+    assert_eq!(flattened, expected);
+
+    let nested: Vec<_> = TREE
+        .get_dir("migrations/2023")
+        .unwrap()
+        .flatten()
+        .map(|(path, _)| path)
+        .collect();
+    assert_eq!(
+        nested,
+        [
+            "migrations/2023/001_posts.sql",
+            "migrations/2023/q1/002_titles.sql"
+        ]
+    );
+}
+
+#[test]
+fn maps_use_the_same_paths_as_the_getters() {
+    static PREFIXED: Dir<'_> = include_dir!("tests/fixtures/tree", prefix = "static");
+
+    let map = PREFIXED.to_map();
+    assert_eq!(map.len(), PREFIXED.file_paths().count());
+
+    for (path, file) in &map {
+        assert_eq!(PREFIXED.get_file(path), Some(*file));
+        assert_eq!(file.path, *path);
+    }
+    assert!(map.contains_key("static/migrations/README.md"));
+    assert!(!map.contains_key("static/migrations"));
+}
+
+#[test]
+fn files_matching_a_predicate() {
+    let extension = String::from("sql");
+    let sql: Vec<_> = TREE
+        .files_matching(|f| f.extension() == Some(extension.as_str()))
+        .map(|f| f.path)
+        .collect();
+    assert_eq!(
+        sql,
+        [
+            "migrations/0000_bootstrap.sql",
+            "migrations/2023/001_posts.sql",
+            "migrations/2023/q1/002_titles.sql",
+        ]
+    );
+
+    // matches are found lazily
+    let visited = Cell::new(0);
+    let first: Vec<_> = TREE
+        .files_matching(|_| {
+            visited.set(visited.get() + 1);
+            true
+        })
+        .take(2)
+        .collect();
+    assert_eq!(first.len(), 2);
+    assert_eq!(visited.get(), 2);
+}
+
+#[test]
+fn dirs_matching_a_predicate() {
+    let years: Vec<_> = TREE
+        .dirs_matching(|d| d.file_name().parse::<u32>().is_ok())
+        .map(|d| d.path)
+        .collect();
+    assert_eq!(years, ["migrations/2023"]);
+
+    // directories which don't match are still searched
+    let quarters: Vec<_> = TREE
+        .dirs_matching(|d| d.file_name().starts_with('q'))
+        .map(|d| d.path)
+        .collect();
+    assert_eq!(quarters, ["migrations/2023/q1"]);
+    assert_eq!(TREE.dirs_matching(|_| false).count(), 0);
+}
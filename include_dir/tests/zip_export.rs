@@ -0,0 +1,85 @@
+#![cfg(feature = "zip")]
+
+mod common;
+
+use common::{dir, file};
+use include_dir::{include_dir, Dir, DirEntry};
+use std::io::{Cursor, Read};
+use zip::ZipArchive;
+
+const TREE: Dir<'_> = include_dir!("tests/fixtures/tree");
+
+fn write_zip(dir: &Dir<'_>) -> ZipArchive<Cursor<Vec<u8>>> {
+    let mut buffer = Cursor::new(Vec::new());
+    dir.write_zip(&mut buffer).unwrap();
+
+    buffer.set_position(0);
+    ZipArchive::new(buffer).unwrap()
+}
+
+#[test]
+fn round_trip_the_tree_fixture() {
+    let mut archive = write_zip(&TREE);
+
+    for entry in TREE.walk() {
+        match entry {
+            DirEntry::Dir(d) => {
+                let zipped = archive.by_name(&format!("{}/", d.path)).unwrap();
+                assert!(zipped.is_dir());
+            }
+            DirEntry::File(f) => {
+                let mut zipped = archive.by_name(f.path).unwrap();
+                assert!(zipped.is_file());
+                assert_eq!(zipped.unix_mode().map(|m| m & 0o777), Some(0o644));
+
+                let mut contents = Vec::new();
+                zipped.read_to_end(&mut contents).unwrap();
+                assert_eq!(contents, f.contents());
+            }
+        }
+    }
+    assert_eq!(archive.len(), TREE.walk().count());
+}
+
+#[test]
+fn empty_directories_get_an_entry() {
+    const EMPTY: Dir<'_> = dir("", &[], &[dir("logs", &[], &[])]);
+
+    let mut archive = write_zip(&EMPTY);
+
+    assert_eq!(archive.len(), 1);
+    assert!(archive.by_name("logs/").unwrap().is_dir());
+}
+
+#[cfg(feature = "metadata")]
+#[test]
+fn files_keep_their_modification_time() {
+    use include_dir::{File, Metadata};
+
+    const DIR: Dir<'_> = dir(
+        "",
+        &[File {
+            metadata: Some(Metadata {
+                modified: 1_700_000_000,
+                len: 0,
+                mode: None,
+            }),
+            ..file("notes.txt", b"")
+        }],
+        &[],
+    );
+
+    let mut archive = write_zip(&DIR);
+    let zipped = archive.by_name("notes.txt").unwrap();
+    let modified = zipped.last_modified().unwrap();
+
+    // 2023-11-14T22:13:20Z
+    assert_eq!(
+        (modified.year(), modified.month(), modified.day()),
+        (2023, 11, 14)
+    );
+    assert_eq!(
+        (modified.hour(), modified.minute(), modified.second()),
+        (22, 13, 20)
+    );
+}
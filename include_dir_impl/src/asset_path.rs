@@ -0,0 +1,175 @@
+//! The `include_dir_path!()` macro, which checks that a path exists in a
+//! directory at compile time.
+
+use crate::file::path_to_string;
+use crate::options::{Options, Symlinks};
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::path::{Path, PathBuf};
+use syn::parse::{Parse, ParseStream};
+use syn::{Error, LitStr, Token};
+
+/// The arguments passed to `include_dir_path!()`, the directory and the path
+/// inside it followed by any of `include_dir!()`'s options.
+pub(crate) struct Input {
+    options: Options,
+    path: LitStr,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let dir = input.parse::<LitStr>().map_err(|e| {
+            Error::new(
+                e.span(),
+                "Expected the directory's path as a string literal, e.g. `include_dir_path!(\"assets\", \"index.html\")`",
+            )
+        })?;
+        input.parse::<Token![,]>()?;
+        let path = input.parse::<LitStr>().map_err(|e| {
+            Error::new(
+                e.span(),
+                "Expected the path inside the directory as a string literal, e.g. `include_dir_path!(\"assets\", \"index.html\")`",
+            )
+        })?;
+
+        // the rest is the same as for `include_dir!()`
+        let rest: TokenStream = input.parse()?;
+        let options: Options = syn::parse2(quote!(#dir #rest))?;
+        if let Some(extra) = options.paths.get(1) {
+            return Err(Error::new(
+                extra.span(),
+                "include_dir_path!() only takes one directory",
+            ));
+        }
+
+        Ok(Input { options, path })
+    }
+}
+
+/// Make sure the path exists and would be embedded by `include_dir!()` with
+/// the same options, expanding to its stored path.
+pub(crate) fn expand(input: &Input) -> syn::Result<TokenStream> {
+    let Input { options, path } = input;
+    let lit = &options.paths[0];
+
+    let root = options.resolved_path(lit)?;
+    if !root.exists() {
+        let msg = format!("\"{}\" doesn't exist", root.display());
+        return Err(Error::new(lit.span(), msg));
+    }
+    if !root.is_dir() {
+        let msg = format!("\"{}\" isn't a directory", root.display());
+        return Err(Error::new(lit.span(), msg));
+    }
+
+    let names = names(path)?;
+    let abs_path = find(&root, &names, options).map_err(|msg| Error::new(path.span(), msg))?;
+
+    let stored = path_to_string(&options.stored_path(&names.iter().collect::<PathBuf>()));
+    let keep_tracked = keep_tracked(&abs_path);
+
+    Ok(quote!({
+        #keep_tracked
+        #stored
+    }))
+}
+
+/// Split the path into its names, accepting either kind of slash like the
+/// runtime lookups do.
+fn names(path: &LitStr) -> syn::Result<Vec<String>> {
+    let value = path.value();
+    let mut names = Vec::new();
+
+    for name in value.split(['/', '\\']) {
+        match name {
+            "" | "." => {}
+            ".." => {
+                return Err(Error::new(
+                    path.span(),
+                    "The path can't contain any \"..\" components",
+                ))
+            }
+            name => names.push(name.to_string()),
+        }
+    }
+
+    if value.starts_with(['/', '\\']) {
+        return Err(Error::new(
+            path.span(),
+            "The path must be relative to the directory",
+        ));
+    }
+
+    Ok(names)
+}
+
+/// Walk down from `root` one name at a time, checking each entry against the
+/// filters the same way `include_dir!()` would, and returning where the path
+/// ended up.
+fn find(root: &Path, names: &[String], options: &Options) -> Result<PathBuf, String> {
+    let mut abs_path = root.to_path_buf();
+    let mut rel_path = PathBuf::new();
+
+    for (ix, name) in names.iter().enumerate() {
+        abs_path.push(name);
+        rel_path.push(name);
+        let is_last = ix == names.len() - 1;
+
+        let missing = || {
+            format!(
+                "\"{}\" isn't in \"{}\"",
+                path_to_string(&rel_path),
+                root.display()
+            )
+        };
+
+        if options.is_excluded(&rel_path) {
+            return Err(format!(
+                "\"{}\" is left out by the `exclude` patterns",
+                path_to_string(&rel_path)
+            ));
+        }
+
+        let meta = abs_path.symlink_metadata().map_err(|_| missing())?;
+        if meta.file_type().is_symlink() && options.symlinks != Symlinks::Follow {
+            return Err(format!(
+                "\"{}\" is a symlink, which is only embedded with `symlinks = \"follow\"`",
+                path_to_string(&rel_path)
+            ));
+        }
+
+        let meta = abs_path.metadata().map_err(|_| missing())?;
+        if meta.is_file() && is_last {
+            if !options.is_included(&rel_path) {
+                return Err(format!(
+                    "\"{}\" doesn't match any of the `include` patterns",
+                    path_to_string(&rel_path)
+                ));
+            }
+        } else if !meta.is_dir() {
+            return Err(missing());
+        }
+    }
+
+    Ok(abs_path)
+}
+
+/// Make sure deleting or renaming the file causes a rebuild, so the check
+/// runs again.
+fn keep_tracked(abs_path: &Path) -> TokenStream {
+    // directories can't be tracked
+    let abs_path = match abs_path.to_str() {
+        Some(abs_path) if Path::new(abs_path).is_file() => abs_path,
+        _ => return TokenStream::new(),
+    };
+
+    #[cfg(feature = "nightly")]
+    {
+        proc_macro::tracked::path(abs_path);
+        TokenStream::new()
+    }
+    #[cfg(not(feature = "nightly"))]
+    quote!(
+        const _: &[u8] = include_bytes!(#abs_path);
+    )
+}
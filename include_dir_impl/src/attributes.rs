@@ -0,0 +1,109 @@
+//! Support for the `attributes = "..."` option, which attaches key/value
+//! attributes from a TOML manifest to the files matching each glob.
+
+use crate::dir::Dir;
+use crate::options::Options;
+use anyhow::{format_err, Context, Error};
+use glob::Pattern;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The parsed manifest, with its rules in the order they were written.
+pub(crate) struct Manifest {
+    path: PathBuf,
+    rules: Vec<Rule>,
+}
+
+struct Rule {
+    pattern: Pattern,
+    attrs: Vec<(String, String)>,
+}
+
+impl Manifest {
+    /// Read and parse the manifest at `path`.
+    pub fn from_disk(path: PathBuf) -> Result<Manifest, Error> {
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("Couldn't read \"{}\"", path.display()))?;
+        let rules = parse(&text)
+            .with_context(|| format!("\"{}\" isn't a valid attributes file", path.display()))?;
+
+        Ok(Manifest { path, rules })
+    }
+
+    /// The manifest's location, so it can be tracked as a build input.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Give every file the attributes of each rule matching it, with later
+    /// rules overriding earlier ones. Returns the globs which matched
+    /// nothing.
+    pub fn apply(&self, dir: &mut Dir, options: &Options) -> Vec<String> {
+        let mut matched = vec![false; self.rules.len()];
+
+        for file in dir.all_files_mut() {
+            let mut attrs = BTreeMap::new();
+
+            for (rule, matched) in self.rules.iter().zip(&mut matched) {
+                if options.matches_stored(&rule.pattern, file.root_rel_path()) {
+                    *matched = true;
+                    attrs.extend(rule.attrs.iter().cloned());
+                }
+            }
+
+            file.set_attrs(attrs.into_iter().collect());
+        }
+
+        self.rules
+            .iter()
+            .zip(matched)
+            .filter(|(_, matched)| !matched)
+            .map(|(rule, _)| rule.pattern.as_str().to_string())
+            .collect()
+    }
+}
+
+/// Parse a manifest, where each top-level key is a glob and its table holds
+/// the attributes.
+fn parse(text: &str) -> Result<Vec<Rule>, Error> {
+    let table: toml::Table = text.parse()?;
+    let mut rules = Vec::new();
+
+    for (glob, attrs) in table {
+        let pattern = Pattern::new(&glob)
+            .map_err(|e| format_err!("\"{}\" isn't a valid glob pattern: {}", glob, e))?;
+        let attrs = match attrs {
+            toml::Value::Table(attrs) => attrs,
+            _ => {
+                return Err(format_err!(
+                    "Expected a table of attributes for \"{}\"",
+                    glob
+                ))
+            }
+        };
+
+        let attrs = attrs
+            .into_iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    toml::Value::String(s) => s,
+                    toml::Value::Integer(i) => i.to_string(),
+                    toml::Value::Float(f) => f.to_string(),
+                    toml::Value::Boolean(b) => b.to_string(),
+                    _ => {
+                        return Err(format_err!(
+                            "The \"{}\" attribute for \"{}\" must be a string, number, or boolean",
+                            key,
+                            glob
+                        ))
+                    }
+                };
+                Ok((key, value))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        rules.push(Rule { pattern, attrs });
+    }
+
+    Ok(rules)
+}
@@ -0,0 +1,127 @@
+//! Support for the `deduplicate = true` option.
+
+use crate::dir::Dir;
+use crate::file::{self, shared_ident, File};
+use anyhow::Error;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Find files with identical contents and point them all at a single shared
+/// constant, returning the definitions of those constants.
+///
+/// Files whose contents are unique are left alone.
+pub(crate) fn deduplicate(dir: &mut Dir) -> Result<TokenStream, Error> {
+    let mut files = dir.all_files_mut();
+
+    // files can only be the same if their fingerprints are, so nothing else
+    // needs comparing (or keeping in memory)
+    let mut candidates: HashMap<Fingerprint, Vec<usize>> = HashMap::new();
+    for (ix, file) in files.iter().enumerate() {
+        candidates.entry(fingerprint(file)?).or_default().push(ix);
+    }
+
+    let mut groups = Vec::new();
+    for mut candidates in candidates.into_values() {
+        // the fingerprint only says they might be the same, so compare them
+        // byte-for-byte against the first one, and go again with whatever
+        // didn't match
+        while candidates.len() > 1 {
+            let first = candidates[0];
+            let reference = contents(files[first])?;
+
+            let mut group = vec![first];
+            let mut rest = Vec::new();
+            for &ix in &candidates[1..] {
+                if contents(files[ix])? == reference {
+                    group.push(ix);
+                } else {
+                    rest.push(ix);
+                }
+            }
+
+            // the shared constant has to hold the bytes which were compared
+            if group.len() > 1 {
+                if let Cow::Owned(bytes) = reference {
+                    files[first].embed(bytes);
+                }
+                groups.push(group);
+            }
+            candidates = rest;
+        }
+    }
+
+    // number the constants in embedding order so the output is reproducible
+    groups.sort_unstable_by_key(|group| group[0]);
+
+    let mut definitions = TokenStream::new();
+    for (index, group) in groups.iter().enumerate() {
+        let name = shared_ident(index);
+        let contents = files[group[0]].contents();
+        definitions.extend(quote!(const #name: &[u8] = #contents;));
+
+        for &ix in group {
+            files[ix].share(index);
+        }
+    }
+
+    Ok(definitions)
+}
+
+/// The file's contents, only going back to the disk if the macro hasn't read
+/// them already.
+fn contents(file: &File) -> Result<Cow<'_, [u8]>, Error> {
+    match file.snapshot() {
+        Some(bytes) => Ok(Cow::Borrowed(bytes)),
+        None => file::read(file.abs_path()).map(Cow::Owned),
+    }
+}
+
+#[cfg(any(feature = "hash", feature = "build-id"))]
+type Fingerprint = crate::hash::Hash;
+
+/// Every file has already been hashed.
+#[cfg(any(feature = "hash", feature = "build-id"))]
+fn fingerprint(file: &File) -> Result<Fingerprint, Error> {
+    Ok(*file.hash())
+}
+
+/// The length and a (non-cryptographic) hash of the contents.
+#[cfg(not(any(feature = "hash", feature = "build-id")))]
+type Fingerprint = (u64, u64);
+
+/// Hash the file's contents, streaming them from the disk if the macro
+/// hasn't read them already.
+#[cfg(not(any(feature = "hash", feature = "build-id")))]
+fn fingerprint(file: &File) -> Result<Fingerprint, Error> {
+    use crate::file::{io_error, retry_if_shared};
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::Hasher;
+    use std::io::Read;
+
+    let mut hasher = DefaultHasher::new();
+
+    if let Some(bytes) = file.snapshot() {
+        hasher.write(bytes);
+        return Ok((bytes.len() as u64, hasher.finish()));
+    }
+
+    let path = file.abs_path();
+    let mut reader =
+        retry_if_shared(|| std::fs::File::open(path)).map_err(|e| io_error(path, e))?;
+    let mut buffer = vec![0; 64 * 1024];
+    let mut len = 0;
+
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok((len, hasher.finish())),
+            Ok(n) => {
+                hasher.write(&buffer[..n]);
+                len += n as u64;
+            }
+            Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(io_error(path, e)),
+        }
+    }
+}
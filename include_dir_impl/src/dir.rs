@@ -1,5 +1,8 @@
-use crate::file::File;
-use anyhow::{self, format_err, Context, Error};
+use crate::file::{intern, io_error, path_to_string, path_tokens, retry_if_shared, File, Vanished};
+#[cfg(any(feature = "hash", feature = "build-id"))]
+use crate::hash::{self, Hash};
+use crate::options::{format_size, Duplicates, NonUtf8Names, Options, Symlinks, VanishedFiles};
+use anyhow::{self, format_err, Error};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use std::path::{Path, PathBuf};
@@ -7,50 +10,523 @@ use std::path::{Path, PathBuf};
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct Dir {
     root_rel_path: PathBuf,
-    abs_path: PathBuf,
+    /// Where the directory was read from. There is more than one when
+    /// several directories passed to the macro were merged together.
+    sources: Vec<PathBuf>,
     files: Vec<File>,
     dirs: Vec<Dir>,
+    #[cfg(any(feature = "hash", feature = "build-id"))]
+    hash: Hash,
+    /// Where the stored path is in the table of every path.
+    path_range: Option<(usize, usize)>,
+    /// When the macro ran, in seconds since the Unix epoch.
+    #[cfg(feature = "source-info")]
+    embedded_at: Option<u64>,
 }
 
 impl Dir {
-    pub fn from_disk<Q: AsRef<Path>, P: Into<PathBuf>>(root: Q, path: P) -> Result<Dir, Error> {
-        let abs_path = path.into();
-        let root = root.as_ref();
+    pub fn from_disk<Q: AsRef<Path>, P: Into<PathBuf>>(
+        root: Q,
+        path: P,
+        options: &Options,
+        progress: &mut Progress,
+    ) -> Result<Dir, Error> {
+        Dir::load(root.as_ref(), path.into(), options, progress)
+    }
 
-        let root_rel_path = abs_path.strip_prefix(&root).unwrap().to_path_buf();
+    /// Recursively load a directory, keeping track of everything loaded so
+    /// far in `progress`.
+    fn load(
+        root: &Path,
+        abs_path: PathBuf,
+        options: &Options,
+        progress: &mut Progress,
+    ) -> Result<Dir, Error> {
+        let root_rel_path = options.stored_path(abs_path.strip_prefix(root).unwrap());
 
         if !abs_path.exists() {
+            // it was there when its parent was listed
+            if abs_path != root {
+                return Err(Vanished(abs_path).into());
+            }
             return Err(format_err!("The directory doesn't exist"));
         }
 
+        let canonical = abs_path.canonicalize().map_err(|e| match e.kind() {
+            // deleted since the `exists()` check above
+            std::io::ErrorKind::NotFound if abs_path != root => Vanished(abs_path.clone()).into(),
+            _ => Error::new(e).context(format!("Couldn't resolve \"{}\"", abs_path.display())),
+        })?;
+        if let Some(ancestor) = progress.ancestors.iter().find(|a| **a == canonical) {
+            return Err(format_err!(
+                "Following the symlink at \"{}\" leads back to \"{}\"",
+                abs_path.display(),
+                ancestor.display()
+            ));
+        }
+        if let Some(max_depth) = options.max_depth {
+            let depth = progress.ancestors.len();
+            if depth > max_depth {
+                return Err(format_err!(
+                    "\"{}\" is nested {} directories deep, which is more than `max_depth = {}`",
+                    abs_path.display(),
+                    depth,
+                    max_depth
+                ));
+            }
+        }
+
         let mut files = Vec::new();
         let mut dirs = Vec::new();
 
-        for entry in abs_path.read_dir().context("Couldn't read the directory")? {
-            let entry = entry?.path();
+        let mut entries = abs_path
+            .read_dir()
+            .and_then(|entries| {
+                entries
+                    .map(|entry| entry.map(|e| e.path()))
+                    .collect::<std::io::Result<Vec<_>>>()
+            })
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound => Vanished(abs_path.clone()).into(),
+                _ => Error::new(e).context(format!(
+                    "Couldn't read the directory \"{}\"",
+                    abs_path.display()
+                )),
+            })?;
+        // visit entries in a consistent order so errors are reproducible
+        entries.sort();
+
+        // only once the directory is known to exist, because a vanished one
+        // returns early and `vanished = "skip"` carries on with its siblings
+        progress.ancestors.push(canonical);
+
+        for entry in entries {
+            let entry_rel_path = entry.strip_prefix(root).unwrap();
+
+            if options.is_excluded(entry_rel_path) {
+                continue;
+            }
+
+            let is_utf8 = entry.file_name().and_then(|n| n.to_str()).is_some();
+            if !is_utf8 {
+                match options.non_utf8_names {
+                    NonUtf8Names::Error => {
+                        return Err(format_err!(
+                            "The name of \"{}\" isn't valid UTF-8, use `non_utf8_names = \"skip\"` or `non_utf8_names = \"lossy\"` to include the rest of the directory",
+                            entry.display()
+                        ))
+                    }
+                    NonUtf8Names::Skip => {
+                        progress.skipped.push(entry);
+                        continue;
+                    }
+                    NonUtf8Names::Lossy => {}
+                }
+            }
+
+            // everything below uses this one look at the entry, so the
+            // decisions all agree even if it changes in the meantime
+            let meta = match retry_if_shared(|| entry.symlink_metadata()) {
+                Ok(meta) => meta,
+                Err(e) => {
+                    skip_if_vanished(io_error(&entry, e), options)?;
+                    continue;
+                }
+            };
 
-            if entry.is_file() {
-                files.push(File::from_disk(&root, entry)?);
-            } else if entry.is_dir() {
-                dirs.push(Dir::from_disk(&root, entry)?);
+            let meta = if meta.file_type().is_symlink() {
+                match options.symlinks {
+                    Symlinks::Follow => match entry.metadata() {
+                        Ok(target) => target,
+                        Err(_) => {
+                            return Err(format_err!(
+                                "\"{}\" is a symlink to \"{}\", which doesn't exist",
+                                entry.display(),
+                                entry.read_link()?.display()
+                            ))
+                        }
+                    },
+                    Symlinks::Skip => continue,
+                    Symlinks::Error => {
+                        return Err(format_err!(
+                            "\"{}\" is a symlink, which isn't allowed by `symlinks = \"error\"`",
+                            entry.display()
+                        ))
+                    }
+                }
+            } else {
+                meta
+            };
+
+            if meta.is_file() {
+                if !options.is_included(entry_rel_path) {
+                    continue;
+                }
+
+                progress.check_size(&entry, meta.len(), options)?;
+                match File::from_disk(root, entry, &meta, options) {
+                    Ok(file) => files.push(file),
+                    Err(e) => skip_if_vanished(e, options)?,
+                }
+            } else if meta.is_dir() {
+                let dir = match Dir::load(root, entry, options, progress) {
+                    Ok(dir) => dir,
+                    Err(e) => {
+                        skip_if_vanished(e, options)?;
+                        continue;
+                    }
+                };
+
+                // filtering may leave a directory with nothing to embed
+                if options.keep_empty_dirs() || !dir.is_empty() {
+                    dirs.push(dir);
+                }
             }
         }
 
-        Ok(Dir {
+        progress.ancestors.pop();
+
+        let dir = Dir::new(root_rel_path, vec![abs_path], files, dirs);
+        dir.check_unique_names()?;
+
+        Ok(dir)
+    }
+
+    /// Make sure no two children ended up with the same name, which can
+    /// happen when names are converted lossily.
+    fn check_unique_names(&self) -> Result<(), Error> {
+        let mut names: Vec<(String, &Path)> = self
+            .files
+            .iter()
+            .map(|f| (path_to_string(f.root_rel_path()), f.abs_path()))
+            .chain(
+                self.dirs
+                    .iter()
+                    .map(|d| (path_to_string(&d.root_rel_path), d.sources[0].as_path())),
+            )
+            .collect();
+        names.sort();
+
+        for pair in names.windows(2) {
+            if pair[0].0 == pair[1].0 {
+                // debug formatting shows the bytes which made them clash
+                return Err(format_err!(
+                    "{:?} and {:?} would both be embedded as \"{}\"",
+                    pair[0].1,
+                    pair[1].1,
+                    pair[0].0
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn new(
+        root_rel_path: PathBuf,
+        sources: Vec<PathBuf>,
+        mut files: Vec<File>,
+        mut dirs: Vec<Dir>,
+    ) -> Dir {
+        // the runtime crate relies on entries being sorted by path, and
+        // siblings only differ in their last component so this compares the
+        // bytes of their names (as they will be stored, in case a name had
+        // to be converted lossily)
+        files.sort_by_cached_key(|f| path_to_string(f.root_rel_path()));
+        dirs.sort_by_cached_key(|d| path_to_string(&d.root_rel_path));
+
+        #[cfg(any(feature = "hash", feature = "build-id"))]
+        let hash = hash::hash_children(
+            files.iter().map(|f| (f.root_rel_path(), f.hash())),
+            dirs.iter().map(|d| (d.root_rel_path.as_path(), &d.hash)),
+        );
+
+        Dir {
             root_rel_path,
-            abs_path,
+            sources,
             files,
             dirs,
-        })
+            #[cfg(any(feature = "hash", feature = "build-id"))]
+            hash,
+            path_range: None,
+            #[cfg(feature = "source-info")]
+            embedded_at: None,
+        }
+    }
+
+    /// Record when this directory and everything inside it was embedded.
+    #[cfg(feature = "source-info")]
+    pub fn stamp(&mut self, embedded_at: u64) {
+        self.embedded_at = Some(embedded_at);
+
+        for dir in &mut self.dirs {
+            dir.stamp(embedded_at);
+        }
+    }
+
+    /// Add the paths of this directory and everything inside it to the table
+    /// of every path, in the same order as `Dir::walk()` visits them.
+    pub fn intern_paths(&mut self, table: &mut String) {
+        self.path_range = Some(intern(table, &path_to_string(&self.root_rel_path)));
+        intern_children(&mut self.files, &mut self.dirs, table);
+    }
+
+    /// Every file inside this directory, recursively, in the order they are
+    /// embedded.
+    pub fn all_files(&self) -> Vec<&File> {
+        let mut files: Vec<&File> = self.files.iter().collect();
+
+        for dir in &self.dirs {
+            files.extend(dir.all_files());
+        }
+
+        files
+    }
+
+    /// Every file inside this directory, recursively, in the order they are
+    /// embedded.
+    pub fn all_files_mut(&mut self) -> Vec<&mut File> {
+        let mut files: Vec<&mut File> = self.files.iter_mut().collect();
+
+        for dir in &mut self.dirs {
+            files.extend(dir.all_files_mut());
+        }
+
+        files
+    }
+
+    /// Lay `overlay` on top of this directory, for when several directories
+    /// are passed to the macro.
+    ///
+    /// Sub-directories which appear in both are merged recursively. Any
+    /// other path which appears in both is an error, unless
+    /// `duplicates = "override"` lets the overlay win.
+    pub fn merge(self, overlay: Dir, options: &Options) -> Result<Dir, Error> {
+        let Dir {
+            root_rel_path,
+            mut sources,
+            mut files,
+            mut dirs,
+            ..
+        } = self;
+        sources.extend(overlay.sources);
+
+        for file in overlay.files {
+            let path = file.root_rel_path();
+
+            if let Some(ix) = files.iter().position(|f| f.root_rel_path() == path) {
+                check_duplicate(files[ix].abs_path(), file.abs_path(), path, options)?;
+                files.remove(ix);
+            } else if let Some(ix) = dirs.iter().position(|d| d.root_rel_path == path) {
+                check_duplicate(&dirs[ix].sources[0], file.abs_path(), path, options)?;
+                dirs.remove(ix);
+            }
+
+            files.push(file);
+        }
+
+        for dir in overlay.dirs {
+            if let Some(ix) = dirs
+                .iter()
+                .position(|d| d.root_rel_path == dir.root_rel_path)
+            {
+                let base = dirs.remove(ix);
+                dirs.push(base.merge(dir, options)?);
+                continue;
+            }
+
+            if let Some(ix) = files
+                .iter()
+                .position(|f| f.root_rel_path() == dir.root_rel_path)
+            {
+                check_duplicate(
+                    files[ix].abs_path(),
+                    &dir.sources[0],
+                    &dir.root_rel_path,
+                    options,
+                )?;
+                files.remove(ix);
+            }
+
+            dirs.push(dir);
+        }
+
+        Ok(Dir::new(root_rel_path, sources, files, dirs))
+    }
+
+    /// Ask the compiler to re-run the macro when entries are added to,
+    /// removed from, or renamed inside this directory or any of its
+    /// sub-directories, or when any of its files change.
+    ///
+    /// Files embedded with `include_bytes!()` would be picked up anyway, but
+    /// this saves inlined and compressed files from needing an unused one.
+    #[cfg(feature = "nightly")]
+    pub fn track(&self) {
+        for source in &self.sources {
+            proc_macro::tracked::path(source.display().to_string());
+        }
+
+        for file in &self.files {
+            file.track();
+        }
+
+        for dir in &self.dirs {
+            dir.track();
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.dirs.is_empty()
+    }
+}
+
+/// Make sure it's okay for `overlay` to replace `base` when merging
+/// directories.
+fn check_duplicate(
+    base: &Path,
+    overlay: &Path,
+    stored: &Path,
+    options: &Options,
+) -> Result<(), Error> {
+    match options.duplicates {
+        Duplicates::Override => Ok(()),
+        Duplicates::Error => Err(format_err!(
+            "\"{}\" and \"{}\" would both be embedded as \"{}\", use `duplicates = \"override\"` to let the last directory win",
+            base.display(),
+            overlay.display(),
+            path_to_string(stored)
+        )),
+    }
+}
+
+/// Carry on without an entry which was deleted while the directory was being
+/// read if `vanished = "skip"` allows it, otherwise fail with `error`.
+fn skip_if_vanished(error: Error, options: &Options) -> Result<(), Error> {
+    match options.vanished {
+        VanishedFiles::Skip if error.is::<Vanished>() => Ok(()),
+        _ => Err(error),
+    }
+}
+
+/// What has been loaded so far, used to enforce the macro's limits.
+#[derive(Debug, Default)]
+pub(crate) struct Progress {
+    /// The canonical paths of every directory above the one being loaded, so
+    /// we can detect when a symlink would send us around in circles.
+    ancestors: Vec<PathBuf>,
+    /// The size of every file loaded so far.
+    total_size: u64,
+    /// Entries left out because of `non_utf8_names = "skip"`.
+    pub skipped: Vec<PathBuf>,
+}
+
+impl Progress {
+    /// Make sure embedding the file at `path` stays within the size limits.
+    fn check_size(&mut self, path: &Path, size: u64, options: &Options) -> Result<(), Error> {
+        if let Some(max) = options.max_file_size {
+            if size > max {
+                return Err(format_err!(
+                    "\"{}\" is {}, which is bigger than `max_file_size` ({})",
+                    path.display(),
+                    format_size(size),
+                    format_size(max)
+                ));
+            }
+        }
+
+        self.total_size += size;
+        if let Some(max) = options.max_total_size {
+            if self.total_size > max {
+                return Err(format_err!(
+                    "Embedding \"{}\" ({}) brings the total to {}, which is more than `max_total_size` ({})",
+                    path.display(),
+                    format_size(size),
+                    format_size(self.total_size),
+                    format_size(max)
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Intern the paths of a directory's entries, merging the files and
+/// directories by name like `Dir::entries()` does.
+fn intern_children(files: &mut [File], dirs: &mut [Dir], table: &mut String) {
+    let (mut files, mut dirs) = (files.iter_mut().peekable(), dirs.iter_mut().peekable());
+
+    loop {
+        let file_first = match (files.peek(), dirs.peek()) {
+            (Some(f), Some(d)) => {
+                path_to_string(f.root_rel_path()) < path_to_string(&d.root_rel_path)
+            }
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => return,
+        };
+
+        if file_first {
+            files.next().unwrap().intern_path(table);
+        } else {
+            dirs.next().unwrap().intern_paths(table);
+        }
     }
 }
 
 impl ToTokens for Dir {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let root_rel_path = self.root_rel_path.display().to_string();
+        let root_rel_path = path_tokens(&path_to_string(&self.root_rel_path), self.path_range);
         let files = &self.files;
         let dirs = &self.dirs;
 
+        #[cfg(feature = "hash")]
+        let hash = {
+            let hash = &self.hash;
+            quote!(hash: [#(#hash),*],)
+        };
+        #[cfg(not(feature = "hash"))]
+        let hash = quote!();
+
+        // a merged directory has no single place to reload it from
+        #[cfg(feature = "hot-reload")]
+        let source = match *self.sources {
+            [ref abs_path] => {
+                let abs_path = abs_path.display().to_string();
+                // keep the path out of release binaries
+                quote!(source: if cfg!(debug_assertions) { #abs_path } else { "" },)
+            }
+            _ => quote!(source: "",),
+        };
+        #[cfg(not(feature = "hot-reload"))]
+        let source = quote!();
+
+        #[cfg(feature = "source-info")]
+        let source_info = {
+            let source_path = match *self.sources {
+                [ref abs_path] => abs_path.display().to_string(),
+                _ => String::new(),
+            };
+            let embedded_at = match self.embedded_at {
+                Some(secs) => quote!(Some(#secs)),
+                None => quote!(None),
+            };
+            quote!(source_path: #source_path, embedded_at: #embedded_at,)
+        };
+        #[cfg(not(feature = "source-info"))]
+        let source_info = quote!();
+
+        #[cfg(feature = "build-id")]
+        let build_id = {
+            let build_id: String = self.hash[..8]
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect();
+            quote!(build_id: #build_id,)
+        };
+        #[cfg(not(feature = "build-id"))]
+        let build_id = quote!();
+
         let tok = quote! {
             $crate::Dir {
                 path: #root_rel_path,
@@ -60,6 +536,10 @@ impl ToTokens for Dir {
                 dirs: &[#(
                     #dirs
                  ),*],
+                #hash
+                #source
+                #source_info
+                #build_id
             }
         };
 
@@ -1,40 +1,432 @@
+#[cfg(any(feature = "hash", feature = "build-id"))]
+use crate::hash::{self, Hash};
+#[cfg(feature = "metadata")]
+use crate::metadata::Metadata;
+use crate::options::Options;
 use anyhow::Error;
-use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use std::fmt::{self, Display, Formatter};
+use std::fs::Metadata as FsMetadata;
+use std::io;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct File {
     root_rel_path: PathBuf,
     abs_path: PathBuf,
+    #[cfg(feature = "metadata")]
+    metadata: Metadata,
+    #[cfg(any(feature = "hash", feature = "build-id"))]
+    hash: Hash,
+    #[cfg(feature = "compression")]
+    compressed: Option<Vec<u8>>,
+    /// The contents the macro read, if it needed to. Everything worked out
+    /// from them (the hash, the compressed bytes, the length) comes from this
+    /// one read, so they agree even if the file changes while it is read.
+    snapshot: Option<Vec<u8>>,
+    /// Whether the contents are written into the generated code instead of
+    /// using `include_bytes!()`, which happens whenever the macro read them
+    /// itself: the file is smaller than the `inline_threshold`, its path
+    /// isn't valid UTF-8, or they were needed for hashing or compression.
+    inline: bool,
+    /// Which of the constants generated by `deduplicate = true` holds this
+    /// file's contents, if it has the same contents as another file.
+    shared: Option<usize>,
+    /// Where the stored path is in the table of every path.
+    path_range: Option<(usize, usize)>,
+    /// Attributes from the `attributes` manifest, sorted by key.
+    #[cfg(feature = "attributes")]
+    attrs: Vec<(String, String)>,
 }
 
 impl File {
-    pub fn from_disk<Q: AsRef<Path>, P: Into<PathBuf>>(root: Q, path: P) -> Result<File, Error> {
+    /// Load the file at `path`, given the metadata read while listing its
+    /// directory. Fails with [`Vanished`] if the file has been deleted since
+    /// then.
+    pub fn from_disk<Q: AsRef<Path>, P: Into<PathBuf>>(
+        root: Q,
+        path: P,
+        meta: &FsMetadata,
+        options: &Options,
+    ) -> Result<File, Error> {
         let abs_path = path.into();
         let root = root.as_ref();
 
-        let root_rel_path = abs_path.strip_prefix(&root).unwrap().to_path_buf();
+        let root_rel_path = options.stored_path(abs_path.strip_prefix(root).unwrap());
+
+        // Small files are written straight into the generated code, and so
+        // are files whose paths `include_bytes!()` can't be given.
+        let small = meta.len() < options.inline_threshold || abs_path.to_str().is_none();
+
+        // most of the time rustc is the one reading the file
+        let snapshot = if small
+            || cfg!(any(feature = "hash", feature = "build-id"))
+            || (cfg!(feature = "compression") && options.compress)
+        {
+            Some(read(&abs_path)?)
+        } else {
+            None
+        };
+
+        // Once the macro has read a file, `include_bytes!()` reading it again
+        // could see something different (even with the same length). Embed
+        // what we read so it always matches the hash and compressed bytes.
+        let inline = snapshot.is_some();
+
+        #[cfg(any(feature = "hash", feature = "build-id"))]
+        let contents = snapshot.as_deref().unwrap_or_default();
 
         Ok(File {
+            inline,
+            shared: None,
+            path_range: None,
+            #[cfg(feature = "attributes")]
+            attrs: Vec::new(),
+            #[cfg(feature = "metadata")]
+            metadata: {
+                // the length should match what actually gets embedded
+                let len = snapshot.as_ref().map_or(meta.len(), |s| s.len() as u64);
+                Metadata::from_disk(meta, len)?
+            },
+            #[cfg(any(feature = "hash", feature = "build-id"))]
+            hash: hash::hash_contents(contents),
+            #[cfg(feature = "compression")]
+            compressed: if options.compress {
+                compress(snapshot.as_deref().unwrap_or_default())
+            } else {
+                None
+            },
+            snapshot,
             abs_path,
             root_rel_path,
         })
     }
+
+    pub fn root_rel_path(&self) -> &Path {
+        &self.root_rel_path
+    }
+
+    pub fn abs_path(&self) -> &Path {
+        &self.abs_path
+    }
+
+    #[cfg(any(feature = "hash", feature = "build-id"))]
+    pub fn hash(&self) -> &Hash {
+        &self.hash
+    }
+
+    /// The contents the macro read, if it needed to.
+    pub fn snapshot(&self) -> Option<&[u8]> {
+        self.snapshot.as_deref()
+    }
+
+    /// Embed `bytes`, which were read after the file was loaded, instead of
+    /// letting `include_bytes!()` read the file again.
+    pub fn embed(&mut self, bytes: Vec<u8>) {
+        #[cfg(feature = "metadata")]
+        self.metadata.set_len(bytes.len() as u64);
+
+        self.snapshot = Some(bytes);
+        self.inline = true;
+    }
+
+    /// Add the stored path to the table of every path.
+    pub fn intern_path(&mut self, table: &mut String) {
+        self.path_range = Some(intern(table, &path_to_string(&self.root_rel_path)));
+    }
+
+    /// Set the attributes from the `attributes` manifest, which must already
+    /// be sorted by key.
+    #[cfg(feature = "attributes")]
+    pub fn set_attrs(&mut self, attrs: Vec<(String, String)>) {
+        self.attrs = attrs;
+    }
+
+    /// Get the contents from the `index`'th shared constant instead of
+    /// embedding them again.
+    pub fn share(&mut self, index: usize) {
+        self.shared = Some(index);
+    }
+
+    /// The expression for the `contents` field, ignoring any sharing.
+    pub fn contents(&self) -> TokenStream {
+        let keep_tracked = self.keep_tracked();
+
+        // Let rustc read the file itself rather than inlining its bytes into
+        // the token stream. This keeps the expansion small, no matter how
+        // big the file is, and rustc tracks the file as a build input.
+        let contents = match self.snapshot {
+            Some(ref bytes) if self.inline => {
+                let bytes = proc_macro2::Literal::byte_string(bytes);
+                quote!({
+                    #keep_tracked
+                    #bytes
+                })
+            }
+            _ => {
+                let abs_path = self.abs_path.display().to_string();
+                quote!(include_bytes!(#abs_path))
+            }
+        };
+
+        #[cfg(feature = "compression")]
+        if let Some(ref bytes) = self.compressed {
+            let bytes = proc_macro2::Literal::byte_string(bytes);
+            return quote!({
+                #keep_tracked
+                #bytes
+            });
+        }
+
+        contents
+    }
+
+    /// An unused `include_bytes!()`, so rustc still tracks the file when its
+    /// contents come from somewhere else.
+    ///
+    /// Nightly compilers track the file with [`File::track()`] instead, and
+    /// paths which aren't valid UTF-8 can't be tracked at all.
+    fn keep_tracked(&self) -> TokenStream {
+        match self.abs_path.to_str() {
+            Some(abs_path) if !cfg!(feature = "nightly") => {
+                quote!(
+                    const _: &[u8] = include_bytes!(#abs_path);
+                )
+            }
+            _ => TokenStream::new(),
+        }
+    }
+
+    /// Ask the compiler to re-run the macro when the file changes.
+    #[cfg(feature = "nightly")]
+    pub fn track(&self) {
+        if let Some(abs_path) = self.abs_path.to_str() {
+            proc_macro::tracked::path(abs_path);
+        }
+    }
 }
 
 impl ToTokens for File {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let root_rel_path = self.root_rel_path.display().to_string();
-        let abs_path = self.abs_path.display().to_string();
+        let root_rel_path = path_tokens(&path_to_string(&self.root_rel_path), self.path_range);
+
+        #[cfg(feature = "metadata")]
+        let metadata = {
+            let metadata = &self.metadata;
+            quote!(metadata: Some(#metadata),)
+        };
+        #[cfg(not(feature = "metadata"))]
+        let metadata = quote!();
+
+        #[cfg(feature = "hash")]
+        let hash = {
+            let hash = &self.hash;
+            quote!(hash: [#(#hash),*],)
+        };
+        #[cfg(not(feature = "hash"))]
+        let hash = quote!();
+
+        let contents = match self.shared {
+            Some(index) => {
+                let keep_tracked = self.keep_tracked();
+                let shared = shared_ident(index);
+                quote!({
+                    #keep_tracked
+                    #shared
+                })
+            }
+            None => self.contents(),
+        };
+
+        #[cfg(feature = "compression")]
+        let compressed = if self.compressed.is_some() {
+            quote!(compressed: true,)
+        } else {
+            quote!(compressed: false,)
+        };
+        #[cfg(not(feature = "compression"))]
+        let compressed = quote!();
+
+        #[cfg(feature = "attributes")]
+        let attrs = {
+            let keys = self.attrs.iter().map(|(key, _)| key);
+            let values = self.attrs.iter().map(|(_, value)| value);
+            quote!(attrs: &[#((#keys, #values)),*],)
+        };
+        #[cfg(not(feature = "attributes"))]
+        let attrs = quote!();
 
         let tok = quote! {
             $crate::File {
                 path: #root_rel_path,
-                contents: include_bytes!(#abs_path),
+                contents: #contents,
+                #metadata
+                #hash
+                #compressed
+                #attrs
             }
         };
 
         tok.to_tokens(tokens);
     }
 }
+
+/// A file or directory which was deleted between listing its parent
+/// directory and reading it, e.g. by another process cleaning up a build
+/// directory.
+#[derive(Debug)]
+pub(crate) struct Vanished(pub PathBuf);
+
+impl Display for Vanished {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "\"{}\" disappeared while it was being embedded, use `vanished = \"skip\"` to leave out anything deleted in the middle of a build",
+            self.0.display()
+        )
+    }
+}
+
+impl std::error::Error for Vanished {}
+
+/// Read a whole file, failing with [`Vanished`] if it doesn't exist.
+///
+/// The contents are whatever a single `fs::read()` saw, even if the file was
+/// being written to at the same time.
+pub(crate) fn read(path: &Path) -> Result<Vec<u8>, Error> {
+    retry_if_shared(|| std::fs::read(path)).map_err(|e| io_error(path, e))
+}
+
+/// Turn an error from reading `path` into a [`Vanished`] if it's because the
+/// path no longer exists.
+pub(crate) fn io_error(path: &Path, error: io::Error) -> Error {
+    if error.kind() == io::ErrorKind::NotFound {
+        Error::new(Vanished(path.to_path_buf()))
+    } else {
+        Error::new(error).context(format!("Couldn't read \"{}\"", path.display()))
+    }
+}
+
+/// Run `op` again if it failed because another process has the file open
+/// without sharing it, which is common on Windows while editors, virus
+/// scanners, or indexers look at a file which was just written.
+pub(crate) fn retry_if_shared<T>(op: impl Fn() -> io::Result<T>) -> io::Result<T> {
+    match op() {
+        Err(ref e) if is_sharing_violation(e) => {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            op()
+        }
+        result => result,
+    }
+}
+
+#[cfg(windows)]
+fn is_sharing_violation(error: &io::Error) -> bool {
+    // ERROR_SHARING_VIOLATION and ERROR_LOCK_VIOLATION
+    matches!(error.raw_os_error(), Some(32) | Some(33))
+}
+
+#[cfg(not(windows))]
+fn is_sharing_violation(_error: &io::Error) -> bool {
+    false
+}
+
+/// The name of the constant holding every stored path, one after the other.
+pub(crate) fn paths_ident() -> Ident {
+    format_ident!("__INCLUDE_DIR_PATHS")
+}
+
+/// Append `path` to the table of every path, returning where it ended up.
+pub(crate) fn intern(table: &mut String, path: &str) -> (usize, usize) {
+    let start = table.len();
+    table.push_str(path);
+    (start, table.len())
+}
+
+/// The expression for a stored path, taking it from the table of every path
+/// when it has been added to one.
+pub(crate) fn path_tokens(path: &str, range: Option<(usize, usize)>) -> TokenStream {
+    match range {
+        Some((start, end)) => {
+            let table = paths_ident();
+            quote!($crate::__path(#table, #start, #end))
+        }
+        None => quote!(#path),
+    }
+}
+
+/// The name of the `index`'th constant generated by `deduplicate = true`.
+pub(crate) fn shared_ident(index: usize) -> Ident {
+    format_ident!("__INCLUDE_DIR_SHARED_{}", index)
+}
+
+/// Files smaller than this are never compressed.
+#[cfg(feature = "compression")]
+const COMPRESSION_THRESHOLD: usize = 512;
+
+/// Compress a file's contents with DEFLATE, as long as it's big enough to be
+/// worth it and actually gets smaller.
+#[cfg(feature = "compression")]
+fn compress(contents: &[u8]) -> Option<Vec<u8>> {
+    if contents.len() < COMPRESSION_THRESHOLD {
+        return None;
+    }
+
+    let compressed = miniz_oxide::deflate::compress_to_vec(contents, 10);
+
+    if compressed.len() < contents.len() {
+        Some(compressed)
+    } else {
+        None
+    }
+}
+
+/// Convert a path to the `/`-separated form used in the generated code, no
+/// matter which platform the macro runs on.
+pub(crate) fn path_to_string(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The generated `contents` for one of the files in the `sizes` fixture.
+    fn contents(name: &str, options: &str) -> String {
+        let root =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../include_dir/tests/fixtures/sizes");
+        let path = root.join(name);
+        let meta = std::fs::metadata(&path).unwrap();
+        let options: Options = syn::parse_str(&format!("\"sizes\"{}", options)).unwrap();
+
+        File::from_disk(&root, path, &meta, &options)
+            .unwrap()
+            .contents()
+            .to_string()
+    }
+
+    #[test]
+    fn small_files_are_written_into_the_generated_code() {
+        let tiny = contents("tiny.txt", "");
+        assert!(tiny.contains(r#"b"tiny file\n""#), "{}", tiny);
+    }
+
+    #[cfg(not(any(feature = "hash", feature = "build-id")))]
+    #[test]
+    fn big_files_are_read_by_the_compiler() {
+        let large = contents("large.txt", "");
+        assert!(large.starts_with("include_bytes !"), "{}", large);
+    }
+
+    #[cfg(not(any(feature = "hash", feature = "build-id")))]
+    #[test]
+    fn a_threshold_of_zero_never_inlines() {
+        let tiny = contents("tiny.txt", ", inline_threshold = 0");
+        assert!(tiny.starts_with("include_bytes !"), "{}", tiny);
+        assert!(!tiny.contains("tiny file"), "{}", tiny);
+    }
+}
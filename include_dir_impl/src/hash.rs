@@ -0,0 +1,41 @@
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+pub(crate) type Hash = [u8; 32];
+
+/// Hash a file's contents.
+pub(crate) fn hash_contents(contents: &[u8]) -> Hash {
+    Sha256::digest(contents).into()
+}
+
+/// Combine the hashes of a directory's children into a single hash.
+///
+/// Each child contributes its kind, its name (not its full path, so moving a
+/// directory doesn't change its hash), and its own hash. The children must
+/// already be sorted so the result doesn't depend on the order the
+/// filesystem listed them in.
+pub(crate) fn hash_children<'a, F, D>(files: F, dirs: D) -> Hash
+where
+    F: IntoIterator<Item = (&'a Path, &'a Hash)>,
+    D: IntoIterator<Item = (&'a Path, &'a Hash)>,
+{
+    let mut hasher = Sha256::new();
+
+    for (kind, (path, hash)) in files
+        .into_iter()
+        .map(|f| (b'f', f))
+        .chain(dirs.into_iter().map(|d| (b'd', d)))
+    {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy())
+            .unwrap_or_default();
+
+        hasher.update([kind]);
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update(hash);
+    }
+
+    hasher.finalize().into()
+}
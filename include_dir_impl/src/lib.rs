@@ -2,6 +2,11 @@
 //!
 //! [include_dir!()]: https://github.com/Michael-F-Bryan/include_dir
 
+#![cfg_attr(
+    feature = "nightly",
+    feature(proc_macro_tracked_path, proc_macro_diagnostic)
+)]
+
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
@@ -9,29 +14,197 @@ use proc_macro_hack::proc_macro_hack;
 use quote::quote;
 use syn::{parse_macro_input, LitStr};
 
-use crate::dir::Dir;
-use std::env;
-use std::path::PathBuf;
+use crate::dir::{Dir, Progress};
+use crate::options::Options;
 
+mod asset_path;
+#[cfg(feature = "attributes")]
+mod attributes;
+mod dedup;
 mod dir;
 mod file;
+#[cfg(any(feature = "hash", feature = "build-id"))]
+mod hash;
+#[cfg(feature = "metadata")]
+mod metadata;
+mod options;
+mod require;
+#[cfg(any(feature = "metadata", feature = "source-info"))]
+mod source_date_epoch;
+#[cfg(feature = "source-info")]
+mod source_info;
 
 #[proc_macro_hack]
 pub fn include_dir(input: TokenStream) -> TokenStream {
-    let input: LitStr = parse_macro_input!(input as LitStr);
-    let crate_root = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let options = parse_macro_input!(input as Options);
 
-    let path = PathBuf::from(crate_root).join(input.value());
+    let mut progress = Progress::default();
+    let mut merged: Option<Dir> = None;
 
-    if !path.exists() {
-        panic!("\"{}\" doesn't exist", path.display());
+    for lit in &options.paths {
+        let dir = match load(lit, &options, &mut progress) {
+            Ok(dir) => dir,
+            Err(e) => return e.to_compile_error().into(),
+        };
+
+        merged = match merged {
+            None => Some(dir),
+            Some(base) => match base.merge(dir, &options) {
+                Ok(merged) => Some(merged),
+                Err(e) => return error(lit, format!("{:#}", e)),
+            },
+        };
+    }
+
+    let mut dir = merged.expect("The parser always finds at least one path");
+
+    #[cfg(feature = "attributes")]
+    let keep_manifest_tracked = match options.attributes {
+        Some(ref lit) => match apply_attributes(lit, &mut dir, &options) {
+            Ok(tokens) => tokens,
+            Err(e) => return e.to_compile_error().into(),
+        },
+        None => proc_macro2::TokenStream::new(),
+    };
+    #[cfg(not(feature = "attributes"))]
+    let keep_manifest_tracked = proc_macro2::TokenStream::new();
+
+    if let Err(msg) = require::check(&dir, &options) {
+        // the problem is with the whole invocation, not one of its paths
+        return syn::Error::new(proc_macro2::Span::call_site(), msg)
+            .to_compile_error()
+            .into();
+    }
+
+    #[cfg(feature = "source-info")]
+    match source_info::embedded_at() {
+        Ok(embedded_at) => dir.stamp(embedded_at),
+        Err(e) => return error(&options.paths[0], format!("{:#}", e)),
     }
 
-    let path = path.canonicalize().expect("Can't normalize the path");
+    #[cfg(feature = "nightly")]
+    dir.track();
 
-    let dir = Dir::from_disk(&path, &path).expect("Couldn't load the directory");
+    let shared = if options.deduplicate {
+        match dedup::deduplicate(&mut dir) {
+            Ok(shared) => shared,
+            Err(e) => return error(&options.paths[0], format!("{:#}", e)),
+        }
+    } else {
+        proc_macro2::TokenStream::new()
+    };
 
-    TokenStream::from(quote! {
+    // Keep every path in one string, separate from the contents, so walking
+    // the tree doesn't touch the pages holding file contents.
+    let mut paths = String::new();
+    dir.intern_paths(&mut paths);
+    let paths_ident = file::paths_ident();
+
+    TokenStream::from(quote! {{
+        const #paths_ident: &str = #paths;
+        #keep_manifest_tracked
+        #shared
         #dir
-    })
+    }})
+}
+
+#[proc_macro_hack]
+pub fn include_dir_path(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as asset_path::Input);
+
+    match asset_path::expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Load one of the directories passed to the macro.
+fn load(lit: &LitStr, options: &Options, progress: &mut Progress) -> syn::Result<Dir> {
+    let path = options.resolved_path(lit)?;
+
+    if !path.exists() {
+        let msg = format!("\"{}\" doesn't exist", path.display());
+        return Err(syn::Error::new(lit.span(), msg));
+    }
+    if !path.is_dir() {
+        let msg = format!("\"{}\" isn't a directory", path.display());
+        return Err(syn::Error::new(lit.span(), msg));
+    }
+
+    let path = path.canonicalize().map_err(|e| {
+        let msg = format!("Couldn't resolve \"{}\": {}", path.display(), e);
+        syn::Error::new(lit.span(), msg)
+    })?;
+
+    let dir = Dir::from_disk(&path, &path, options, progress).map_err(|e| {
+        let msg = format!("Couldn't include \"{}\": {:#}", path.display(), e);
+        syn::Error::new(lit.span(), msg)
+    })?;
+
+    // stable compilers have no way to emit warnings
+    #[cfg(feature = "nightly")]
+    for skipped in &progress.skipped {
+        lit.span()
+            .unwrap()
+            .warning(format!(
+                "Skipping \"{}\" because its name isn't valid UTF-8",
+                skipped.display()
+            ))
+            .emit();
+    }
+    progress.skipped.clear();
+
+    Ok(dir)
+}
+
+/// Load the manifest given by `attributes = "..."` and attach its attributes
+/// to every file, returning whatever is needed to keep it tracked.
+#[cfg(feature = "attributes")]
+fn apply_attributes(
+    lit: &LitStr,
+    dir: &mut Dir,
+    options: &Options,
+) -> syn::Result<proc_macro2::TokenStream> {
+    use crate::options::UnmatchedAttributes;
+
+    let path = options.resolved_path(lit)?;
+    let manifest = attributes::Manifest::from_disk(path)
+        .map_err(|e| syn::Error::new(lit.span(), format!("{:#}", e)))?;
+
+    let unmatched = manifest.apply(dir, options);
+    for glob in &unmatched {
+        let msg = format!(
+            "\"{}\" in \"{}\" doesn't match any of the embedded files",
+            glob,
+            manifest.path().display()
+        );
+        match options.unmatched_attributes {
+            UnmatchedAttributes::Error => return Err(syn::Error::new(lit.span(), msg)),
+            // stable compilers have no way to emit warnings
+            #[cfg(feature = "nightly")]
+            UnmatchedAttributes::Warn => lit.span().unwrap().warning(msg).emit(),
+            #[cfg(not(feature = "nightly"))]
+            UnmatchedAttributes::Warn => {}
+        }
+    }
+
+    // the manifest isn't embedded, but editing it should still cause a
+    // rebuild
+    #[cfg(feature = "nightly")]
+    {
+        proc_macro::tracked::path(manifest.path().display().to_string());
+        Ok(proc_macro2::TokenStream::new())
+    }
+    #[cfg(not(feature = "nightly"))]
+    {
+        let path = manifest.path().display().to_string();
+        Ok(quote!(
+            const _: &[u8] = include_bytes!(#path);
+        ))
+    }
+}
+
+/// Report a problem with one of the directories, pointing at its path.
+fn error(lit: &LitStr, msg: String) -> TokenStream {
+    syn::Error::new(lit.span(), msg).to_compile_error().into()
 }
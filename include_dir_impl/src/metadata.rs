@@ -0,0 +1,81 @@
+use crate::source_date_epoch::source_date_epoch;
+use anyhow::Error;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use std::time::UNIX_EPOCH;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Metadata {
+    modified: u64,
+    len: u64,
+    mode: Option<u32>,
+}
+
+impl Metadata {
+    /// Gather the metadata to embed from what the filesystem said, using
+    /// `len` as the file's length so it matches the contents actually read.
+    pub fn from_disk(meta: &std::fs::Metadata, len: u64) -> Result<Metadata, Error> {
+        let mtime = meta
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        // files touched after SOURCE_DATE_EPOCH are clamped to it, older
+        // ones keep their real mtime
+        let modified = match source_date_epoch()? {
+            Some(epoch) => mtime.min(epoch),
+            None => mtime,
+        };
+
+        Ok(Metadata {
+            modified,
+            len,
+            mode: mode(meta),
+        })
+    }
+
+    /// Change the recorded length, when the contents embedded were read
+    /// again after the file was loaded.
+    pub fn set_len(&mut self, len: u64) {
+        self.len = len;
+    }
+}
+
+/// The file's Unix permission bits, when the `permissions` feature is
+/// enabled and the host supports them.
+#[cfg(all(unix, feature = "permissions"))]
+fn mode(meta: &std::fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+
+    Some(meta.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(all(unix, feature = "permissions")))]
+fn mode(_meta: &std::fs::Metadata) -> Option<u32> {
+    None
+}
+
+impl ToTokens for Metadata {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Metadata {
+            modified,
+            len,
+            mode,
+        } = *self;
+        let mode = match mode {
+            Some(mode) => quote!(Some(#mode)),
+            None => quote!(None),
+        };
+
+        let tok = quote! {
+            $crate::Metadata {
+                modified: #modified,
+                len: #len,
+                mode: #mode,
+            }
+        };
+
+        tok.to_tokens(tokens);
+    }
+}
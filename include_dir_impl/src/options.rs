@@ -0,0 +1,588 @@
+use glob::{MatchOptions, Pattern};
+use std::env;
+use std::path::{Component, Path, PathBuf};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{bracketed, Error, Ident, Lit, LitBool, LitInt, LitStr, Token};
+
+/// Glob patterns use the same rules as `Dir::find()` in the runtime crate.
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Files smaller than this are inlined unless `inline_threshold` says
+/// otherwise.
+const DEFAULT_INLINE_THRESHOLD: u64 = 1024;
+
+/// What to do with symlinks found while reading the directory.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum Symlinks {
+    /// Embed whatever the link points to.
+    Follow,
+    /// Leave the link out.
+    Skip,
+    /// Fail to compile.
+    Error,
+}
+
+/// What to do with files and directories whose names aren't valid UTF-8.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum NonUtf8Names {
+    /// Fail to compile.
+    Error,
+    /// Leave the entry out.
+    Skip,
+    /// Replace invalid sequences with U+FFFD.
+    Lossy,
+}
+
+/// What to do with files and directories which are deleted while the macro
+/// is reading them.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum VanishedFiles {
+    /// Fail to compile.
+    Error,
+    /// Leave the entry out.
+    Skip,
+}
+
+/// What to do when several directories are passed to the macro and more than
+/// one of them contains the same path.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum Duplicates {
+    /// Fail to compile.
+    Error,
+    /// Use the entry from the directory listed last.
+    Override,
+}
+
+/// What to do when a glob in the `attributes` manifest doesn't match any of
+/// the embedded files.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum UnmatchedAttributes {
+    /// Fail to compile.
+    Error,
+    /// Carry on, warning about it when the compiler lets us.
+    Warn,
+}
+
+/// What the paths passed to the macro are relative to.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) enum RelativeTo {
+    /// The directory containing the crate's `Cargo.toml`.
+    Manifest,
+    /// The directory containing the file the macro was called from.
+    File,
+}
+
+/// The arguments passed to `include_dir!()`, one or more paths followed by
+/// optional `key = value` pairs.
+pub(crate) struct Options {
+    pub paths: Vec<LitStr>,
+    pub prefix: Option<PathBuf>,
+    pub compress: bool,
+    pub include: Vec<Pattern>,
+    pub exclude: Vec<Pattern>,
+    pub symlinks: Symlinks,
+    pub include_empty_dirs: bool,
+    pub deduplicate: bool,
+    pub deny_empty: bool,
+    /// Patterns which each have to match at least one embedded file.
+    pub require: Vec<Pattern>,
+    pub duplicates: Duplicates,
+    pub non_utf8_names: NonUtf8Names,
+    pub vanished: VanishedFiles,
+    pub relative_to: RelativeTo,
+    /// The largest file which may be embedded, in bytes.
+    pub max_file_size: Option<u64>,
+    /// The most which may be embedded altogether, in bytes.
+    pub max_total_size: Option<u64>,
+    /// How many levels of sub-directories are allowed.
+    pub max_depth: Option<usize>,
+    /// Files smaller than this many bytes are written into the generated
+    /// code instead of using `include_bytes!()`.
+    pub inline_threshold: u64,
+    /// A TOML file mapping globs to attributes for the files they match.
+    pub attributes: Option<LitStr>,
+    pub unmatched_attributes: UnmatchedAttributes,
+}
+
+impl Options {
+    /// A path to include, with any `$VAR` or `${VAR}` environment variables
+    /// expanded and made relative to `CARGO_MANIFEST_DIR` (or the calling
+    /// file, with `relative_to = "file"`).
+    pub fn resolved_path(&self, path: &LitStr) -> syn::Result<PathBuf> {
+        let expanded =
+            expand_env_vars(&path.value()).map_err(|msg| Error::new(path.span(), msg))?;
+
+        let base = match self.relative_to {
+            RelativeTo::Manifest => PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()),
+            RelativeTo::File => calling_dir(path)?,
+        };
+
+        // joining with an absolute path (e.g. "$OUT_DIR/...") replaces the
+        // base entirely
+        Ok(base.join(expanded))
+    }
+
+    /// The path recorded in the generated code for an entry, given its path
+    /// relative to the included directory.
+    pub fn stored_path(&self, root_rel_path: &Path) -> PathBuf {
+        match self.prefix {
+            // joining an empty path would add a trailing slash
+            Some(ref prefix) if root_rel_path.as_os_str().is_empty() => prefix.clone(),
+            Some(ref prefix) => prefix.join(root_rel_path),
+            None => root_rel_path.to_path_buf(),
+        }
+    }
+
+    /// Should the entry at `root_rel_path` be left out of the embedded tree?
+    pub fn is_excluded(&self, root_rel_path: &Path) -> bool {
+        self.exclude
+            .iter()
+            .any(|pattern| pattern.matches_path_with(root_rel_path, MATCH_OPTIONS))
+    }
+
+    /// Should the file at `root_rel_path` be embedded, according to the
+    /// `include` allow-list?
+    pub fn is_included(&self, root_rel_path: &Path) -> bool {
+        self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|pattern| pattern.matches_path_with(root_rel_path, MATCH_OPTIONS))
+    }
+
+    /// Does `pattern` match the file stored at `stored_path`? Like the
+    /// filters, this ignores any `prefix`.
+    pub fn matches_stored(&self, pattern: &Pattern, stored_path: &Path) -> bool {
+        let path = match self.prefix {
+            Some(ref prefix) => stored_path.strip_prefix(prefix).unwrap_or(stored_path),
+            None => stored_path,
+        };
+
+        pattern.matches_path_with(path, MATCH_OPTIONS)
+    }
+
+    /// Are any filters being applied?
+    pub fn has_filters(&self) -> bool {
+        !self.include.is_empty() || !self.exclude.is_empty()
+    }
+
+    /// Should a directory with nothing in it be embedded?
+    ///
+    /// Directories which were already empty are always kept, but ones which
+    /// filtering left empty are dropped unless asked for.
+    pub fn keep_empty_dirs(&self) -> bool {
+        self.include_empty_dirs || !self.has_filters()
+    }
+}
+
+impl Parse for Options {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let path = input.parse::<LitStr>().map_err(|e| {
+            Error::new(
+                e.span(),
+                "Expected the directory's path as a string literal, e.g. `include_dir!(\"assets\")`",
+            )
+        })?;
+        let mut options = Options {
+            paths: vec![path],
+            prefix: None,
+            compress: false,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            symlinks: Symlinks::Follow,
+            include_empty_dirs: false,
+            deduplicate: false,
+            deny_empty: false,
+            require: Vec::new(),
+            duplicates: Duplicates::Error,
+            non_utf8_names: NonUtf8Names::Error,
+            vanished: VanishedFiles::Error,
+            relative_to: RelativeTo::Manifest,
+            max_file_size: None,
+            max_total_size: None,
+            max_depth: None,
+            inline_threshold: DEFAULT_INLINE_THRESHOLD,
+            attributes: None,
+            unmatched_attributes: UnmatchedAttributes::Error,
+        };
+        let mut seen: Vec<String> = Vec::new();
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            if input.peek(LitStr) {
+                let path: LitStr = input.parse()?;
+                if !seen.is_empty() {
+                    return Err(Error::new(
+                        path.span(),
+                        "Directories must be listed before any options",
+                    ));
+                }
+                options.paths.push(path);
+                continue;
+            }
+
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+
+            let name = key.to_string();
+            if seen.contains(&name) {
+                return Err(Error::new(
+                    key.span(),
+                    format!("The \"{}\" option was provided more than once", name),
+                ));
+            }
+
+            match name.as_str() {
+                "prefix" => options.prefix = parse_prefix(input)?,
+                "compress" => options.compress = parse_compress(input)?,
+                "include" => options.include = parse_globs(input)?,
+                "exclude" => options.exclude = parse_globs(input)?,
+                "symlinks" => options.symlinks = parse_symlinks(input)?,
+                "duplicates" => options.duplicates = parse_duplicates(input)?,
+                "non_utf8_names" => options.non_utf8_names = parse_non_utf8_names(input)?,
+                "vanished" => options.vanished = parse_vanished(input)?,
+                "relative_to" => options.relative_to = parse_relative_to(input)?,
+                "include_empty_dirs" => {
+                    options.include_empty_dirs = input.parse::<LitBool>()?.value
+                }
+                "deduplicate" => options.deduplicate = input.parse::<LitBool>()?.value,
+                "deny_empty" => options.deny_empty = input.parse::<LitBool>()?.value,
+                "require" => options.require = parse_globs(input)?,
+                "max_file_size" => options.max_file_size = Some(parse_size(input)?),
+                "max_total_size" => options.max_total_size = Some(parse_size(input)?),
+                "max_depth" => options.max_depth = Some(input.parse::<LitInt>()?.base10_parse()?),
+                "inline_threshold" => options.inline_threshold = parse_size(input)?,
+                "attributes" => options.attributes = Some(parse_attributes(input)?),
+                "unmatched_attributes" => {
+                    options.unmatched_attributes = parse_unmatched_attributes(input)?
+                }
+                _ => {
+                    return Err(Error::new(
+                        key.span(),
+                        format!("Unknown option, \"{}\"", name),
+                    ))
+                }
+            }
+
+            seen.push(name);
+        }
+
+        Ok(options)
+    }
+}
+
+/// Parse the `prefix = "..."` option, which must be a relative path that
+/// stays inside the included directory.
+fn parse_prefix(input: ParseStream<'_>) -> syn::Result<Option<PathBuf>> {
+    let lit: LitStr = input.parse()?;
+    let value = lit.value();
+    let mut prefix = PathBuf::new();
+
+    for component in Path::new(&value).components() {
+        match component {
+            Component::Normal(c) => prefix.push(c),
+            Component::CurDir => {}
+            _ => {
+                return Err(Error::new(
+                    lit.span(),
+                    "The prefix must be a relative path without any \"..\" components",
+                ))
+            }
+        }
+    }
+
+    if prefix.as_os_str().is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(prefix))
+    }
+}
+
+/// Parse the `compress = true` option, which needs the runtime crate to be
+/// able to decompress files.
+fn parse_compress(input: ParseStream<'_>) -> syn::Result<bool> {
+    let lit: LitBool = input.parse()?;
+
+    if lit.value && !cfg!(feature = "compression") {
+        return Err(Error::new(
+            lit.span,
+            "Compression requires the \"compression\" feature",
+        ));
+    }
+
+    Ok(lit.value)
+}
+
+/// Parse the `attributes = "..."` option, the path to a manifest of
+/// per-file attributes.
+fn parse_attributes(input: ParseStream<'_>) -> syn::Result<LitStr> {
+    let lit: LitStr = input.parse()?;
+
+    if !cfg!(feature = "attributes") {
+        return Err(Error::new(
+            lit.span(),
+            "Attributes require the \"attributes\" feature",
+        ));
+    }
+
+    Ok(lit)
+}
+
+/// Parse the `unmatched_attributes = "..."` option.
+fn parse_unmatched_attributes(input: ParseStream<'_>) -> syn::Result<UnmatchedAttributes> {
+    let lit: LitStr = input.parse()?;
+
+    match lit.value().as_str() {
+        "error" => Ok(UnmatchedAttributes::Error),
+        "warn" => Ok(UnmatchedAttributes::Warn),
+        other => Err(Error::new(
+            lit.span(),
+            format!(
+                "Expected \"error\" or \"warn\" for unmatched_attributes, found \"{}\"",
+                other
+            ),
+        )),
+    }
+}
+
+/// Parse a list of glob patterns, `["*.txt", "**/.DS_Store"]`.
+fn parse_globs(input: ParseStream<'_>) -> syn::Result<Vec<Pattern>> {
+    let content;
+    bracketed!(content in input);
+
+    Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?
+        .iter()
+        .map(|lit| {
+            Pattern::new(&lit.value()).map_err(|e| {
+                Error::new(
+                    lit.span(),
+                    format!("\"{}\" isn't a valid glob pattern: {}", lit.value(), e),
+                )
+            })
+        })
+        .collect()
+}
+
+/// Parse the `symlinks = "..."` option.
+fn parse_symlinks(input: ParseStream<'_>) -> syn::Result<Symlinks> {
+    let lit: LitStr = input.parse()?;
+
+    match lit.value().as_str() {
+        "follow" => Ok(Symlinks::Follow),
+        "skip" => Ok(Symlinks::Skip),
+        "error" => Ok(Symlinks::Error),
+        other => Err(Error::new(
+            lit.span(),
+            format!(
+                "Expected \"follow\", \"skip\", or \"error\" for symlinks, found \"{}\"",
+                other
+            ),
+        )),
+    }
+}
+
+/// Parse the `vanished = "..."` option.
+fn parse_vanished(input: ParseStream<'_>) -> syn::Result<VanishedFiles> {
+    let lit: LitStr = input.parse()?;
+
+    match lit.value().as_str() {
+        "error" => Ok(VanishedFiles::Error),
+        "skip" => Ok(VanishedFiles::Skip),
+        other => Err(Error::new(
+            lit.span(),
+            format!(
+                "Expected \"error\" or \"skip\" for vanished, found \"{}\"",
+                other
+            ),
+        )),
+    }
+}
+
+/// The directory containing the file `path` was written in.
+fn calling_dir(path: &LitStr) -> syn::Result<PathBuf> {
+    let unknown = || {
+        Error::new(
+            path.span(),
+            "Couldn't work out which file called the macro, so the path can't be relative to it. Use `relative_to = \"manifest\"` instead",
+        )
+    };
+
+    // this is relative to the compiler's working directory, which is ours
+    // too
+    let file = path.span().unwrap().local_file().ok_or_else(unknown)?;
+    let file = env::current_dir().map_err(|_| unknown())?.join(file);
+
+    file.parent().map(Path::to_path_buf).ok_or_else(unknown)
+}
+
+/// Parse the `relative_to = "..."` option.
+fn parse_relative_to(input: ParseStream<'_>) -> syn::Result<RelativeTo> {
+    let lit: LitStr = input.parse()?;
+
+    match lit.value().as_str() {
+        "manifest" => Ok(RelativeTo::Manifest),
+        "file" => Ok(RelativeTo::File),
+        other => Err(Error::new(
+            lit.span(),
+            format!(
+                "Expected \"manifest\" or \"file\" for relative_to, found \"{}\"",
+                other
+            ),
+        )),
+    }
+}
+
+/// Parse the `duplicates = "..."` option.
+fn parse_duplicates(input: ParseStream<'_>) -> syn::Result<Duplicates> {
+    let lit: LitStr = input.parse()?;
+
+    match lit.value().as_str() {
+        "error" => Ok(Duplicates::Error),
+        "override" => Ok(Duplicates::Override),
+        other => Err(Error::new(
+            lit.span(),
+            format!(
+                "Expected \"error\" or \"override\" for duplicates, found \"{}\"",
+                other
+            ),
+        )),
+    }
+}
+
+/// Parse the `non_utf8_names = "..."` option.
+fn parse_non_utf8_names(input: ParseStream<'_>) -> syn::Result<NonUtf8Names> {
+    let lit: LitStr = input.parse()?;
+
+    match lit.value().as_str() {
+        "error" => Ok(NonUtf8Names::Error),
+        "skip" => Ok(NonUtf8Names::Skip),
+        "lossy" => Ok(NonUtf8Names::Lossy),
+        other => Err(Error::new(
+            lit.span(),
+            format!(
+                "Expected \"error\", \"skip\", or \"lossy\" for non_utf8_names, found \"{}\"",
+                other
+            ),
+        )),
+    }
+}
+
+/// Parse a size limit, either a number of bytes (`1048576`) or a string with
+/// a unit (`"1MB"`, `"1.5 GiB"`).
+///
+/// Units are case-insensitive and, to match how the runtime crate displays
+/// sizes, powers of 1024 whether or not they have an "i".
+fn parse_size(input: ParseStream<'_>) -> syn::Result<u64> {
+    let lit: Lit = input.parse()?;
+
+    match lit {
+        Lit::Int(int) => int.base10_parse(),
+        Lit::Str(s) => size_from_str(&s.value()).ok_or_else(|| {
+            Error::new(
+                s.span(),
+                format!(
+                    "\"{}\" isn't a valid size, expected something like \"10MB\"",
+                    s.value()
+                ),
+            )
+        }),
+        other => Err(Error::new(
+            other.span(),
+            "Expected a size in bytes or a string like \"10MB\"",
+        )),
+    }
+}
+
+fn size_from_str(raw: &str) -> Option<u64> {
+    let raw = raw.trim();
+    let split = raw
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(raw.len());
+    let (number, unit) = raw.split_at(split);
+
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        _ => return None,
+    };
+
+    if let Ok(whole) = number.parse::<u64>() {
+        return whole.checked_mul(multiplier);
+    }
+
+    let fractional: f64 = number.parse().ok()?;
+    Some((fractional * multiplier as f64) as u64)
+}
+
+/// Display a number of bytes the same way the runtime crate's `Dir::tree()`
+/// does.
+pub(crate) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Replace each `$VAR` or `${VAR}` with the variable's value at compile time.
+/// A `$` which isn't followed by a variable name is left as-is.
+fn expand_env_vars(raw: &str) -> Result<String, String> {
+    let mut expanded = String::with_capacity(raw.len());
+    let mut rest = raw;
+
+    while let Some(dollar) = rest.find('$') {
+        expanded.push_str(&rest[..dollar]);
+        rest = &rest[dollar + 1..];
+
+        let (name, remainder) = if let Some(braced) = rest.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => return Err(format!("Unterminated \"${{\" in \"{}\"", raw)),
+            }
+        } else {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
+        };
+
+        if name.is_empty() {
+            expanded.push('$');
+            continue;
+        }
+
+        match env::var(name) {
+            Ok(value) => expanded.push_str(&value),
+            Err(_) => {
+                return Err(format!(
+                    "Unable to expand \"{}\", the \"{}\" environment variable isn't set",
+                    raw, name
+                ))
+            }
+        }
+        rest = remainder;
+    }
+
+    expanded.push_str(rest);
+    Ok(expanded)
+}
@@ -0,0 +1,60 @@
+//! Support for the `deny_empty = true` and `require = [...]` options.
+
+use crate::dir::Dir;
+use crate::file::path_to_string;
+use crate::options::Options;
+
+/// The most file names to list when a requirement isn't met.
+const MAX_LISTED: usize = 10;
+
+/// Make sure everything the caller said to expect was actually embedded.
+pub(crate) fn check(dir: &Dir, options: &Options) -> Result<(), String> {
+    let files = dir.all_files();
+
+    if options.deny_empty && files.is_empty() {
+        return Err(String::from(
+            "Expected at least one file because of `deny_empty = true`, but nothing was embedded",
+        ));
+    }
+
+    let missing: Vec<_> = options
+        .require
+        .iter()
+        .filter(|pattern| {
+            !files
+                .iter()
+                .any(|f| options.matches_stored(pattern, f.root_rel_path()))
+        })
+        .map(|pattern| format!("\"{}\"", pattern.as_str()))
+        .collect();
+
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let found = match files.len() {
+        0 => String::from("nothing was embedded"),
+        n => {
+            let mut names: Vec<_> = files
+                .iter()
+                .map(|f| path_to_string(f.root_rel_path()))
+                .collect();
+            names.sort_unstable();
+            let mut names: Vec<_> = names
+                .iter()
+                .take(MAX_LISTED)
+                .map(|name| format!("\"{}\"", name))
+                .collect();
+            if n > MAX_LISTED {
+                names.push(format!("and {} more", n - MAX_LISTED));
+            }
+            format!("found {}", names.join(", "))
+        }
+    };
+
+    Err(format!(
+        "Expected files matching {}, but {}",
+        missing.join(", "),
+        found
+    ))
+}
@@ -0,0 +1,17 @@
+use anyhow::{format_err, Error};
+use std::env;
+
+/// The `SOURCE_DATE_EPOCH` used for reproducible builds, if set.
+///
+/// See <https://reproducible-builds.org/specs/source-date-epoch/>.
+pub(crate) fn source_date_epoch() -> Result<Option<u64>, Error> {
+    match env::var("SOURCE_DATE_EPOCH") {
+        Ok(raw) => raw.trim().parse().map(Some).map_err(|_| {
+            format_err!(
+                "SOURCE_DATE_EPOCH should be a number of seconds since the Unix epoch, found \"{}\"",
+                raw
+            )
+        }),
+        Err(_) => Ok(None),
+    }
+}
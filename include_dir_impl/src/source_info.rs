@@ -0,0 +1,17 @@
+use crate::source_date_epoch::source_date_epoch;
+use anyhow::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// When the directory is being embedded, in seconds since the Unix epoch.
+///
+/// `SOURCE_DATE_EPOCH` takes priority so reproducible builds get the same
+/// output every time.
+pub(crate) fn embedded_at() -> Result<u64, Error> {
+    match source_date_epoch()? {
+        Some(epoch) => Ok(epoch),
+        None => Ok(SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)),
+    }
+}
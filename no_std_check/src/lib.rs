@@ -0,0 +1,36 @@
+//! A `#![no_std]` crate using `include_dir`, to make sure it still builds
+//! without the standard library.
+//!
+//! Building this for a bare-metal target (e.g.
+//! `cargo build -p include_dir_no_std_check --target thumbv7m-none-eabi`)
+//! fails if anything in the core API starts depending on `std`.
+
+#![no_std]
+
+use include_dir::{include_dir, Dir, DirEntry};
+
+/// The same tree the `include_dir` integration tests use.
+pub static TREE: Dir<'_> = include_dir!("../include_dir/tests/fixtures/tree");
+
+/// Look up a file's text by path.
+pub fn read(path: &str) -> Option<&'static str> {
+    TREE.get_file(path)?.contents_utf8()
+}
+
+/// The number of SQL files, found by walking the whole tree.
+pub fn sql_files() -> usize {
+    TREE.files_with_extension("sql").count()
+}
+
+/// The number of directories, found by walking the whole tree.
+pub fn dirs() -> usize {
+    TREE.walk()
+        .filter(|entry| matches!(entry, DirEntry::Dir(_)))
+        .count()
+}
+
+/// The path of the first file containing `needle`.
+pub fn first_file_containing(needle: &str) -> Option<&'static str> {
+    let (file, _) = TREE.find_str_in_contents(needle).next()?;
+    Some(file.path())
+}
@@ -0,0 +1,19 @@
+use include_dir_no_std_check::{dirs, first_file_containing, read, sql_files};
+
+#[test]
+fn lookups() {
+    assert!(read("README.md").unwrap().starts_with("# Fixture tree"));
+    assert!(read("migrations\\2023\\001_posts.sql").is_some());
+    assert!(read("../README.md").is_none());
+    assert!(read("/README.md").is_none());
+}
+
+#[test]
+fn walking() {
+    assert_eq!(sql_files(), 3);
+    assert_eq!(dirs(), 3);
+    assert_eq!(
+        first_file_containing("ADD COLUMN"),
+        Some("migrations/2023/q1/002_titles.sql")
+    );
+}